@@ -0,0 +1,67 @@
+//! Expose the version of the Simfony compiler as `SIMFONY_VERSION`
+//! and generate the index of jets in `$OUT_DIR/jet_index.rs`.
+
+use std::path::Path;
+use std::{env, fs};
+
+use simfony::simplicity::jet::Elements;
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    let lock = fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let version = simfony_version(&lock).unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=SIMFONY_VERSION={version}");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR should be set by cargo");
+    fs::write(Path::new(&out_dir).join("jet_index.rs"), jet_index())
+        .expect("jet index should be writable");
+}
+
+/// Generate the list of jet names and type signatures,
+/// in the same way as the jet documentation of `simfony-as-rust`.
+fn jet_index() -> String {
+    let mut jets = Elements::ALL
+        .iter()
+        .map(|jet| {
+            let source = simfony::jet::source_type(*jet)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            let target = simfony::jet::target_type(*jet);
+            (jet.to_string(), format!("fn({source}) -> {target}"))
+        })
+        .collect::<Vec<_>>();
+    jets.sort();
+
+    let mut index = String::from("pub const JETS: &[(&str, &str)] = &[\n");
+    for (name, signature) in jets {
+        index.push_str(&format!("    ({name:?}, {signature:?}),\n"));
+    }
+    index.push_str("];\n");
+    index
+}
+
+/// Find the version of the `simfony` package in the given lock file.
+///
+/// Git dependencies include the abbreviated commit hash.
+fn simfony_version(lock: &str) -> Option<String> {
+    let package = lock
+        .split("[[package]]")
+        .find(|package| package.lines().any(|line| line == "name = \"simfony\""))?;
+    let field = |name: &str| {
+        package.lines().find_map(|line| {
+            line.strip_prefix(name)
+                .and_then(|rest| rest.strip_prefix(" = \""))
+                .and_then(|rest| rest.strip_suffix('"'))
+        })
+    };
+    let version = field("version")?;
+    let commit = field("source")
+        .and_then(|source| source.rsplit_once('#'))
+        .map(|(_, commit)| &commit[..commit.len().min(7)]);
+    match commit {
+        Some(commit) => Some(format!("{version} ({commit})")),
+        None => Some(version.to_string()),
+    }
+}
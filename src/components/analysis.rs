@@ -1,7 +1,7 @@
 use leptos::*;
-use std::str::FromStr;
 use std::sync::Arc;
 
+use crate::jet;
 use crate::util;
 use crate::util::Expression;
 
@@ -33,8 +33,7 @@ const MILLISECONDS_PER_WU: f64 = 0.5 / 1000.0;
 #[component]
 fn AnalysisInner(expression: Arc<Expression>, run_result: Result<String, String>) -> impl IntoView {
     let bounds = expression.bounds();
-    // FIXME: Add conversion method to simplicity::Cost
-    let milli_weight = u32::from_str(&bounds.cost.to_string()).unwrap();
+    let milli_weight = jet::milliweight(bounds.cost);
     let weight = milli_weight.saturating_add(999) / 1000;
     let virtual_size = weight.div_ceil(4);
     let size = weight; // Simplicity programs are Taproot witness data
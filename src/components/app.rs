@@ -1,19 +1,44 @@
-use leptos::{component, provide_context, view, IntoView, RwSignal};
+use std::time::Duration;
 
-use super::program_window::{select_example, Program, ProgramWindow, Runtime};
+use gloo_timers::callback::Interval;
+use leptos::leptos_dom::helpers::TimeoutHandle;
+use leptos::{
+    component, create_effect, on_cleanup, provide_context, set_timeout_with_handle, store_value,
+    view, IntoView, RwSignal, SignalSet, SignalWith, SignalWithUntracked,
+};
+
+use super::program_window::{
+    program_from_url_hash, select_example, Program, ProgramWindow, Runtime,
+};
 use crate::components::footer::Footer;
-use crate::components::run_window::{HashCount, KeyCount, RunWindow, SignedData, TxEnv};
-use crate::components::state::LocalStorage;
+use crate::components::notifications::NotificationBar;
+use crate::components::restore_banner::RestoreBanner;
+use crate::components::run_window::{
+    Breakpoints, ComputedHashes, HashCount, KeyCount, NamedKeys, RootKey, RunWindow, SignedData,
+    TxEnv,
+};
+use crate::components::shortcuts_modal::{KeyboardShortcutsModal, ShortcutsModalOpen};
+use crate::components::split_pane::SplitPane;
+use crate::components::state::{autosave_program, LocalStorage};
+use crate::components::theme_toggle::ActiveTheme;
+use crate::components::toast::ToastProvider;
 use crate::examples;
+use crate::routing::{route_from_location, Route};
 use crate::transaction::TxParams;
 use crate::util::{HashedData, SigningKeys};
 
+/// Time between two automatic snapshots of the program, in milliseconds.
+const AUTOSAVE_INTERVAL_MS: u32 = 30_000;
+
 #[derive(Copy, Clone, Debug, Default)]
 pub struct ActiveRunTab(pub RwSignal<&'static str>);
 
 #[component]
 pub fn App() -> impl IntoView {
-    let program = Program::load_from_storage().unwrap_or_default();
+    let program = program_from_url_hash()
+        .map(Program::new)
+        .or_else(Program::load_from_storage)
+        .unwrap_or_default();
     provide_context(program);
     let tx_params = TxParams::load_from_storage().unwrap_or_default();
     let tx_env = TxEnv::new(program, tx_params);
@@ -22,17 +47,56 @@ pub fn App() -> impl IntoView {
     provide_context(SignedData::new(tx_env.lazy_env));
     provide_context(HashedData::load_from_storage().unwrap_or_default());
     provide_context(KeyCount::load_from_storage().unwrap_or_default());
+    provide_context(NamedKeys::default());
+    provide_context(RootKey::default());
     provide_context(HashCount::load_from_storage().unwrap_or_default());
-    provide_context(Runtime::new(program, tx_env.lazy_env));
+    provide_context(ComputedHashes::default());
+    let breakpoints = Breakpoints::default();
+    provide_context(breakpoints);
+    provide_context(Runtime::new(program, tx_env.lazy_env, breakpoints));
     provide_context(ActiveRunTab::default());
+    provide_context(ActiveTheme::default());
+    provide_context(ShortcutsModalOpen::default());
 
-    if program.is_empty() {
-        select_example(examples::get("✍️️ P2PK").expect("P2PK example should exist"))
+    match route_from_location() {
+        Some(Route::Example(name)) => {
+            select_example(examples::get(name).expect("routed example should exist"))
+        }
+        Some(Route::Source(text)) => program.text.set(text),
+        None if program.is_empty() => {
+            select_example(examples::get("✍️️ P2PK").expect("P2PK example should exist"))
+        }
+        None => {}
     }
 
+    let autosave_handle = store_value(None::<TimeoutHandle>);
+    create_effect(move |_| {
+        // Store the program once the user stops typing
+        program.text.with(|_text| ());
+        if let Some(handle) = autosave_handle.get_value() {
+            handle.clear();
+        }
+        let handle = set_timeout_with_handle(
+            move || program.store_in_storage(),
+            Duration::from_millis(500),
+        )
+        .ok();
+        autosave_handle.set_value(handle);
+    });
+    let autosave_interval = Interval::new(AUTOSAVE_INTERVAL_MS, move || {
+        program
+            .text
+            .with_untracked(|text| autosave_program(js_sys::Date::now() as u64, text));
+    });
+    on_cleanup(move || drop(autosave_interval));
+
     view! {
-        <ProgramWindow />
-        <RunWindow />
-        <Footer />
+        <ToastProvider>
+            <NotificationBar />
+            <RestoreBanner />
+            <SplitPane left=|| view! { <ProgramWindow /> } right=|| view! { <RunWindow /> } />
+            <Footer />
+            <KeyboardShortcutsModal />
+        </ToastProvider>
     }
 }
@@ -0,0 +1,91 @@
+use leptos::wasm_bindgen::JsCast;
+use leptos::{
+    component, create_rw_signal, document, ev, spawn_local, view, IntoView, Signal, SignalGet,
+    SignalGetUntracked, SignalSet, View,
+};
+use wasm_bindgen_futures::JsFuture;
+
+/// How long the result of copying is shown, in milliseconds.
+pub const COPY_RESULT_DURATION_MS: u32 = 1500;
+
+/// Copy the given text to the clipboard.
+///
+/// Fall back to selecting the text in a hidden `<textarea>` if the clipboard API
+/// is unavailable or access is denied. Return whether copying succeeded.
+pub async fn copy_text(text: &str) -> bool {
+    let clipboard = web_sys::window()
+        .as_ref()
+        .map(web_sys::Window::navigator)
+        .as_ref()
+        .map(web_sys::Navigator::clipboard)
+        // The clipboard API is missing in insecure contexts
+        .filter(|clipboard| !clipboard.is_undefined());
+    if let Some(clipboard) = clipboard {
+        if JsFuture::from(clipboard.write_text(text)).await.is_ok() {
+            return true;
+        }
+    }
+    copy_with_textarea(text)
+}
+
+/// Copy the given text using the legacy `execCommand` API.
+fn copy_with_textarea(text: &str) -> bool {
+    let document = document();
+    let Some(body) = document.body() else {
+        return false;
+    };
+    let Ok(textarea) = document.create_element("textarea") else {
+        return false;
+    };
+    let textarea: web_sys::HtmlTextAreaElement = textarea.unchecked_into();
+    textarea.set_value(text);
+    let _result = textarea.set_attribute("readonly", "");
+    let _result = textarea.set_attribute("style", "position: fixed; top: 0; opacity: 0;");
+    if body.append_child(&textarea).is_err() {
+        return false;
+    }
+    textarea.select();
+    let copied = document
+        .unchecked_ref::<web_sys::HtmlDocument>()
+        .exec_command("copy")
+        .unwrap_or(false);
+    textarea.remove();
+    copied
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum CopyState {
+    Idle,
+    Copied,
+    Failed,
+}
+
+/// Icon button that copies a hex value and briefly confirms the result.
+#[component]
+pub fn CopyButton(#[prop(into)] content: Signal<String>) -> impl IntoView {
+    let state = create_rw_signal(CopyState::Idle);
+    let button_click = move |_event: ev::MouseEvent| {
+        let text = content.get_untracked();
+        spawn_local(async move {
+            match copy_text(&text).await {
+                true => state.set(CopyState::Copied),
+                false => state.set(CopyState::Failed),
+            }
+            gloo_timers::future::TimeoutFuture::new(COPY_RESULT_DURATION_MS).await;
+            state.set(CopyState::Idle);
+        });
+    };
+    let label = move || -> View {
+        match state.get() {
+            CopyState::Idle => view! { <i class="far fa-copy"></i> }.into_view(),
+            CopyState::Copied => "✓ Copied".into_view(),
+            CopyState::Failed => "Failed to copy".into_view(),
+        }
+    };
+
+    view! {
+        <button class="copy-button" type="button" title="Copy" on:click=button_click>
+            {label}
+        </button>
+    }
+}
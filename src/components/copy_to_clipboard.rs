@@ -1,4 +1,9 @@
-use leptos::{component, create_rw_signal, ev, view, with, Children, IntoView, Signal, SignalSet};
+use leptos::{
+    component, create_rw_signal, ev, spawn_local, view, Children, IntoView, Signal,
+    SignalGetUntracked, SignalSet,
+};
+
+use crate::components::copy_button::{copy_text, COPY_RESULT_DURATION_MS};
 
 #[component]
 pub fn CopyToClipboard(
@@ -7,37 +12,33 @@ pub fn CopyToClipboard(
     #[prop(default = false)] tooltip_below: bool,
     children: Children,
 ) -> impl IntoView {
-    web_sys::window()
-        .as_ref()
-        .map(web_sys::Window::navigator)
-        .as_ref()
-        .map(web_sys::Navigator::clipboard)
-        .map(|clipboard| {
-            let tooltip_text = create_rw_signal("Copy");
-
-            let button_click = move |_event: ev::MouseEvent| {
-                let _promise = with!(|content| clipboard.write_text(content));
-                tooltip_text.set("Copied!");
-            };
-            let button_mouseout = move |_event: ev::MouseEvent| {
-                tooltip_text.set("Copy");
-            };
-            let tooltip_class = match tooltip_below {
-                false => "tooltip-above",
-                true => "tooltip-below",
-            };
+    let tooltip_text = create_rw_signal("Copy");
 
-            view! {
-                <div class=tooltip_class>
-                    <button
-                        class=class
-                        on:click=button_click
-                        on:mouseout=button_mouseout
-                    >
-                        <span class="tooltip-text">{tooltip_text}</span>
-                        {children()}
-                    </button>
-                </div>
+    let button_click = move |_event: ev::MouseEvent| {
+        let text = content.get_untracked();
+        spawn_local(async move {
+            match copy_text(&text).await {
+                true => tooltip_text.set("Copied!"),
+                false => tooltip_text.set("Failed to copy"),
             }
-        })
+            gloo_timers::future::TimeoutFuture::new(COPY_RESULT_DURATION_MS).await;
+            tooltip_text.set("Copy");
+        });
+    };
+    let tooltip_class = match tooltip_below {
+        false => "tooltip-above",
+        true => "tooltip-below",
+    };
+
+    view! {
+        <div class=tooltip_class>
+            <button
+                class=class
+                on:click=button_click
+            >
+                <span class="tooltip-text">{tooltip_text}</span>
+                {children()}
+            </button>
+        </div>
+    }
 }
@@ -0,0 +1,256 @@
+use leptos::{component, view, CollectView, IntoView, Signal, SignalWith};
+
+/// Edit operation that turns the old lines into the new lines.
+///
+/// Lines are identified by their index, starting at 0.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Edit {
+    /// The old line at the first index equals the new line at the second index.
+    Equal(usize, usize),
+    /// The old line at the index is deleted.
+    Delete(usize),
+    /// The new line at the index is inserted.
+    Insert(usize),
+}
+
+/// Compute a shortest edit script from the old lines to the new lines.
+///
+/// This is the greedy algorithm of Myers, "An O(ND) Difference Algorithm and Its Variations".
+/// Deletions come before insertions where both are possible.
+pub fn diff_lines<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    let n = old.len() as isize;
+    let m = new.len() as isize;
+    let max = n + m;
+    // Diagonal k is stored at index k + offset, for k in -max - 1 ..= max + 1
+    let offset = max + 1;
+    let index = |k: isize| (k + offset) as usize;
+    let mut furthest = vec![0isize; 2 * max as usize + 3];
+    let mut trace = vec![];
+
+    'search: for d in 0..=max {
+        trace.push(furthest.clone());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && furthest[index(k - 1)] < furthest[index(k + 1)]) {
+                furthest[index(k + 1)]
+            } else {
+                furthest[index(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            furthest[index(k)] = x;
+            if n <= x && m <= y {
+                break 'search;
+            }
+        }
+    }
+
+    let mut edits = vec![];
+    let (mut x, mut y) = (n, m);
+    for (d, furthest) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let previous_k = if k == -d || (k != d && furthest[index(k - 1)] < furthest[index(k + 1)]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = furthest[index(previous_k)];
+        let previous_y = previous_x - previous_k;
+        while previous_x < x && previous_y < y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(x as usize, y as usize));
+        }
+        if 0 < d {
+            if x == previous_x {
+                edits.push(Edit::Insert(previous_y as usize));
+            } else {
+                edits.push(Edit::Delete(previous_x as usize));
+            }
+        }
+        x = previous_x;
+        y = previous_y;
+    }
+    edits.reverse();
+    edits
+}
+
+/// Row of a side-by-side diff.
+///
+/// Each side holds the line number, starting at 1, and the text of the line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiffRow {
+    pub old: Option<(usize, String)>,
+    pub new: Option<(usize, String)>,
+    pub changed: bool,
+}
+
+/// Compute the side-by-side diff of two texts.
+///
+/// Deleted lines are shown next to the lines that were inserted in their place.
+pub fn side_by_side(old: &str, new: &str) -> Vec<DiffRow> {
+    let old_lines = old.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+    let old_line = |index: usize| Some((index + 1, old_lines[index].to_string()));
+    let new_line = |index: usize| Some((index + 1, new_lines[index].to_string()));
+
+    let mut rows = vec![];
+    let mut deleted = vec![];
+    let mut inserted = vec![];
+    let flush = |rows: &mut Vec<DiffRow>, deleted: &mut Vec<usize>, inserted: &mut Vec<usize>| {
+        for row in 0..deleted.len().max(inserted.len()) {
+            rows.push(DiffRow {
+                old: deleted.get(row).and_then(|index| old_line(*index)),
+                new: inserted.get(row).and_then(|index| new_line(*index)),
+                changed: true,
+            });
+        }
+        deleted.clear();
+        inserted.clear();
+    };
+
+    for edit in diff_lines(&old_lines, &new_lines) {
+        match edit {
+            Edit::Equal(old_index, new_index) => {
+                flush(&mut rows, &mut deleted, &mut inserted);
+                rows.push(DiffRow {
+                    old: old_line(old_index),
+                    new: new_line(new_index),
+                    changed: false,
+                });
+            }
+            Edit::Delete(index) => deleted.push(index),
+            Edit::Insert(index) => inserted.push(index),
+        }
+    }
+    flush(&mut rows, &mut deleted, &mut inserted);
+    rows
+}
+
+/// Two-column table that shows the changes from the old text to the new text.
+#[component]
+pub fn DiffViewer(
+    #[prop(into)] old_title: Signal<String>,
+    #[prop(into)] old: Signal<String>,
+    #[prop(into)] new_title: Signal<String>,
+    #[prop(into)] new: Signal<String>,
+) -> impl IntoView {
+    let rows = move || {
+        old.with(|old| new.with(|new| side_by_side(old, new)))
+            .into_iter()
+            .map(|row| {
+                let (old_class, new_class) = match row.changed {
+                    true => ("diff-deleted", "diff-added"),
+                    false => ("", ""),
+                };
+                let (old_number, old_text) = row.old.unzip();
+                let (new_number, new_text) = row.new.unzip();
+                view! {
+                    <tr>
+                        <td class="diff-line-number">{old_number}</td>
+                        <td class=old_class>{old_text}</td>
+                        <td class="diff-line-number">{new_number}</td>
+                        <td class=new_class>{new_text}</td>
+                    </tr>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <table class="diff-viewer">
+            <thead>
+                <tr>
+                    <th colspan="2">{old_title}</th>
+                    <th colspan="2">{new_title}</th>
+                </tr>
+            </thead>
+            <tbody>
+                {rows}
+            </tbody>
+        </table>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reconstruct the old and the new sequence from the edit script.
+    fn apply(old: &[char], new: &[char], edits: &[Edit]) -> (Vec<char>, Vec<char>) {
+        let mut from_old = vec![];
+        let mut from_new = vec![];
+        for edit in edits {
+            match *edit {
+                Edit::Equal(old_index, new_index) => {
+                    assert_eq!(old[old_index], new[new_index]);
+                    from_old.push(old[old_index]);
+                    from_new.push(new[new_index]);
+                }
+                Edit::Delete(index) => from_old.push(old[index]),
+                Edit::Insert(index) => from_new.push(new[index]),
+            }
+        }
+        (from_old, from_new)
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn shortest_edit_script() {
+        let cases = [
+            ("", "", 0),
+            ("abc", "", 3),
+            ("", "abc", 3),
+            ("abc", "abc", 0),
+            ("abcabba", "cbabac", 5),
+            ("abcd", "acbd", 2),
+        ];
+        for (old, new, edit_count) in cases {
+            let old = old.chars().collect::<Vec<_>>();
+            let new = new.chars().collect::<Vec<_>>();
+            let edits = diff_lines(&old, &new);
+            assert_eq!((old.clone(), new.clone()), apply(&old, &new, &edits));
+            let changes = edits
+                .iter()
+                .filter(|edit| !matches!(edit, Edit::Equal(..)))
+                .count();
+            assert_eq!(edit_count, changes, "{old:?} -> {new:?}");
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn side_by_side_rows() {
+        let old = "fn main() {\n    let x: u8 = 1;\n}";
+        let new = "fn main() {\n    let x: u8 = 2;\n    let y: u8 = 3;\n}";
+        let line = |number: usize, text: &str| Some((number, text.to_string()));
+        assert_eq!(
+            vec![
+                DiffRow {
+                    old: line(1, "fn main() {"),
+                    new: line(1, "fn main() {"),
+                    changed: false,
+                },
+                DiffRow {
+                    old: line(2, "    let x: u8 = 1;"),
+                    new: line(2, "    let x: u8 = 2;"),
+                    changed: true,
+                },
+                DiffRow {
+                    old: None,
+                    new: line(3, "    let y: u8 = 3;"),
+                    changed: true,
+                },
+                DiffRow {
+                    old: line(3, "}"),
+                    new: line(4, "}"),
+                    changed: false,
+                },
+            ],
+            side_by_side(old, new)
+        );
+    }
+}
@@ -0,0 +1,594 @@
+use std::cmp::Ordering;
+
+use leptos::{
+    component, create_node_ref, create_rw_signal, ev, event_target_value, html, view, with,
+    Callable, Callback, CollectView, IntoView, RwSignal, Show, Signal, SignalGet,
+    SignalGetUntracked, SignalSet, SignalUpdate, SignalWith, SignalWithUntracked, View,
+};
+
+use crate::jet_index;
+use crate::linter::{LintSeverity, LintWarning};
+
+const TAB_KEY: u32 = 9;
+
+/// Kind of token in Simfony source code, for syntax highlighting.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TokenKind {
+    Keyword,
+    Type,
+    Jet,
+    Macro,
+    Number,
+    Comment,
+    Plain,
+}
+
+impl TokenKind {
+    fn class(self) -> &'static str {
+        match self {
+            TokenKind::Keyword => "token-keyword",
+            TokenKind::Type => "token-type",
+            TokenKind::Jet => "token-jet",
+            TokenKind::Macro => "token-macro",
+            TokenKind::Number => "token-number",
+            TokenKind::Comment => "token-comment",
+            TokenKind::Plain => "",
+        }
+    }
+}
+
+const KEYWORDS: [&str; 14] = [
+    "fn", "let", "match", "mod", "const", "type", "witness", "param", "true", "false", "None",
+    "Some", "Left", "Right",
+];
+
+const TYPES: [&str; 13] = [
+    "bool", "u1", "u2", "u4", "u8", "u16", "u32", "u64", "u128", "u256", "Either", "Option", "List",
+];
+
+/// Return the length in bytes of the longest prefix of `s` whose characters satisfy `f`.
+fn prefix_len(s: &str, f: impl Fn(char) -> bool) -> usize {
+    s.find(|c| !f(c)).unwrap_or(s.len())
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Split Simfony source code into highlighted tokens.
+///
+/// The concatenation of all tokens is equal to the input text.
+pub fn tokenize(text: &str) -> Vec<(TokenKind, &str)> {
+    let mut ranges: Vec<(TokenKind, usize, usize)> = vec![];
+    let mut start = 0;
+
+    while start < text.len() {
+        let rest = &text[start..];
+        let (kind, len) = if rest.starts_with("//") {
+            (TokenKind::Comment, prefix_len(rest, |c| c != '\n'))
+        } else if rest.starts_with("/*") {
+            let len = rest.find("*/").map(|end| end + 2).unwrap_or(rest.len());
+            (TokenKind::Comment, len)
+        } else if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            (TokenKind::Number, prefix_len(rest, is_ident_char))
+        } else if rest.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+            let ident_len = prefix_len(rest, is_ident_char);
+            let ident = &rest[..ident_len];
+            let after = &rest[ident_len..];
+            if ident == "jet" && after.starts_with("::") {
+                let name_len = prefix_len(&after[2..], is_ident_char);
+                (TokenKind::Jet, ident_len + 2 + name_len)
+            } else if after.starts_with('!') {
+                (TokenKind::Macro, ident_len + 1)
+            } else if KEYWORDS.contains(&ident) {
+                (TokenKind::Keyword, ident_len)
+            } else if TYPES.contains(&ident) {
+                (TokenKind::Type, ident_len)
+            } else {
+                (TokenKind::Plain, ident_len)
+            }
+        } else {
+            let len = rest.chars().next().map(char::len_utf8).unwrap_or(1);
+            (TokenKind::Plain, len)
+        };
+
+        match ranges.last_mut() {
+            Some((TokenKind::Plain, _, end)) if kind == TokenKind::Plain => *end += len,
+            _ => ranges.push((kind, start, start + len)),
+        }
+        start += len;
+    }
+
+    ranges
+        .into_iter()
+        .map(|(kind, start, end)| (kind, &text[start..end]))
+        .collect()
+}
+
+/// Return the partially typed jet name that ends at the cursor.
+///
+/// Return `None` unless the text before the cursor ends with `jet::` and a partial name.
+pub fn jet_completion_prefix(text: &str, cursor: usize) -> Option<&str> {
+    let before = text.get(..cursor)?;
+    let name_start = before.trim_end_matches(is_ident_char).len();
+    let path = before[..name_start].strip_suffix("jet::")?;
+    if path.ends_with(is_ident_char) {
+        return None;
+    }
+    Some(&before[name_start..])
+}
+
+/// Convert an offset in UTF-16 code units, which the DOM uses for the selection of a `<textarea>`,
+/// into a byte offset into the text.
+///
+/// Return `None` if the offset is inside a character or beyond the end of the text.
+pub fn byte_offset(text: &str, utf16_offset: usize) -> Option<usize> {
+    let mut units = 0;
+    for (index, c) in text.char_indices() {
+        match units.cmp(&utf16_offset) {
+            Ordering::Less => units += c.len_utf16(),
+            Ordering::Equal => return Some(index),
+            Ordering::Greater => return None,
+        }
+    }
+    (units == utf16_offset).then_some(text.len())
+}
+
+/// Convert a byte offset into the text into an offset in UTF-16 code units.
+pub fn utf16_offset(text: &str, byte_offset: usize) -> usize {
+    text[..byte_offset].encode_utf16().count()
+}
+
+/// Return the CSS class that underlines a span with a lint of the given severity.
+fn lint_span_class(severity: LintSeverity) -> &'static str {
+    match severity {
+        LintSeverity::Warning => "warning-span",
+        LintSeverity::Error => "error-span",
+    }
+}
+
+/// Return the CSS class that marks a line number with a lint of the given severity.
+fn lint_line_class(severity: LintSeverity) -> &'static str {
+    match severity {
+        LintSeverity::Warning => "warning-line",
+        LintSeverity::Error => "error-line",
+    }
+}
+
+/// Error that is attached to a span of the program text.
+///
+/// Lines and columns start at 1. The column range is exclusive at the end.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ErrorAnnotation {
+    pub line: usize,
+    pub col_start: usize,
+    pub col_end: usize,
+    pub message: String,
+}
+
+impl ErrorAnnotation {
+    /// Extract the annotation from a rendered Simfony error.
+    ///
+    /// Return `None` if the error does not point to a span of the program text.
+    pub fn from_error(error: &str) -> Option<Self> {
+        let mut line = None;
+        let mut underline = None;
+
+        for text in error.lines() {
+            let (gutter, content) = match text.split_once('|') {
+                Some(split) => split,
+                None => continue,
+            };
+            match gutter.trim().parse::<usize>() {
+                Ok(line_num) if line.is_none() => line = Some(line_num),
+                Ok(..) => {}
+                Err(..) if gutter.trim().is_empty() => {
+                    if let Some(start) = content.find('^') {
+                        let len = prefix_len(&content[start..], |c| c == '^');
+                        let message = content[start + len..].trim().to_string();
+                        underline = Some((start, len, message));
+                    }
+                }
+                Err(..) => {}
+            }
+        }
+
+        let (start, len, message) = underline?;
+        let col_start = start.max(1);
+        Some(Self {
+            line: line?,
+            col_start,
+            col_end: col_start + len,
+            message,
+        })
+    }
+}
+
+#[component]
+pub fn Editor(
+    value: RwSignal<String>,
+    #[prop(into)] on_change: Callback<String>,
+    #[prop(into, optional)] annotations: Signal<Vec<ErrorAnnotation>>,
+    #[prop(into, optional)] lints: Signal<Vec<LintWarning>>,
+) -> impl IntoView {
+    let textarea_ref = create_node_ref::<html::Textarea>();
+    let highlight_ref = create_node_ref::<html::Pre>();
+    let annotation_ref = create_node_ref::<html::Pre>();
+    let gutter_ref = create_node_ref::<html::Pre>();
+
+    let highlighted = move || -> View {
+        value.with(|text| {
+            tokenize(text)
+                .into_iter()
+                .map(|(kind, token)| match kind {
+                    TokenKind::Plain => token.to_string().into_view(),
+                    _ => {
+                        view! { <span class={kind.class()}>{token.to_string()}</span> }.into_view()
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    let underlined = move || -> View {
+        with!(|value, annotations, lints| {
+            value
+                .split('\n')
+                .enumerate()
+                .map(|(index, line)| {
+                    let errors = annotations
+                        .iter()
+                        .filter(|x| x.line == index + 1)
+                        .map(|x| (x.col_start, x.col_end, "error-span"));
+                    let warnings = lints
+                        .iter()
+                        .filter(|x| x.line == index + 1)
+                        .map(|x| (x.col_start, x.col_end, lint_span_class(x.severity)));
+                    let mut spans = errors.chain(warnings).collect::<Vec<_>>();
+                    spans.sort();
+
+                    // Only the underlined text matters, the rest of the layer is transparent
+                    let mut col = 1;
+                    let mut views = vec![];
+                    for (col_start, col_end, class) in spans {
+                        if col_start < col {
+                            continue;
+                        }
+                        let before = line.chars().skip(col - 1).take(col_start - col);
+                        let spanned = line.chars().skip(col_start - 1).take(col_end - col_start);
+                        views.push(before.collect::<String>().into_view());
+                        views.push(
+                            view! { <span class=class>{spanned.collect::<String>()}</span> }
+                                .into_view(),
+                        );
+                        col = col_end;
+                    }
+                    views.push("\n".into_view());
+                    views.collect_view()
+                })
+                .collect_view()
+        })
+    };
+    let line_numbers = move || -> View {
+        with!(|value, annotations, lints| {
+            (1..=value.split('\n').count())
+                .map(|line| {
+                    let error = annotations
+                        .iter()
+                        .find(|x| x.line == line)
+                        .map(|x| ("error-line", x.message.clone()));
+                    let lint = lints
+                        .iter()
+                        .filter(|x| x.line == line)
+                        .max_by_key(|x| x.severity)
+                        .map(|x| (lint_line_class(x.severity), x.message.clone()));
+                    match error.or(lint) {
+                        Some((class, message)) => view! {
+                            <span class=class title=message>
+                                {line}
+                                "\n"
+                            </span>
+                        }
+                        .into_view(),
+                        None => format!("{line}\n").into_view(),
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    let completions = create_rw_signal(Vec::<(&'static str, &'static str)>::new());
+    let selected_completion = create_rw_signal(0usize);
+    let update_completions = move || {
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        let cursor = match element.selection_start() {
+            Ok(Some(cursor)) => cursor as usize,
+            _ => {
+                completions.update(Vec::clear);
+                return;
+            }
+        };
+        let suggested = value.with_untracked(|text| {
+            byte_offset(text, cursor)
+                .and_then(|cursor| jet_completion_prefix(text, cursor))
+                .map(jet_index::complete)
+                .unwrap_or_default()
+        });
+        completions.set(suggested);
+        selected_completion.set(0);
+    };
+    let accept_completion = move |index: usize| {
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        let name = match completions.with_untracked(|x| x.get(index).map(|(name, _)| *name)) {
+            Some(name) => name,
+            None => return,
+        };
+        if let Ok(Some(cursor)) = element.selection_start() {
+            let mut text = value.get_untracked();
+            let Some(cursor) = byte_offset(&text, cursor as usize) else {
+                return;
+            };
+            let prefix_len = match jet_completion_prefix(&text, cursor) {
+                Some(prefix) => prefix.len(),
+                None => return,
+            };
+            let start = cursor - prefix_len;
+            text.replace_range(start..cursor, name);
+            let new_cursor = utf16_offset(&text, start + name.len()) as u32;
+            on_change.call(text);
+            let _result = element.set_selection_range(new_cursor, new_cursor);
+        }
+        completions.update(Vec::clear);
+    };
+
+    // The highlighted jet that the mouse is over, with the position of its bottom left corner
+    let hovered_jet = create_rw_signal(None::<(String, f64, f64)>);
+    let update_hovered_jet = move |event: ev::MouseEvent| {
+        let highlight = highlight_ref.get().expect("<pre> should be mounted");
+        let (x, y) = (f64::from(event.client_x()), f64::from(event.client_y()));
+        let jets = highlight.get_elements_by_class_name(TokenKind::Jet.class());
+        let hovered = (0..jets.length())
+            .filter_map(|index| jets.item(index))
+            .find_map(|span| {
+                let rect = span.get_bounding_client_rect();
+                let is_inside =
+                    rect.left() <= x && x <= rect.right() && rect.top() <= y && y <= rect.bottom();
+                let name = span.text_content()?.strip_prefix("jet::")?.to_string();
+                is_inside.then_some((name, rect.left(), rect.bottom()))
+            });
+        if hovered != hovered_jet.get_untracked() {
+            hovered_jet.set(hovered);
+        }
+    };
+    let jet_documentation = move || {
+        hovered_jet.get().and_then(|(name, left, top)| {
+            let signature = jet_index::signature(&name)?;
+            let description = jet_index::description(&name).unwrap_or_default();
+            let style = format!("left: {left}px; top: {top}px;");
+            Some(view! {
+                <div class="editor-hover" style=style>
+                    <code class="hover-signature">{format!("jet::{name}: {signature}")}</code>
+                    <p class="hover-description">{description}</p>
+                </div>
+            })
+        })
+    };
+
+    let update_value = move |event: ev::Event| {
+        on_change.call(event_target_value(&event));
+        update_completions();
+    };
+    let sync_scroll = move |_event: ev::Event| {
+        let textarea = textarea_ref.get().expect("<textarea> should be mounted");
+        for pre in [highlight_ref, annotation_ref] {
+            let pre = pre.get().expect("<pre> should be mounted");
+            pre.set_scroll_top(textarea.scroll_top());
+            pre.set_scroll_left(textarea.scroll_left());
+        }
+        let gutter = gutter_ref.get().expect("<pre> should be mounted");
+        gutter.set_scroll_top(textarea.scroll_top());
+    };
+    let insert_4_spaces = move || {
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        if let Ok(Some(start)) = element.selection_start() {
+            let mut text = value.get_untracked();
+            let Some(start_) = byte_offset(&text, start as usize) else {
+                return;
+            };
+            text.insert_str(start_, "    ");
+            on_change.call(text);
+            let _result = element.set_selection_range(start + 4, start + 4);
+        }
+    };
+    let delete_4_spaces = move || {
+        let element = textarea_ref.get().expect("<textarea> should be mounted");
+        if let Ok(Some(start)) = element.selection_start() {
+            let mut text = value.get_untracked();
+            let Some(start_) = byte_offset(&text, start as usize) else {
+                return;
+            };
+            if start_ < 4 || text.get(start_ - 4..start_) != Some("    ") {
+                return;
+            }
+            text.replace_range(start_ - 4..start_, "");
+            on_change.call(text);
+            let _result = element.set_selection_range(start - 4, start - 4);
+        }
+    };
+    let handle_keydown = move |event: ev::KeyboardEvent| {
+        let count = completions.with_untracked(Vec::len);
+        if 0 < count {
+            match event.key().as_str() {
+                "ArrowDown" => selected_completion.update(|x| *x = (*x + 1) % count),
+                "ArrowUp" => selected_completion.update(|x| *x = (*x + count - 1) % count),
+                "Enter" | "Tab" => accept_completion(selected_completion.get_untracked()),
+                "Escape" => completions.update(Vec::clear),
+                _ => return,
+            }
+            event.prevent_default();
+            return;
+        }
+        if event.key_code() == TAB_KEY {
+            event.prevent_default();
+            match event.shift_key() {
+                false => insert_4_spaces(),
+                true => delete_4_spaces(),
+            }
+        }
+    };
+
+    view! {
+        <div class="editor">
+            <pre class="program-input-field editor-gutter" node_ref=gutter_ref>
+                {line_numbers}
+            </pre>
+            <div class="editor-body">
+                <pre
+                    class="program-input-field editor-highlight"
+                    aria-hidden="true"
+                    node_ref=highlight_ref
+                >
+                    {highlighted}
+                    // Trailing newlines need content to take up space
+                    " "
+                </pre>
+                <pre
+                    class="program-input-field editor-annotations"
+                    aria-hidden="true"
+                    node_ref=annotation_ref
+                >
+                    {underlined}
+                </pre>
+                <textarea
+                    class="program-input-field editor-input"
+                    placeholder="Enter your program here"
+                    rows="25"
+                    cols="80"
+                    spellcheck="false"
+                    wrap="off"
+                    prop:value=value
+                    on:input=update_value
+                    on:keydown=handle_keydown
+                    on:scroll=sync_scroll
+                    on:blur=move |_| completions.update(Vec::clear)
+                    on:mousemove=update_hovered_jet
+                    on:mouseleave=move |_| hovered_jet.set(None)
+                    node_ref=textarea_ref
+                >
+                    {value.get_untracked()}
+                </textarea>
+                <Show when=move || completions.with(|x| !x.is_empty())>
+                    <ul class="editor-completions" role="listbox">
+                        {move || {
+                            completions
+                                .get()
+                                .into_iter()
+                                .enumerate()
+                                .map(|(index, (name, signature))| {
+                                    let select = move |event: ev::MouseEvent| {
+                                        // Keep the focus in the textarea
+                                        event.prevent_default();
+                                        accept_completion(index);
+                                    };
+                                    view! {
+                                        <li
+                                            role="option"
+                                            class:selected=move || selected_completion.get() == index
+                                            on:mousedown=select
+                                        >
+                                            <span class="completion-name">{name}</span>
+                                            <span class="completion-signature">{signature}</span>
+                                        </li>
+                                    }
+                                })
+                                .collect_view()
+                        }}
+                    </ul>
+                </Show>
+                {jet_documentation}
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn tokenize_is_lossless() {
+        let text = "fn main() {\n    // check 0x01\n    let x: u8 = 0x01;\n    assert!(jet::eq_8(x, 1)); /* done */\n}";
+        let joined: String = tokenize(text).into_iter().map(|(_, token)| token).collect();
+        assert_eq!(text, joined);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn tokenize_kinds() {
+        let tokens = tokenize("let x: u8 = jet::add_8(0x01, y); // add\nassert!(true)");
+        let non_plain: Vec<(TokenKind, &str)> = tokens
+            .into_iter()
+            .filter(|(kind, _)| *kind != TokenKind::Plain)
+            .collect();
+        assert_eq!(
+            vec![
+                (TokenKind::Keyword, "let"),
+                (TokenKind::Type, "u8"),
+                (TokenKind::Jet, "jet::add_8"),
+                (TokenKind::Number, "0x01"),
+                (TokenKind::Comment, "// add"),
+                (TokenKind::Macro, "assert!"),
+                (TokenKind::Keyword, "true"),
+            ],
+            non_plain
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn completion_prefix() {
+        let text = "let x: u8 = jet::ad";
+        assert_eq!(Some("ad"), jet_completion_prefix(text, text.len()));
+        assert_eq!(Some(""), jet_completion_prefix("jet::", 5));
+        assert_eq!(Some("a"), jet_completion_prefix(text, text.len() - 1));
+        assert_eq!(None, jet_completion_prefix("myjet::ad", 9));
+        assert_eq!(None, jet_completion_prefix("jet:ad", 6));
+        assert_eq!(None, jet_completion_prefix(text, text.len() + 1));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn utf16_offsets() {
+        // `é` is two bytes and one UTF-16 unit, `🦀` is four bytes and two UTF-16 units
+        let text = "// é🦀\njet::ad";
+        assert_eq!(Some(0), byte_offset(text, 0));
+        assert_eq!(Some(5), byte_offset(text, 4));
+        assert_eq!(None, byte_offset(text, 5));
+        assert_eq!(Some(9), byte_offset(text, 6));
+        assert_eq!(
+            Some(text.len()),
+            byte_offset(text, utf16_offset(text, text.len()))
+        );
+        assert_eq!(None, byte_offset(text, utf16_offset(text, text.len()) + 1));
+        assert_eq!(6, utf16_offset(text, 9));
+
+        let cursor = byte_offset(text, utf16_offset(text, text.len())).unwrap();
+        assert_eq!(Some("ad"), jet_completion_prefix(text, cursor));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn annotation_from_error() {
+        let error =
+            "  |\n2 |     let x: u8 = y;\n  |                 ^ Variable `y` is not defined";
+        let expected = ErrorAnnotation {
+            line: 2,
+            col_start: 17,
+            col_end: 18,
+            message: "Variable `y` is not defined".to_string(),
+        };
+        assert_eq!(Some(expected), ErrorAnnotation::from_error(error));
+        assert_eq!(None, ErrorAnnotation::from_error("Witness `x` is missing"));
+    }
+}
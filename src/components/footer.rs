@@ -1,5 +1,10 @@
 use leptos::{component, view, IntoView};
 
+/// Version of the web IDE.
+const IDE_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Version of the Simfony compiler, set by the build script.
+const SIMFONY_VERSION: &str = env!("SIMFONY_VERSION");
+
 #[component]
 pub fn Footer() -> impl IntoView {
     view! {
@@ -7,20 +12,39 @@ pub fn Footer() -> impl IntoView {
             <h1 class="intro-title">Further Links</h1>
             <div>
                 "🚀 "
-                <a href="https://simfony-lang.com/" target="blank">Simfony Lander</a>
+                <a href="https://simfony-lang.com/" target="_blank" rel="noopener noreferrer">Simfony Lander</a>
             </div>
             <div>
                 "📜 "
-                <a href="https://docs.rs/simfony-as-rust/latest/simfony_as_rust/jet/index.html" target="blank">Jet documentation</a>
+                <a href="https://docs.rs/simfony-as-rust/latest/simfony_as_rust/jet/index.html" target="_blank" rel="noopener noreferrer">Jet documentation</a>
             </div>
             <div>
                 "🛠️ "
-                <a href="https://github.com/uncomputable/simfony-webide" target="blank">Simfony web IDE GitHub repository</a>
+                <a href="https://github.com/uncomputable/simfony-webide" target="_blank" rel="noopener noreferrer">Simfony web IDE GitHub repository</a>
             </div>
             <div>
                 "🛠️ "
-                <a href="https://github.com/BlockstreamResearch/simfony" target="blank">Simfony compiler GitHub repository</a>
+                <a href="https://github.com/BlockstreamResearch/simfony" target="_blank" rel="noopener noreferrer">Simfony compiler GitHub repository</a>
+            </div>
+            <div>
+                "📄 "
+                <a href="https://blockstream.com/simplicity.pdf" target="_blank" rel="noopener noreferrer">Simplicity whitepaper</a>
             </div>
+            <div>
+                "📐 "
+                <a href="https://github.com/BlockstreamResearch/simplicity" target="_blank" rel="noopener noreferrer">Simplicity specification GitHub repository</a>
+            </div>
+            <VersionBadge />
+        </div>
+    }
+}
+
+#[component]
+fn VersionBadge() -> impl IntoView {
+    view! {
+        <div class="version-badge">
+            <span>"IDE v" {IDE_VERSION}</span>
+            <span>"Simfony v" {SIMFONY_VERSION}</span>
         </div>
     }
 }
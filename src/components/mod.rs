@@ -1,13 +1,25 @@
 mod analysis;
 mod app;
+mod copy_button;
 mod copy_to_clipboard;
+mod diff_viewer;
 mod dropdown;
+mod editor;
 mod footer;
 mod navbar;
+mod notifications;
 mod program_window;
+mod restore_banner;
 mod run_window;
+mod shortcuts_modal;
+mod spinner;
+mod split_pane;
 mod state;
 mod string_box;
+mod theme_toggle;
+mod toast;
 mod toolbar;
+mod type_explorer;
+mod value_tree;
 
 pub use app::App;
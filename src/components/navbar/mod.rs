@@ -0,0 +1,233 @@
+mod tab_router;
+
+use leptos::leptos_dom::Transparent;
+use leptos::wasm_bindgen::closure::Closure;
+use leptos::wasm_bindgen::JsCast;
+use leptos::{
+    component, create_effect, create_rw_signal, document, ev, html, on_cleanup, store_value, view,
+    Children, ChildrenFn, IntoView, NodeRef, RwSignal, SignalGet, SignalGetUntracked, SignalSet,
+    View,
+};
+
+use crate::components::state::local_storage;
+
+/// Prefix of the local storage keys of the active tabs.
+///
+/// The name of the default tab is appended to tell navbars apart.
+const ACTIVE_TAB_PREFIX: &str = "active_tab:";
+
+/// Distance in pixels that the scroll buttons move the tab bar.
+const SCROLL_STEP: f64 = 200.0;
+
+#[component]
+pub fn Navbar(
+    default_tab: &'static str,
+    children: Children,
+    active_tab: RwSignal<&'static str>,
+    /// Whether the active tab is reflected in the fragment of the URL.
+    #[prop(optional)]
+    route: bool,
+) -> impl IntoView {
+    let mut tabs_content = Vec::new();
+    let mut tab_buttons = Vec::new();
+    let mut button_bar = Vec::new();
+
+    for child in children()
+        .as_children()
+        .iter()
+        .filter_map(View::as_transparent)
+        .filter_map(Transparent::downcast_ref::<TabView>)
+        .cloned()
+    {
+        match child {
+            TabView::Tab { name, children } => {
+                let node_ref = NodeRef::<html::Button>::new();
+                tabs_content.push((name, children));
+                tab_buttons.push((name, node_ref));
+                button_bar.push(view! {
+                    <TabButton tab_name=name active_tab=active_tab node_ref=node_ref />
+                })
+            }
+            TabView::Button { children } => button_bar.push(children().into_view()),
+        }
+    }
+
+    let storage_key = format!("{ACTIVE_TAB_PREFIX}{default_tab}");
+    let stored_tab = local_storage()
+        .and_then(|storage| storage.get_item(&storage_key).ok().flatten())
+        .and_then(|stored| {
+            tabs_content
+                .iter()
+                .map(|(tab_name, _content)| *tab_name)
+                .find(|tab_name| *tab_name == stored)
+        });
+    active_tab.set(stored_tab.unwrap_or(default_tab));
+    if route {
+        let tab_names = tabs_content.iter().map(|(name, _)| *name).collect();
+        tab_router::route_tabs(tab_names, active_tab);
+    }
+    create_effect(move |_| {
+        let tab_name = active_tab.get();
+        if let Some(storage) = local_storage() {
+            let _result = storage.set_item(&storage_key, tab_name);
+        }
+    });
+
+    let active_tab_content = move || -> ChildrenFn {
+        tabs_content
+            .iter()
+            .find(|(tab_name, _content)| tab_name == &active_tab.get())
+            .expect("Tab not found")
+            .1
+            .clone()
+    };
+
+    let tab_buttons = store_value(tab_buttons);
+    let navigate_tabs = move |event: ev::KeyboardEvent| {
+        let focused = document().active_element();
+        tab_buttons.with_value(|tabs| {
+            let is_focused = |node_ref: &NodeRef<html::Button>| {
+                node_ref
+                    .get()
+                    .zip(focused.as_ref())
+                    .is_some_and(|(button, focused)| {
+                        let element: &web_sys::Element = &button;
+                        element == focused
+                    })
+            };
+            let Some(index) = tabs.iter().position(|(_, node_ref)| is_focused(node_ref)) else {
+                return;
+            };
+            let last = tabs.len() - 1;
+            let target = match event.key().as_str() {
+                "ArrowLeft" if index == 0 => last,
+                "ArrowLeft" => index - 1,
+                "ArrowRight" if index == last => 0,
+                "ArrowRight" => index + 1,
+                "Home" => 0,
+                "End" => last,
+                "Enter" | " " => {
+                    event.prevent_default();
+                    active_tab.set(tabs[index].0);
+                    return;
+                }
+                _ => return,
+            };
+            event.prevent_default();
+            if let Some(button) = tabs[target].1.get() {
+                let _result = button.focus();
+            }
+        });
+    };
+
+    // Show scroll buttons while the tab bar is too narrow for all tabs
+    let tab_list = NodeRef::<html::Div>::new();
+    let overflowing = create_rw_signal(false);
+    let check_overflow = Closure::<dyn FnMut()>::new(move || {
+        if let Some(list) = tab_list.get_untracked() {
+            overflowing.set(list.scroll_width() > list.client_width());
+        }
+    });
+    let observer = web_sys::ResizeObserver::new(check_overflow.as_ref().unchecked_ref()).ok();
+    if let Some(observer) = observer.clone() {
+        create_effect(move |_| {
+            if let Some(list) = tab_list.get() {
+                observer.observe(&list);
+            }
+        });
+    }
+    on_cleanup(move || {
+        if let Some(observer) = observer {
+            observer.disconnect();
+        }
+        drop(check_overflow);
+    });
+    let scroll_tabs = move |distance: f64| {
+        if let Some(list) = tab_list.get_untracked() {
+            list.scroll_by_with_x_and_y(distance, 0.0);
+        }
+    };
+
+    view! {
+        <div class="navbar" class:overflowing=overflowing>
+            <button
+                class="tab-scroll"
+                tabindex="-1"
+                aria-hidden="true"
+                on:click=move |_| scroll_tabs(-SCROLL_STEP)
+            >
+                <i class="fas fa-chevron-left"></i>
+            </button>
+            <div class="tab-list" role="tablist" node_ref=tab_list on:keydown=navigate_tabs>
+                {button_bar}
+            </div>
+            <button
+                class="tab-scroll"
+                tabindex="-1"
+                aria-hidden="true"
+                on:click=move |_| scroll_tabs(SCROLL_STEP)
+            >
+                <i class="fas fa-chevron-right"></i>
+            </button>
+        </div>
+        {active_tab_content}
+    }
+}
+
+#[component(transparent)]
+pub fn Tab(name: &'static str, children: ChildrenFn) -> impl IntoView {
+    TabView::Tab { name, children }
+}
+
+#[component(transparent)]
+pub fn Button(children: ChildrenFn) -> impl IntoView {
+    TabView::Button { children }
+}
+
+#[derive(Clone)]
+enum TabView {
+    Tab {
+        name: &'static str,
+        children: ChildrenFn,
+    },
+    Button {
+        children: ChildrenFn,
+    },
+}
+
+impl IntoView for TabView {
+    fn into_view(self) -> View {
+        Transparent::new(self).into_view()
+    }
+}
+
+#[component]
+fn TabButton(
+    tab_name: &'static str,
+    active_tab: RwSignal<&'static str>,
+    node_ref: NodeRef<html::Button>,
+) -> impl IntoView {
+    let button_click = move |_event: ev::MouseEvent| active_tab.set(tab_name);
+    let button_class = move || match active_tab.get() == tab_name {
+        true => "tab active",
+        false => "tab",
+    };
+    let is_active = move || active_tab.get() == tab_name;
+    // Only the active tab is reachable with the Tab key, the others via the arrow keys
+    let tab_index = move || match is_active() {
+        true => "0",
+        false => "-1",
+    };
+    view! {
+        <button
+            class=button_class
+            role="tab"
+            aria-selected=move || is_active().to_string()
+            tabindex=tab_index
+            node_ref=node_ref
+            on:click=button_click
+        >
+            {tab_name}
+        </button>
+    }
+}
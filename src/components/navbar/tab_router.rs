@@ -0,0 +1,87 @@
+//! Routing of tabs via the fragment of the URL,
+//! so that links can open a specific tab.
+
+use leptos::wasm_bindgen::JsValue;
+use leptos::{
+    create_effect, ev, on_cleanup, window_event_listener, RwSignal, SignalGet, SignalSet,
+};
+
+/// Convert the name of a tab into the fragment of its URL.
+///
+/// "Key Store" becomes "#key-store".
+pub fn tab_fragment(name: &str) -> String {
+    let slug = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<String>>()
+        .join("-");
+    format!("#{slug}")
+}
+
+/// Return the tab whose URL fragment is the given fragment, including the leading `#`.
+pub fn tab_from_fragment(fragment: &str, tabs: &[&'static str]) -> Option<&'static str> {
+    tabs.iter()
+        .find(|name| tab_fragment(name) == fragment)
+        .copied()
+}
+
+fn current_fragment() -> Option<String> {
+    web_sys::window()?.location().hash().ok()
+}
+
+fn tab_from_location(tabs: &[&'static str]) -> Option<&'static str> {
+    current_fragment().and_then(|fragment| tab_from_fragment(&fragment, tabs))
+}
+
+/// Keep the active tab and the fragment of the URL in sync.
+///
+/// The tab of the URL fragment is opened on startup.
+/// Switching tabs adds an entry to the browser history,
+/// so the back and forward buttons switch between tabs.
+pub fn route_tabs(tabs: Vec<&'static str>, active_tab: RwSignal<&'static str>) {
+    if let Some(tab) = tab_from_location(&tabs) {
+        active_tab.set(tab);
+    }
+    create_effect(move |previous: Option<()>| {
+        let fragment = tab_fragment(active_tab.get());
+        let current = current_fragment().unwrap_or_default();
+        let Some(history) = web_sys::window().and_then(|window| window.history().ok()) else {
+            return;
+        };
+        match previous {
+            _ if current == fragment => {}
+            // Mark the first history entry with its tab, so going back returns to it,
+            // but keep other fragments such as shared programs
+            None if current.is_empty() => {
+                let _result = history.replace_state_with_url(&JsValue::NULL, "", Some(&fragment));
+            }
+            None => {}
+            Some(()) => {
+                let _result = history.push_state_with_url(&JsValue::NULL, "", Some(&fragment));
+            }
+        }
+    });
+    let popstate_handle = window_event_listener(ev::popstate, move |_event| {
+        if let Some(tab) = tab_from_location(&tabs) {
+            active_tab.set(tab);
+        }
+    });
+    on_cleanup(move || popstate_handle.remove());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn fragments_of_tabs() {
+        let tabs = ["Execution", "Key Store", "Hash Store"];
+        assert_eq!("#execution", tab_fragment("Execution"));
+        assert_eq!("#key-store", tab_fragment("Key Store"));
+        assert_eq!(Some("Hash Store"), tab_from_fragment("#hash-store", &tabs));
+        assert_eq!(None, tab_from_fragment("#program=abc", &tabs));
+        assert_eq!(None, tab_from_fragment("", &tabs));
+    }
+}
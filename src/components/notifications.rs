@@ -0,0 +1,133 @@
+use leptos::{
+    component, create_rw_signal, view, For, IntoView, Show, SignalGet, SignalUpdate, SignalWith,
+};
+
+use crate::components::state::{dismiss_announcement, dismissed_announcement};
+use crate::config::ANNOUNCEMENT;
+
+/// Smallest WebAssembly module that uses a SIMD instruction.
+///
+/// The module defines a function that returns `i8x16.popcnt(i8x16.splat(i32.const 0))`.
+const SIMD_MODULE: [u8; 31] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7b, 0x03,
+    0x02, 0x01, 0x00, 0x0a, 0x0a, 0x01, 0x08, 0x00, 0x41, 0x00, 0xfd, 0x0f, 0xfd, 0x62, 0x0b,
+];
+
+/// Message that is shown at the top of the page.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Notification {
+    /// Announcement that was injected at build time.
+    Announcement(&'static str),
+    /// The browser does not support WebAssembly SIMD.
+    NoSimd,
+    /// The browser does not provide `SharedArrayBuffer`.
+    NoSharedArrayBuffer,
+}
+
+impl Notification {
+    fn message(&self) -> &'static str {
+        match self {
+            Self::Announcement(text) => *text,
+            Self::NoSimd => "Your browser does not support WebAssembly SIMD. Some features may be slow or unavailable.",
+            Self::NoSharedArrayBuffer => "Your browser does not provide SharedArrayBuffer. Some features may be slow or unavailable.",
+        }
+    }
+}
+
+/// Return the notifications that apply to the given conditions.
+///
+/// An announcement is skipped if the user already dismissed it.
+pub fn notifications(
+    announcement: Option<&'static str>,
+    dismissed: Option<&str>,
+    simd: bool,
+    shared_array_buffer: bool,
+) -> Vec<Notification> {
+    let mut notifications = Vec::new();
+    if let Some(text) = announcement.filter(|text| Some(*text) != dismissed) {
+        notifications.push(Notification::Announcement(text));
+    }
+    if !simd {
+        notifications.push(Notification::NoSimd);
+    }
+    if !shared_array_buffer {
+        notifications.push(Notification::NoSharedArrayBuffer);
+    }
+    notifications
+}
+
+fn supports_simd() -> bool {
+    let module = js_sys::Uint8Array::from(SIMD_MODULE.as_slice());
+    js_sys::WebAssembly::validate(&module).unwrap_or(false)
+}
+
+fn supports_shared_array_buffer() -> bool {
+    js_sys::Reflect::has(&js_sys::global(), &"SharedArrayBuffer".into()).unwrap_or(false)
+}
+
+/// Bar that shows announcements and warnings about missing browser features
+/// at the top of the page.
+///
+/// Dismissed warnings reappear on the next visit,
+/// but a dismissed announcement stays hidden until it changes.
+#[component]
+pub fn NotificationBar() -> impl IntoView {
+    let notifications = create_rw_signal(notifications(
+        ANNOUNCEMENT,
+        dismissed_announcement().as_deref(),
+        supports_simd(),
+        supports_shared_array_buffer(),
+    ));
+    let dismiss = move |notification: Notification| {
+        if let Notification::Announcement(text) = &notification {
+            dismiss_announcement(text);
+        }
+        notifications.update(|notifications| notifications.retain(|x| *x != notification));
+    };
+
+    view! {
+        <Show when=move || notifications.with(|notifications| !notifications.is_empty())>
+            <div class="notification-bar">
+                <For
+                    each=move || notifications.get()
+                    key=|notification| notification.clone()
+                    children=move |notification| {
+                        let message = notification.message();
+                        view! {
+                            <div class="notification">
+                                <span class="notification-message">{message}</span>
+                                <button
+                                    class="flat-button bordered"
+                                    type="button"
+                                    on:click=move |_| dismiss(notification.clone())
+                                >
+                                    Dismiss
+                                </button>
+                            </div>
+                        }
+                    }
+                />
+            </div>
+        </Show>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn notifications_of_conditions() {
+        assert!(notifications(None, None, true, true).is_empty());
+        assert_eq!(
+            vec![Notification::NoSimd, Notification::NoSharedArrayBuffer],
+            notifications(None, None, false, false)
+        );
+        assert_eq!(
+            vec![Notification::Announcement("New jets")],
+            notifications(Some("New jets"), Some("Old news"), true, true)
+        );
+        assert!(notifications(Some("New jets"), Some("New jets"), true, true).is_empty());
+    }
+}
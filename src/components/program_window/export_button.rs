@@ -0,0 +1,40 @@
+use leptos::wasm_bindgen::JsValue;
+use leptos::{component, ev, html, use_context, view, IntoView, SignalSet, SignalWithUntracked};
+
+use crate::components::program_window::{Program, Runtime};
+
+/// Let the browser download the given `text` as a file of the given `name`.
+pub fn download_text(name: &str, text: &str) -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(text));
+    let blob = web_sys::Blob::new_with_str_sequence(&parts)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+    let anchor = html::a();
+    anchor.set_href(&url);
+    anchor.set_download(name);
+    anchor.click();
+    web_sys::Url::revoke_object_url(&url)
+}
+
+#[component]
+pub fn ExportButton() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+
+    let export_program = move |_event: ev::MouseEvent| {
+        if let Err(error) = program
+            .text
+            .with_untracked(|text| download_text("program.simf", text))
+        {
+            runtime
+                .error_output
+                .set(format!("Failed to export program: {error:?}"));
+        }
+    };
+
+    view! {
+        <button class="button" on:click=export_program>
+            <i class="fa-solid fa-download"></i>
+            " Export"
+        </button>
+    }
+}
@@ -1,13 +1,32 @@
-use leptos::{component, view, IntoView};
+use leptos::{component, ev, use_context, view, IntoView, SignalSet};
+
+use crate::components::shortcuts_modal::ShortcutsModalOpen;
 
 #[component]
 pub fn HelpButton() -> impl IntoView {
+    let shortcuts_open = use_context::<ShortcutsModalOpen>()
+        .expect("shortcuts modal state should exist in context")
+        .0;
+    let show_shortcuts = move |_event: ev::MouseEvent| shortcuts_open.set(true);
+
     view! {
-        <form action="https://github.com/uncomputable/simfony-webide/blob/master/doc/README.md" target="_blank">
-            <button class="button" type="submit">
+        <div class="dropdown help-dropdown">
+            <button class="button dropdown-button">
                 <i class="fa-solid fa-question"></i>
                 " Help"
             </button>
-        </form>
+            <div class="dropdown-content">
+                <form action="https://github.com/uncomputable/simfony-webide/blob/master/doc/README.md" target="_blank">
+                    <button type="submit">
+                        <i class="fa-solid fa-book"></i>
+                        " Documentation"
+                    </button>
+                </form>
+                <button on:click=show_shortcuts>
+                    <i class="fa-solid fa-keyboard"></i>
+                    " Keyboard shortcuts"
+                </button>
+            </div>
+        </div>
     }
 }
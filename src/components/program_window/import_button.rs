@@ -0,0 +1,52 @@
+use leptos::{component, ev, event_target, spawn_local, use_context, view, IntoView, SignalSet};
+use wasm_bindgen_futures::JsFuture;
+
+use crate::components::program_window::{Program, Runtime};
+
+#[component]
+pub fn ImportButton() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+
+    let import_program = move |event: ev::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&event);
+        let file = match input.files().and_then(|files| files.get(0)) {
+            Some(file) => file,
+            None => return,
+        };
+        // Allow the same file to be imported again
+        input.set_value("");
+
+        spawn_local(async move {
+            let buffer = match JsFuture::from(file.array_buffer()).await {
+                Ok(buffer) => buffer,
+                Err(error) => {
+                    runtime
+                        .error_output
+                        .set(format!("Failed to read file: {error:?}"));
+                    return;
+                }
+            };
+            let bytes = js_sys::Uint8Array::new(&buffer).to_vec();
+            match String::from_utf8(bytes) {
+                Ok(text) => program.text.set(text),
+                Err(..) => runtime
+                    .error_output
+                    .set(format!("`{}` is not valid UTF-8", file.name())),
+            }
+        });
+    };
+
+    view! {
+        <label class="button import-button">
+            <i class="fa-solid fa-upload"></i>
+            " Import"
+            <input
+                class="hidden"
+                type="file"
+                accept=".simf,.txt"
+                on:change=import_program
+            />
+        </label>
+    }
+}
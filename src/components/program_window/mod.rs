@@ -1,6 +1,8 @@
 mod address_button;
 mod examples_dropdown;
+mod export_button;
 mod help_button;
+mod import_button;
 mod program_tab;
 mod run_button;
 mod share_button;
@@ -12,15 +14,20 @@ use leptos::{component, view, IntoView, SignalGet, SignalSet};
 
 use self::address_button::AddressButton;
 use self::examples_dropdown::ExamplesDropdown;
+use self::export_button::ExportButton;
 use self::help_button::HelpButton;
+use self::import_button::ImportButton;
 use self::program_tab::ProgramTab;
 use self::run_button::RunButton;
 use self::share_button::ShareButton;
 use self::transaction_button::TransactionButton;
+use crate::components::theme_toggle::ThemeToggle;
 use crate::components::toolbar::Toolbar;
 
 pub use self::examples_dropdown::select_example;
+pub use self::export_button::download_text;
 pub use self::program_tab::{Program, Runtime};
+pub use self::share_button::program_from_url_hash;
 
 #[component]
 pub fn ProgramWindow() -> impl IntoView {
@@ -35,10 +42,13 @@ pub fn ProgramWindow() -> impl IntoView {
                 <AddressButton />
                 <TransactionButton />
                 <ShareButton />
+                <ExportButton />
+                <ImportButton />
                 <div class="beta-tag">beta</div>
             </div>
 
             <HelpButton />
+            <ThemeToggle />
 
             {move || if !mobile_open.get() {
                 view! { <i class="fa-solid fa-bars hamburger" on:click=move |_| set_mobile_open.set(true)></i>}
@@ -1,24 +1,39 @@
+use std::io;
 use std::sync::Arc;
+use std::time::Duration;
 
 use itertools::Itertools;
+use leptos::leptos_dom::helpers::TimeoutHandle;
 use leptos::{
-    component, create_node_ref, create_rw_signal, ev, event_target_value, html, spawn_local,
-    use_context, view, IntoView, NodeRef, RwSignal, Signal, SignalGetUntracked, SignalSet,
-    SignalUpdate, SignalWith, SignalWithUntracked,
+    component, create_effect, create_rw_signal, ev, html, set_timeout_with_handle, spawn_local,
+    store_value, use_context, view, IntoView, NodeRef, RwSignal, Show, Signal, SignalGet,
+    SignalGetUntracked, SignalSet, SignalUpdate, SignalWith, SignalWithUntracked, StoredValue,
 };
 use simfony::parse::ParseFromStr;
 use simfony::simplicity::jet::elements::ElementsEnv;
 use simfony::{elements, simplicity};
 use simfony::{CompiledProgram, SatisfiedProgram, WitnessValues};
+use simplicity::{BitWriter, Value};
 
 use crate::components::copy_to_clipboard::CopyToClipboard;
-use crate::function::Runner;
+use crate::components::editor::{Editor, ErrorAnnotation};
+use crate::components::run_window::Breakpoints;
+use crate::components::toast::{ToastLevel, Toasts};
+use crate::formatter::format_simfony;
+use crate::function::{ExecutionResources, JetCallEntry, Runner, StepResult};
+use crate::linter::lint;
+use crate::util::{get_program_metrics, witness_module, ProgramMetrics};
 
 #[derive(Copy, Clone, Debug)]
 pub struct Program {
     pub text: RwSignal<String>,
     cached_text: RwSignal<String>,
     pub lazy_cmr: RwSignal<Result<simplicity::Cmr, String>>,
+    pub lazy_amr: RwSignal<Result<simplicity::Amr, String>>,
+    /// Length of the encoded program in bits.
+    pub program_size: RwSignal<Option<usize>>,
+    /// Size and shape of the satisfied program.
+    pub metrics: RwSignal<Option<ProgramMetrics>>,
     lazy_satisfied: RwSignal<Result<SatisfiedProgram, String>>,
 }
 
@@ -34,6 +49,9 @@ impl Program {
             text: create_rw_signal(text),
             cached_text: create_rw_signal("".to_string()),
             lazy_cmr: create_rw_signal(Err("".to_string())),
+            lazy_amr: create_rw_signal(Err("".to_string())),
+            program_size: create_rw_signal(None),
+            metrics: create_rw_signal(None),
             lazy_satisfied: create_rw_signal(Err("".to_string())),
         };
         program.update_on_read();
@@ -72,15 +90,43 @@ impl Program {
                 .map(|x| x.commit().cmr())
                 .map_err(Clone::clone);
             self.lazy_cmr.set(cmr);
+            let program_size = compiled.as_ref().ok().map(|x| {
+                let mut bits = BitWriter::new(io::sink());
+                simplicity::encode::encode_program(&x.commit(), &mut bits)
+                    .expect("writing to sink should never fail")
+            });
+            self.program_size.set(program_size);
             let satisfied = compiled.and_then(|x| {
                 let witness = WitnessValues::parse_from_str(text)?;
                 x.satisfy(witness)
             });
+            let amr = satisfied
+                .as_ref()
+                .map(|x| x.redeem().amr())
+                .map_err(Clone::clone);
+            self.lazy_amr.set(amr);
+            let metrics = satisfied
+                .as_ref()
+                .ok()
+                .map(|x| get_program_metrics(x.redeem()));
+            self.metrics.set(metrics);
             self.lazy_satisfied.set(satisfied);
         });
     }
 }
 
+/// Run of the program with the witness that it had at that time.
+#[derive(Clone, Debug)]
+pub struct ExecutionRecord {
+    /// Witness module of the program text, or an empty string if there was none.
+    pub witness: String,
+    /// Error message if the program failed to compile or to run.
+    pub result: Result<(), String>,
+    /// Start of the run in milliseconds since the Unix epoch.
+    pub timestamp: f64,
+    pub duration_ms: Option<f64>,
+}
+
 #[derive(Copy, Clone)]
 pub struct Runtime {
     program: Program,
@@ -88,18 +134,54 @@ pub struct Runtime {
     pub run_succeeded: RwSignal<Option<bool>>,
     pub debug_output: RwSignal<String>,
     pub error_output: RwSignal<String>,
+    /// Whether to trace jet calls during execution.
+    pub verbose: RwSignal<bool>,
+    pub jet_trace: RwSignal<Vec<JetCallEntry>>,
+    /// Durations of the most recent runs in milliseconds, from oldest to newest.
+    pub run_durations: RwSignal<Vec<f64>>,
+    /// Most recent runs, from oldest to newest.
+    pub history: RwSignal<Vec<ExecutionRecord>>,
+    /// Output stack of the last run, from bottom to top.
+    pub final_stack: RwSignal<Vec<Value>>,
+    /// Resources that the last run used, if the program could be run.
+    pub resources: RwSignal<Option<ExecutionResources>>,
+    /// Runner that executes the program step by step, if stepping is active.
+    stepper: StoredValue<Option<Runner>>,
+    pub stepping: RwSignal<bool>,
+    pub step_result: RwSignal<Option<StepResult>>,
+    breakpoints: Breakpoints,
+    /// Index of the breakpoint node where stepping is paused, if any.
+    pub paused_at: RwSignal<Option<usize>>,
+    /// Whether the program is being compiled and run.
+    pub compiling: RwSignal<bool>,
     // This node ref needs to be mounted somewhere in order to work.
     pub alarm_audio_ref: NodeRef<html::Audio>,
 }
 
 impl Runtime {
-    pub fn new(program: Program, env: Signal<ElementsEnv<Arc<elements::Transaction>>>) -> Self {
+    pub fn new(
+        program: Program,
+        env: Signal<ElementsEnv<Arc<elements::Transaction>>>,
+        breakpoints: Breakpoints,
+    ) -> Self {
         Self {
             program,
             env,
             run_succeeded: Default::default(),
             debug_output: Default::default(),
             error_output: Default::default(),
+            verbose: Default::default(),
+            jet_trace: Default::default(),
+            run_durations: Default::default(),
+            history: Default::default(),
+            final_stack: Default::default(),
+            resources: Default::default(),
+            stepper: store_value(None),
+            stepping: Default::default(),
+            step_result: Default::default(),
+            breakpoints,
+            paused_at: Default::default(),
+            compiling: Default::default(),
             alarm_audio_ref: Default::default(),
         }
     }
@@ -122,97 +204,317 @@ impl Runtime {
             });
     }
 
-    pub fn run(self) {
+    /// Execute the next step of the program.
+    ///
+    /// Start stepping from the beginning if stepping is not active.
+    /// Does nothing while the program is running.
+    pub fn step(self) {
+        if self.compiling.get_untracked() {
+            return;
+        }
+        if self.start_stepping() && self.step_once() {
+            self.publish_step_result();
+        }
+    }
+
+    /// Execute the program until the next node is a breakpoint or the program finishes.
+    ///
+    /// Start stepping from the beginning if stepping is not active.
+    /// The steps are executed in batches, between which the browser handles events.
+    /// Does nothing while the program is running.
+    pub fn continue_to_breakpoint(self) {
+        if self.compiling.get_untracked() || !self.start_stepping() {
+            return;
+        }
+        self.compiling.set(true);
+        spawn_local(async move {
+            'batches: loop {
+                for _ in 0..STEP_BATCH_SIZE {
+                    if !self.step_once() {
+                        break 'batches;
+                    }
+                    if self.paused_at.get_untracked().is_some() {
+                        self.publish_step_result();
+                        break 'batches;
+                    }
+                }
+                gloo_timers::future::TimeoutFuture::new(0).await;
+                // Stepping may have been stopped in the meantime
+                if !self.stepping.get_untracked() {
+                    break;
+                }
+            }
+            self.compiling.set(false);
+        });
+    }
+
+    /// Start stepping from the beginning if stepping is not active.
+    ///
+    /// Return whether stepping is active.
+    fn start_stepping(self) -> bool {
+        if self.stepping.get_untracked() {
+            return true;
+        }
         let satisfied_program = match self.program.satisfied() {
             Ok(x) => x,
             Err(error) => {
                 self.error_output.set(error);
                 self.set_success(false);
-                return;
+                return false;
             }
         };
-        let mut runner = Runner::for_program(satisfied_program);
-        let success = self.env.with(|env| match runner.run(env) {
-            Ok(..) => {
-                self.error_output.update(String::clear);
+        self.stepper
+            .set_value(Some(Runner::for_program(satisfied_program)));
+        self.stepping.set(true);
+        self.step_result.set(None);
+        self.error_output.update(String::clear);
+        true
+    }
+
+    /// Execute a single step of the active runner.
+    ///
+    /// Return whether the program has steps left.
+    /// The state of the runner is published only once the program finishes or fails.
+    fn step_once(self) -> bool {
+        let result = self.stepper.try_update_value(|stepper| {
+            let runner = stepper.as_mut().expect("stepper should be active");
+            self.env
+                .with_untracked(|env| runner.advance(env))
+                .map(|has_stepped| has_stepped.then(|| runner.next_node_index()))
+        });
+        match result {
+            Some(Ok(Some(next_node_index))) => {
+                let paused_at = next_node_index.filter(|index| {
+                    self.breakpoints
+                        .0
+                        .with_untracked(|breakpoints| breakpoints.contains(index))
+                });
+                if self.paused_at.get_untracked() != paused_at {
+                    self.paused_at.set(paused_at);
+                }
                 true
             }
-            Err(error) => {
+            Some(Ok(None)) | None => {
+                self.publish_step_result();
+                self.stop_stepping();
+                self.set_success(true);
+                false
+            }
+            Some(Err(error)) => {
+                self.publish_step_result();
                 self.error_output.set(error.to_string());
+                self.stop_stepping();
+                self.set_success(false);
                 false
             }
+        }
+    }
+
+    /// Show the state of the active runner.
+    fn publish_step_result(self) {
+        if let Some(step_result) = self
+            .stepper
+            .with_value(|stepper| stepper.as_ref().and_then(Runner::step_result))
+        {
+            self.step_result.set(Some(step_result));
+        }
+    }
+
+    /// Stop executing the program step by step.
+    pub fn stop_stepping(self) {
+        self.stepper.set_value(None);
+        self.stepping.set(false);
+        self.paused_at.set(None);
+    }
+
+    /// Compile and run the program in the background.
+    ///
+    /// Does nothing if the program is already running.
+    pub fn run(self) {
+        if self.compiling.get_untracked() {
+            return;
+        }
+        self.compiling.set(true);
+        spawn_local(async move {
+            // Let the browser render the spinner before blocking the main thread
+            gloo_timers::future::TimeoutFuture::new(RENDER_DELAY_MS).await;
+            self.run_now();
+            self.compiling.set(false);
         });
+    }
+
+    fn run_now(self) {
+        self.stop_stepping();
+        self.step_result.set(None);
+        let timestamp = js_sys::Date::now();
+        let satisfied_program = match self.program.satisfied() {
+            Ok(x) => x,
+            Err(error) => {
+                self.record_run(Err(error.clone()), timestamp, None);
+                self.resources.set(None);
+                self.error_output.set(error);
+                self.set_success(false);
+                return;
+            }
+        };
+        let mut runner = match self.verbose.get_untracked() {
+            false => Runner::for_program(satisfied_program),
+            true => Runner::for_program(satisfied_program).with_jet_trace(),
+        };
+        let start = performance_now();
+        let result = self
+            .env
+            .with(|env| runner.run(env).map_err(|error| error.to_string()));
+        let duration = start.zip(performance_now()).map(|(start, end)| end - start);
+        if let Some(duration) = duration {
+            self.run_durations.update(|durations| {
+                if durations.len() == MAX_RUN_DURATIONS {
+                    durations.remove(0);
+                }
+                durations.push(duration);
+            });
+        }
+        match &result {
+            Ok(..) => self.error_output.update(String::clear),
+            Err(error) => self.error_output.set(error.clone()),
+        }
+        let success = result.is_ok();
+        self.record_run(result, timestamp, duration);
+        self.jet_trace.set(runner.jet_trace().to_vec());
+        self.final_stack.set(runner.output_stack().to_vec());
+        self.resources.set(Some(runner.resources()));
         self.debug_output
             .set(runner.debug_output().into_iter().join("\n"));
         self.set_success(success);
     }
+
+    /// Add a run with the current witness to the execution history.
+    fn record_run(self, result: Result<(), String>, timestamp: f64, duration_ms: Option<f64>) {
+        let witness = self
+            .program
+            .text
+            .with_untracked(|text| witness_module(text).map(str::to_string).unwrap_or_default());
+        self.history.update(|history| {
+            if history.len() == MAX_HISTORY {
+                history.remove(0);
+            }
+            history.push(ExecutionRecord {
+                witness,
+                result,
+                timestamp,
+                duration_ms,
+            });
+        });
+    }
 }
 
-const TAB_KEY: u32 = 9;
-const ENTER_KEY: u32 = 13;
+/// Time to wait for the browser to render a frame, in milliseconds.
+const RENDER_DELAY_MS: u32 = 20;
+
+/// Number of steps between two pauses in which the browser handles events,
+/// when continuing to a breakpoint.
+const STEP_BATCH_SIZE: usize = 1000;
+
+/// Time after the last keystroke until the program is linted, in milliseconds.
+const LINT_DELAY_MS: u64 = 800;
+
+/// Number of recent runs whose durations are kept for averaging.
+const MAX_RUN_DURATIONS: usize = 5;
+
+/// Maximum number of runs in the execution history.
+const MAX_HISTORY: usize = 20;
+
+/// Return the current high-resolution timestamp in milliseconds.
+fn performance_now() -> Option<f64> {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+}
 
 #[component]
 pub fn ProgramTab() -> impl IntoView {
     let program = use_context::<Program>().expect("program should exist in context");
-    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
-    let textarea_ref = create_node_ref::<html::Textarea>();
 
-    let update_program_text = move |event: ev::Event| {
-        program.text.set(event_target_value(&event));
-    };
-    let insert_4_spaces = move || {
-        let element = textarea_ref.get().expect("<textarea> should be mounted");
-        if let Ok(Some(start)) = element.selection_start() {
-            let start_ = start as usize; // safety: 32-bit machine of higher
-            program.text.update(|s| s.insert_str(start_, "    "));
-            let _result = element.set_selection_range(start + 4, start + 4);
-        }
-    };
-    let delete_4_spaces = move || {
-        let element = textarea_ref.get().expect("<textarea> should be mounted");
-        if let Ok(Some(start)) = element.selection_start() {
-            let start_ = start as usize; // safety: 32-bit machine of higher
-            if start < 4 || program.text.with(|s| &s[start_ - 4..start_] != "    ") {
-                return;
-            }
-            program
-                .text
-                .update(|s| s.replace_range(start_ - 4..start_, ""));
-            let _result = element.set_selection_range(start - 4, start - 4);
+    let update_program_text = move |text: String| program.text.set(text);
+    let annotations = Signal::derive(move || {
+        program.lazy_cmr.with(|cmr| match cmr {
+            Ok(..) => vec![],
+            Err(error) => ErrorAnnotation::from_error(error).into_iter().collect(),
+        })
+    });
+
+    // Lint the program once the user stops typing
+    let lints = create_rw_signal(Vec::new());
+    let lint_handle = store_value(None::<TimeoutHandle>);
+    create_effect(move |_| {
+        let text = program.text.get();
+        if let Some(handle) = lint_handle.get_value() {
+            handle.clear();
         }
+        let handle = set_timeout_with_handle(
+            move || lints.set(lint(&text)),
+            Duration::from_millis(LINT_DELAY_MS),
+        )
+        .ok();
+        lint_handle.set_value(handle);
+    });
+
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+    let format_program = move |_event: ev::MouseEvent| match program
+        .text
+        .with_untracked(|text| format_simfony(text))
+    {
+        Ok(formatted) => program.text.set(formatted),
+        Err(error) => toasts.add_toast(ToastLevel::Error, format!("Cannot format: {error}")),
     };
-    let handle_keydown = move |event: ev::KeyboardEvent| {
-        if event.ctrl_key() && event.key_code() == ENTER_KEY {
-            runtime.run();
-        } else if event.key_code() == TAB_KEY {
+
+    // The run window stays mounted underneath the fullscreen editor
+    let fullscreen = create_rw_signal(false);
+    let toggle_fullscreen = move |_event: ev::MouseEvent| fullscreen.update(|x| *x = !*x);
+    let exit_fullscreen = move |_event: ev::MouseEvent| fullscreen.set(false);
+    // Outside the editor, F11 keeps toggling the fullscreen mode of the browser
+    let fullscreen_shortcut = move |event: ev::KeyboardEvent| {
+        if event.key() == "F11" {
             event.prevent_default();
-            match event.shift_key() {
-                false => insert_4_spaces(),
-                true => delete_4_spaces(),
-            }
+            fullscreen.update(|x| *x = !*x);
         }
     };
 
     view! {
-        <div class="tab-content">
-            <div class="copy-program">
+        <div
+            class="tab-content program-tab"
+            class:fullscreen=fullscreen
+            on:keydown=fullscreen_shortcut
+        >
+            <div class="editor-toolbar">
                 <CopyToClipboard content=program.text class="copy-button" tooltip_below=true>
                     <i class="far fa-copy"></i>
                 </CopyToClipboard>
+                <button class="copy-button" title="Format code" on:click=format_program>
+                    <i class="fas fa-align-left"></i>
+                </button>
+                <Show when=move || !fullscreen.get()>
+                    <button
+                        class="copy-button"
+                        title="Fullscreen editor (F11)"
+                        on:click=toggle_fullscreen
+                    >
+                        <i class="fas fa-expand"></i>
+                    </button>
+                </Show>
             </div>
-            <textarea
-                class="program-input-field"
-                placeholder="Enter your program here"
-                rows="25"
-                cols="80"
-                spellcheck="false"
-                prop:value=program.text
-                on:input=update_program_text
-                on:keydown=handle_keydown
-                node_ref=textarea_ref
-            >
-                {program.text.get_untracked()}
-            </textarea>
+            <Show when=move || fullscreen.get()>
+                <button class="flat-button exit-fullscreen" on:click=exit_fullscreen>
+                    <i class="fas fa-compress"></i>
+                    "Exit fullscreen"
+                </button>
+            </Show>
+            <Editor
+                value=program.text
+                on_change=update_program_text
+                annotations=annotations
+                lints=lints
+            />
         </div>
     }
 }
@@ -1,4 +1,6 @@
-use leptos::{component, ev, use_context, view, IntoView, SignalGet};
+use leptos::{
+    component, ev, on_cleanup, use_context, view, window_event_listener, IntoView, SignalGet,
+};
 
 use crate::components::program_window::Runtime;
 use crate::components::state::update_local_storage;
@@ -12,6 +14,15 @@ pub fn RunButton() -> impl IntoView {
         update_local_storage();
         runtime.run();
     };
+    // Ctrl+Enter on Windows / Linux, Cmd+Enter on macOS
+    let shortcut_handle = window_event_listener(ev::keydown, move |event| {
+        if (event.ctrl_key() || event.meta_key()) && event.key() == "Enter" {
+            event.prevent_default();
+            update_local_storage();
+            runtime.run();
+        }
+    });
+    on_cleanup(move || shortcut_handle.remove());
     let button_class = move || match runtime.run_succeeded.get() {
         None => "button run-button",
         Some(false) => "button run-button failure",
@@ -19,13 +30,17 @@ pub fn RunButton() -> impl IntoView {
     };
 
     view! {
-        <button
-            class=button_class
-            on:click=run_program
-        >
-            <i class="fas fa-play"></i>
-            " Run"
-        </button>
+        <div class="tooltip-below run-shortcut">
+            <button
+                class=button_class
+                disabled=move || runtime.compiling.get()
+                on:click=run_program
+            >
+                <span class="tooltip-text">"Ctrl / ⌘ + Enter"</span>
+                <i class="fas fa-play"></i>
+                " Run"
+            </button>
+        </div>
         <audio
             preload="auto"
             node_ref=audio_ref
@@ -1,14 +1,132 @@
-use leptos::{component, view, IntoView};
+use std::io::{Read, Write};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use leptos::{component, ev, use_context, view, IntoView, Signal, SignalWithUntracked};
 
 use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::program_window::Program;
+
+/// Prefix of the URL fragment that contains a shared program.
+const PROGRAM_FRAGMENT_PREFIX: &str = "#program=";
+/// Maximum length of a shared program in bytes.
+///
+/// Longer programs are rejected, so a crafted link cannot decompress to gigabytes.
+const MAX_PROGRAM_LEN: u64 = 1024 * 1024;
+
+/// Compress the program `text` and encode it for use inside a URL.
+pub fn encode_program(text: &str) -> String {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder
+        .write_all(text.as_bytes())
+        .expect("writing to vector should never fail");
+    let compressed = encoder
+        .finish()
+        .expect("writing to vector should never fail");
+    URL_SAFE_NO_PAD.encode(compressed)
+}
+
+/// Decode a program that was encoded with [`encode_program`].
+///
+/// Return `None` if the encoding is ill-formatted
+/// or if the program is longer than [`MAX_PROGRAM_LEN`].
+pub fn decode_program(encoded: &str) -> Option<String> {
+    let compressed = URL_SAFE_NO_PAD.decode(encoded).ok()?;
+    let mut text = String::new();
+    GzDecoder::new(compressed.as_slice())
+        .take(MAX_PROGRAM_LEN + 1)
+        .read_to_string(&mut text)
+        .ok()?;
+    (text.len() as u64 <= MAX_PROGRAM_LEN).then_some(text)
+}
+
+/// Read the shared program from the fragment of the current URL, if there is one.
+pub fn program_from_url_hash() -> Option<String> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    hash.strip_prefix(PROGRAM_FRAGMENT_PREFIX)
+        .and_then(decode_program)
+}
+
+/// Return the URL of the current page with the program `text` in its fragment.
+fn share_url(text: &str) -> Option<String> {
+    let location = web_sys::window()?.location();
+    let origin = location.origin().ok()?;
+    let pathname = location.pathname().ok()?;
+    Some(format!(
+        "{origin}{pathname}{PROGRAM_FRAGMENT_PREFIX}{}",
+        encode_program(text)
+    ))
+}
 
 #[component]
 pub fn ShareButton() -> impl IntoView {
-    let url = move || "Sharing is temporarily disabled".to_string();
+    let program = use_context::<Program>().expect("program should exist in context");
+
+    let share_program = move |_event: ev::MouseEvent| {
+        let fragment = program
+            .text
+            .with_untracked(|text| format!("{PROGRAM_FRAGMENT_PREFIX}{}", encode_program(text)));
+        if let Some(window) = web_sys::window() {
+            let _result = window.location().set_hash(&fragment);
+        }
+    };
+    // Compress the program only when the link is copied
+    let url = Signal::derive(move || {
+        program
+            .text
+            .with_untracked(|text| share_url(text))
+            .unwrap_or_else(|| "Failed to create link".to_string())
+    });
+
     view! {
-        <CopyToClipboard content=url class="button" tooltip_below=true>
+        <button class="button" on:click=share_program>
             <i class="fa-solid fa-share-nodes"></i>
             " Share"
+        </button>
+        <CopyToClipboard content=url class="button" tooltip_below=true>
+            <i class="fa-solid fa-link"></i>
+            " Copy link"
         </CopyToClipboard>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn encode_decode_program() {
+        let text = crate::examples::get("⚡ HTLC")
+            .expect("HTLC example should exist")
+            .template_text()
+            .chars()
+            .cycle()
+            .take(500)
+            .collect::<String>();
+        let encoded = encode_program(&text);
+        assert!(encoded
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        assert_eq!(Some(text), decode_program(&encoded));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn decode_garbage() {
+        assert_eq!(None, decode_program("not a program"));
+        assert_eq!(None, decode_program(&URL_SAFE_NO_PAD.encode(b"not gzip")));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn decode_long_program() {
+        let text = "a".repeat(MAX_PROGRAM_LEN as usize);
+        assert_eq!(Some(text.clone()), decode_program(&encode_program(&text)));
+        let text = "a".repeat(MAX_PROGRAM_LEN as usize + 1);
+        assert_eq!(None, decode_program(&encode_program(&text)));
+    }
+}
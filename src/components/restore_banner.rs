@@ -0,0 +1,46 @@
+use leptos::{
+    component, create_rw_signal, ev, use_context, view, IntoView, Show, SignalGetUntracked,
+    SignalSet, SignalWith, SignalWithUntracked,
+};
+
+use crate::components::program_window::Program;
+use crate::components::state::{last_manual_save, latest_autosave};
+
+/// Banner that offers to restore the latest automatic snapshot of the program
+/// if it is newer than the last manual save and differs from the current program.
+#[component]
+pub fn RestoreBanner() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let unsaved = latest_autosave().filter(|(timestamp, source)| {
+        last_manual_save() < *timestamp && program.text.with_untracked(|text| text != source)
+    });
+    let seconds_ago = unsaved
+        .as_ref()
+        .map(|(timestamp, _)| (js_sys::Date::now() as u64).saturating_sub(*timestamp) / 1000)
+        .unwrap_or(0);
+    let unsaved_source = create_rw_signal(unsaved.map(|(_, source)| source));
+
+    let restore = move |_event: ev::MouseEvent| {
+        if let Some(source) = unsaved_source.get_untracked() {
+            program.text.set(source);
+        }
+        unsaved_source.set(None);
+    };
+    let dismiss = move |_event: ev::MouseEvent| unsaved_source.set(None);
+
+    view! {
+        <Show when=move || unsaved_source.with(Option::is_some)>
+            <div class="restore-banner">
+                <span class="restore-banner-message">
+                    {format!("Restore unsaved changes from {seconds_ago} seconds ago?")}
+                </span>
+                <button class="flat-button bordered" type="button" on:click=restore>
+                    Restore
+                </button>
+                <button class="flat-button bordered" type="button" on:click=dismiss>
+                    Dismiss
+                </button>
+            </div>
+        </Show>
+    }
+}
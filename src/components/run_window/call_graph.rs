@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use leptos::{
+    component, create_memo, create_rw_signal, use_context, view, CollectView, IntoView, Show,
+    SignalGet, SignalSet, SignalWith,
+};
+use simfony::simplicity;
+use simplicity::jet::Elements;
+use simplicity::Value;
+
+use crate::components::program_window::Runtime;
+use crate::function::JetCallEntry;
+use crate::{jet, jet_index};
+
+/// Maximum depth of the product components of a jet input that are matched against earlier outputs.
+const MAX_ARGUMENT_DEPTH: usize = 4;
+
+const LAYER_WIDTH: f64 = 160.0;
+const ROW_HEIGHT: f64 = 70.0;
+const MARGIN: f64 = 50.0;
+const MIN_RADIUS: f64 = 8.0;
+const MAX_RADIUS: f64 = 28.0;
+
+/// Jet that was invoked at least once.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GraphNode {
+    pub name: String,
+    /// Number of invocations.
+    pub count: usize,
+    /// Execution cost of a single invocation in milliweight.
+    pub cost: Option<u32>,
+    /// Column of the node, so that data flows from left to right.
+    pub layer: usize,
+    /// Row of the node inside its layer.
+    pub row: usize,
+}
+
+/// Data that flowed from the output of one jet into the input of another jet.
+///
+/// Nodes are identified by their index.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct GraphEdge {
+    pub from: usize,
+    pub to: usize,
+    /// Number of times that data flowed along the edge.
+    pub count: usize,
+}
+
+/// Graph of the jets in a jet trace.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CallGraph {
+    /// Nodes in the order of their first invocation.
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+/// Return the execution cost of the jet with the given name in milliweight.
+fn jet_cost(name: &str) -> Option<u32> {
+    Elements::from_str(name).ok().map(|jet| jet::jet_cost(&jet))
+}
+
+/// Collect the text of the product components of the value, down to the given depth.
+fn arguments(value: &Value, depth: usize, output: &mut Vec<String>) {
+    output.push(value.to_string());
+    if depth == 0 {
+        return;
+    }
+    if let Some((left, right)) = value.as_product() {
+        arguments(&left.shallow_clone(), depth - 1, output);
+        arguments(&right.shallow_clone(), depth - 1, output);
+    }
+}
+
+impl CallGraph {
+    /// Build the call graph of the given jet trace.
+    ///
+    /// The trace does not record where values come from,
+    /// so data is assumed to flow from the latest jet that output a value
+    /// into the next jet that takes this value as input or as part of its input.
+    /// Unit outputs carry no data and are ignored.
+    pub fn from_trace(trace: &[JetCallEntry]) -> Self {
+        let mut graph = CallGraph::default();
+        let mut node_index = HashMap::<&str, usize>::new();
+        let mut edge_index = HashMap::<(usize, usize), usize>::new();
+        let mut producers = HashMap::<String, usize>::new();
+
+        for entry in trace {
+            let to = *node_index
+                .entry(entry.jet_name.as_str())
+                .or_insert_with(|| {
+                    graph.nodes.push(GraphNode {
+                        name: entry.jet_name.clone(),
+                        count: 0,
+                        cost: jet_cost(&entry.jet_name),
+                        layer: 0,
+                        row: 0,
+                    });
+                    graph.nodes.len() - 1
+                });
+            graph.nodes[to].count += 1;
+
+            let mut inputs = vec![];
+            arguments(&entry.input, MAX_ARGUMENT_DEPTH, &mut inputs);
+            let mut sources = inputs
+                .iter()
+                .filter_map(|input| producers.get(input).copied())
+                .collect::<Vec<_>>();
+            sources.sort_unstable();
+            sources.dedup();
+            for from in sources {
+                let index = *edge_index.entry((from, to)).or_insert_with(|| {
+                    graph.edges.push(GraphEdge { from, to, count: 0 });
+                    graph.edges.len() - 1
+                });
+                graph.edges[index].count += 1;
+            }
+
+            if let Ok(output) = &entry.output {
+                if !output.is_unit() {
+                    producers.insert(output.to_string(), to);
+                }
+            }
+        }
+
+        graph.assign_layers();
+        graph
+    }
+
+    /// Place each node one layer after its latest predecessor.
+    ///
+    /// Edges to nodes that were invoked for the first time earlier
+    /// point backwards and do not influence the layers.
+    fn assign_layers(&mut self) {
+        for to in 0..self.nodes.len() {
+            let layer = self
+                .edges
+                .iter()
+                .filter(|edge| edge.to == to && edge.from < to)
+                .map(|edge| self.nodes[edge.from].layer + 1)
+                .max()
+                .unwrap_or(0);
+            let row = self.nodes[..to]
+                .iter()
+                .filter(|node| node.layer == layer)
+                .count();
+            self.nodes[to].layer = layer;
+            self.nodes[to].row = row;
+        }
+    }
+
+    fn position(&self, index: usize) -> (f64, f64) {
+        let node = &self.nodes[index];
+        (
+            MARGIN + node.layer as f64 * LAYER_WIDTH,
+            MARGIN + node.row as f64 * ROW_HEIGHT,
+        )
+    }
+
+    /// Return the radius of the node, such that its area is proportional to its cost.
+    fn radius(&self, index: usize) -> f64 {
+        let max_cost = self
+            .nodes
+            .iter()
+            .filter_map(|node| node.cost)
+            .max()
+            .unwrap_or(0);
+        match (self.nodes[index].cost, max_cost) {
+            (Some(cost), 1..) => {
+                MIN_RADIUS
+                    + (MAX_RADIUS - MIN_RADIUS) * (f64::from(cost) / f64::from(max_cost)).sqrt()
+            }
+            _ => MIN_RADIUS,
+        }
+    }
+
+    fn size(&self) -> (f64, f64) {
+        let layers = self
+            .nodes
+            .iter()
+            .map(|node| node.layer + 1)
+            .max()
+            .unwrap_or(0);
+        let rows = self
+            .nodes
+            .iter()
+            .map(|node| node.row + 1)
+            .max()
+            .unwrap_or(0);
+        (
+            2.0 * MARGIN + layers.saturating_sub(1) as f64 * LAYER_WIDTH,
+            2.0 * MARGIN + rows.saturating_sub(1) as f64 * ROW_HEIGHT,
+        )
+    }
+}
+
+/// Return the input and output types of the jet with the given name.
+fn jet_types(name: &str) -> (String, String) {
+    let signature = jet_index::signature(name).unwrap_or_default();
+    let (input, output) = signature
+        .strip_prefix("fn")
+        .and_then(|signature| signature.split_once(" -> "))
+        .unwrap_or(("?", "?"));
+    (input.to_string(), output.to_string())
+}
+
+#[component]
+pub fn CallGraphPanel() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let trace_is_nonempty = move || runtime.jet_trace.with(|trace| !trace.is_empty());
+    let graph = create_memo(move |_| runtime.jet_trace.with(|trace| CallGraph::from_trace(trace)));
+    let hovered = create_rw_signal(None::<usize>);
+    let graph_view = move || {
+        let graph = graph.get();
+        let (width, height) = graph.size();
+        let edges = graph
+            .edges
+            .iter()
+            .filter(|edge| edge.from != edge.to)
+            .map(|edge| {
+                let (x1, y1) = graph.position(edge.from);
+                let (x2, y2) = graph.position(edge.to);
+                // End the line at the border of the target node, so the arrow is visible
+                let length = (x2 - x1).hypot(y2 - y1).max(1.0);
+                let shorten = graph.radius(edge.to) / length;
+                let (x2, y2) = (x2 - (x2 - x1) * shorten, y2 - (y2 - y1) * shorten);
+                view! {
+                    <line
+                        class="call-graph-edge"
+                        x1=x1
+                        y1=y1
+                        x2=x2
+                        y2=y2
+                        stroke-width=1.0 + (edge.count as f64).log2()
+                        marker-end="url(#call-graph-arrow)"
+                    />
+                }
+            })
+            .collect_view();
+        let nodes = (0..graph.nodes.len())
+            .map(|index| {
+                let node = &graph.nodes[index];
+                let (x, y) = graph.position(index);
+                view! {
+                    <g
+                        class="call-graph-node"
+                        class:hovered=move || hovered.get() == Some(index)
+                        on:mouseenter=move |_| hovered.set(Some(index))
+                        on:mouseleave=move |_| hovered.set(None)
+                    >
+                        <circle cx=x cy=y r=graph.radius(index) />
+                        <text x=x y=y + graph.radius(index) + 14.0 text-anchor="middle">
+                            {node.name.clone()}
+                        </text>
+                    </g>
+                }
+            })
+            .collect_view();
+
+        view! {
+            <svg
+                class="call-graph"
+                width=width
+                height=height
+                viewBox=format!("0 0 {width} {height}")
+            >
+                <defs>
+                    <marker
+                        id="call-graph-arrow"
+                        viewBox="0 0 10 10"
+                        refX="10"
+                        refY="5"
+                        markerWidth="6"
+                        markerHeight="6"
+                        orient="auto-start-reverse"
+                    >
+                        <path d="M 0 0 L 10 5 L 0 10 z" />
+                    </marker>
+                </defs>
+                <g class="call-graph-edges">{edges}</g>
+                <g class="call-graph-nodes">{nodes}</g>
+            </svg>
+        }
+    };
+
+    let node_details = move || {
+        let index = hovered.get()?;
+        graph.with(|graph| {
+            let node = graph.nodes.get(index)?;
+            let (input, output) = jet_types(&node.name);
+            let cost = node
+                .cost
+                .map(|cost| format!("{cost} mWU"))
+                .unwrap_or_else(|| "unknown".to_string());
+            let self_loops = graph
+                .edges
+                .iter()
+                .find(|edge| edge.from == index && edge.to == index)
+                .map(|edge| edge.count)
+                .unwrap_or(0);
+            Some(view! {
+                <div class="call-graph-details">
+                    <div class="call-graph-name">{node.name.clone()}</div>
+                    <div>"Input: " {input}</div>
+                    <div>"Output: " {output}</div>
+                    <div>"Invocations: " {node.count}</div>
+                    <div>"Cost: " {cost}</div>
+                    {(0 < self_loops)
+                        .then(|| view! { <div>"Feeds itself: " {self_loops} " time(s)"</div> })}
+                </div>
+            })
+        })
+    };
+
+    view! {
+        <Show when=trace_is_nonempty>
+            <details class="call-graph-panel">
+                <summary>"Jet call graph"</summary>
+                <p class="tab-description">
+                    "Node size shows the cost of a jet. Arrows show outputs that became inputs of later jets. Hover over a jet for details."
+                </p>
+                <div class="call-graph-container">{graph_view}</div>
+                {node_details}
+            </details>
+        </Show>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(jet_name: &str, input: Value, output: Value) -> JetCallEntry {
+        JetCallEntry {
+            jet_name: jet_name.to_string(),
+            input,
+            output: Ok(output),
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn graph_from_trace() {
+        let hash = Value::u256([1; 32]);
+        let other_hash = Value::u256([2; 32]);
+        let trace = [
+            entry("sha_256_ctx_8_init", Value::unit(), hash.shallow_clone()),
+            entry(
+                "sha_256_ctx_8_finalize",
+                hash.shallow_clone(),
+                other_hash.shallow_clone(),
+            ),
+            entry(
+                "eq_256",
+                Value::product(other_hash.shallow_clone(), hash.shallow_clone()),
+                Value::unit(),
+            ),
+            entry("sha_256_ctx_8_finalize", hash.shallow_clone(), other_hash),
+        ];
+        let graph = CallGraph::from_trace(&trace);
+
+        assert_eq!(
+            vec![
+                ("sha_256_ctx_8_init", 1, 0, 0),
+                ("sha_256_ctx_8_finalize", 2, 1, 0),
+                ("eq_256", 1, 2, 0),
+            ],
+            graph
+                .nodes
+                .iter()
+                .map(|node| (node.name.as_str(), node.count, node.layer, node.row))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                GraphEdge {
+                    from: 0,
+                    to: 1,
+                    count: 2
+                },
+                GraphEdge {
+                    from: 0,
+                    to: 2,
+                    count: 1
+                },
+                GraphEdge {
+                    from: 1,
+                    to: 2,
+                    count: 1
+                },
+            ],
+            graph.edges
+        );
+        assert!(graph.nodes.iter().all(|node| node.cost.is_some()));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn types_from_signature() {
+        assert_eq!(
+            ("(u8, u8)".to_string(), "(bool, u8)".to_string()),
+            jet_types("add_8")
+        );
+        assert_eq!(("?".to_string(), "?".to_string()), jet_types("no_such_jet"));
+    }
+}
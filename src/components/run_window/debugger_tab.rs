@@ -0,0 +1,155 @@
+use std::collections::BTreeSet;
+
+use leptos::{
+    component, create_rw_signal, ev, event_target_value, use_context, view, For, IntoView,
+    RwSignal, Show, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith,
+};
+
+use crate::components::program_window::{Program, Runtime};
+use crate::components::string_box::{ErrorBox, NeutralBox};
+
+/// Nodes of the program where stepping pauses.
+///
+/// Nodes are identified by their index in post order,
+/// which is the order in which the Merkle graph lists them.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Breakpoints(pub RwSignal<BTreeSet<usize>>);
+
+#[component]
+pub fn DebuggerTab() -> impl IntoView {
+    view! {
+        <div class="tab-content debugger-tab">
+            <DebuggerControls />
+            <AddBreakpoint />
+            <BreakpointList />
+        </div>
+    }
+}
+
+#[component]
+fn DebuggerControls() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let cannot_run = move || program.lazy_cmr.with(|cmr| cmr.is_err());
+    let continue_label = move || match runtime.stepping.get() {
+        true => "Continue",
+        false => "Start",
+    };
+    let continue_to_breakpoint = move |_event: ev::MouseEvent| runtime.continue_to_breakpoint();
+    let step = move |_event: ev::MouseEvent| runtime.step();
+    let stop = move |_event: ev::MouseEvent| runtime.stop_stepping();
+    let paused_message = move || {
+        runtime.paused_at.get().map(|index| {
+            let cursor = runtime.step_result.with(|result| {
+                result
+                    .as_ref()
+                    .map(|result| format!(" after executing {}", result.cursor))
+                    .unwrap_or_default()
+            });
+            format!("Paused at breakpoint on node {index}{cursor}")
+        })
+    };
+    let error_output = runtime.error_output;
+
+    view! {
+        <div class="tab-title-group">
+            <h3 class="tab-title">"Debugger"</h3>
+        </div>
+        <p class="tab-description">
+            "Run the program until it reaches a breakpoint. Breakpoints are set on Simplicity nodes by their index in post order."
+        </p>
+        <div class="execution-display-row">
+            <button class="button" disabled=cannot_run on:click=continue_to_breakpoint>
+                {continue_label}
+            </button>
+            <Show when=move || runtime.stepping.get()>
+                <button class="button" on:click=step>
+                    "Step"
+                </button>
+                <button class="button" on:click=stop>
+                    "Stop"
+                </button>
+            </Show>
+        </div>
+        <NeutralBox neutral=Signal::derive(move || paused_message().unwrap_or_default()) />
+        <ErrorBox error=error_output />
+    }
+}
+
+#[component]
+fn AddBreakpoint() -> impl IntoView {
+    let breakpoints = use_context::<Breakpoints>().expect("breakpoints should exist in context");
+    let node_index = create_rw_signal(String::new());
+    let error = create_rw_signal(String::new());
+
+    let update_node_index = move |event: ev::Event| node_index.set(event_target_value(&event));
+    let add_breakpoint = move |event: ev::SubmitEvent| {
+        event.prevent_default();
+        match node_index.get_untracked().trim().parse::<usize>() {
+            Ok(index) => {
+                breakpoints.0.update(|set| {
+                    set.insert(index);
+                });
+                node_index.update(String::clear);
+                error.update(String::clear);
+            }
+            Err(..) => error.set("Node index must be a non-negative integer".to_string()),
+        }
+    };
+
+    view! {
+        <form class="key-store-display-row" on:submit=add_breakpoint>
+            <input
+                class="input"
+                type="text"
+                placeholder="Node index"
+                prop:value=node_index
+                on:input=update_node_index
+            />
+            <button class="flat-button bordered" type="submit">
+                "Add breakpoint"
+            </button>
+        </form>
+        <ErrorBox error=error />
+    }
+}
+
+#[component]
+fn BreakpointList() -> impl IntoView {
+    let breakpoints = use_context::<Breakpoints>().expect("breakpoints should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let clear_all = move |_event: ev::MouseEvent| breakpoints.0.update(BTreeSet::clear);
+
+    view! {
+        <Show
+            when=move || breakpoints.0.with(|set| !set.is_empty())
+            fallback=|| view! { <p class="tab-description">"No breakpoints set."</p> }
+        >
+            <ul class="breakpoint-list">
+                <For
+                    each=move || breakpoints.0.get()
+                    key=|index| *index
+                    let:index
+                >
+                    <li
+                        class="key-store-display-row"
+                        class:paused=move || runtime.paused_at.get() == Some(index)
+                    >
+                        <span class="display-row-label">{format!("Node {index}")}</span>
+                        <button
+                            class="flat-button bordered"
+                            on:click=move |_| breakpoints.0.update(|set| {
+                                set.remove(&index);
+                            })
+                        >
+                            <i class="fas fa-trash"></i>
+                        </button>
+                    </li>
+                </For>
+            </ul>
+            <button class="flat-button bordered" on:click=clear_all>
+                "Remove all"
+            </button>
+        </Show>
+    }
+}
@@ -0,0 +1,44 @@
+use leptos::{component, ev, view, CollectView, IntoView};
+
+use crate::components::program_window::select_example;
+use crate::examples;
+
+#[component]
+pub fn ExamplesTab() -> impl IntoView {
+    let cards = examples::keys()
+        .map(|name| view! { <ExampleCard name=name /> })
+        .collect_view();
+
+    view! {
+        <div class="tab-content examples-tab">
+            <p class="tab-description">
+                "Load an example to get started. Loading an example replaces the current program."
+            </p>
+            <div class="example-grid">
+                {cards}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ExampleCard(name: &'static str) -> impl IntoView {
+    let example = examples::get(name).expect("example should exist");
+    let summary = example.description().lines().next().unwrap_or_default();
+    let load_example = move |_event: ev::MouseEvent| select_example(example);
+
+    view! {
+        <div class="example-card">
+            <h3 class="example-card-title">
+                {name}
+            </h3>
+            <p class="tab-description">
+                {summary}
+            </p>
+            <button class="flat-button bordered is-small" type="button" on:click=load_example>
+                <i class="fas fa-file-import"></i>
+                Load
+            </button>
+        </div>
+    }
+}
@@ -1,8 +1,27 @@
+use hex_conservative::{DisplayHex, FromHex};
+use itertools::Itertools;
 use js_sys::Date;
-use leptos::{component, use_context, view, IntoView, SignalWith};
+use leptos::{
+    component, create_rw_signal, ev, event_target_checked, event_target_value, use_context, view,
+    Children, CollectView, IntoView, Show, Signal, SignalGet, SignalGetUntracked, SignalSet,
+    SignalUpdate, SignalWith,
+};
+use simfony::elements;
 
-use crate::components::program_window::Runtime;
+use super::call_graph::CallGraphPanel;
+use super::resource_meter::ResourceMeter;
+use super::test_cases_panel::TestCasesPanel;
+use super::witness_form::{WitnessForm, WitnessTrees};
+use super::TxEnv;
+use crate::components::copy_button::CopyButton;
+use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::program_window::{Program, Runtime};
+use crate::components::spinner::Spinner;
 use crate::components::string_box::{ErrorBox, NeutralBox, SuccessBox};
+use crate::components::value_tree::ValueTree;
+use crate::util::{
+    replace_witness_module, spend_witness_weight, strip_witness_module, ProgramMetrics,
+};
 
 #[component]
 pub fn ExecutionTab() -> impl IntoView {
@@ -21,10 +40,697 @@ pub fn ExecutionTab() -> impl IntoView {
     };
 
     view! {
-        <div class="tab-content">
+        <div class="tab-content execution-tab">
+            <Spinner active=runtime.compiling />
+            <RunDuration />
             <SuccessBox success=success_string />
             <ErrorBox error=failure_string />
             <NeutralBox neutral=runtime.debug_output />
+            <FinalStack />
+            <ExecutionHistory />
+            <StepControls />
+            <Environment />
+            <WitnessForm />
+            <WitnessTrees />
+            <JetTrace />
+            <CallGraphPanel />
+            <ProgramRoots />
+            <Metrics />
+            <ResourceMeter />
+            <TestCasesPanel />
+        </div>
+    }
+}
+
+#[component]
+fn RunDuration() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let last_duration = move || {
+        runtime
+            .run_durations
+            .with(|durations| durations.last().map(|ms| format!("{ms:.1} ms")))
+    };
+    let average_duration = move || {
+        runtime.run_durations.with(|durations| {
+            (durations.len() > 1).then(|| {
+                let average = durations.iter().sum::<f64>() / durations.len() as f64;
+                format!("avg of last {}: {average:.1} ms", durations.len())
+            })
+        })
+    };
+
+    view! {
+        <Show when=move || runtime.run_durations.with(|durations| !durations.is_empty())>
+            <div class="execution-display-row">
+                <span class="timing-badge">{last_duration}</span>
+                <span class="timing-average">{average_duration}</span>
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+fn ExecutionHistory() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let history_is_nonempty = move || runtime.history.with(|history| !history.is_empty());
+    let history_summary = move || {
+        runtime
+            .history
+            .with(|history| format!("History ({} runs)", history.len()))
+    };
+    let history_rows = move || {
+        runtime.history.with(|history| {
+            history
+                .iter()
+                .rev()
+                .map(|record| {
+                    let witness = record.witness.clone();
+                    let restore_witness = move |_event: ev::MouseEvent| {
+                        program.text.update(|text| {
+                            *text = match witness.is_empty() {
+                                true => strip_witness_module(text),
+                                false => replace_witness_module(text, &witness),
+                            };
+                        });
+                    };
+                    let time = Date::new(&record.timestamp.into())
+                        .to_iso_string()
+                        .as_string()
+                        .and_then(|iso| iso.get(11..19).map(str::to_string))
+                        .unwrap_or_default();
+                    let (result, error) = match &record.result {
+                        Ok(..) => ("Success", String::new()),
+                        Err(error) => ("Failure", error.clone()),
+                    };
+                    let duration = record
+                        .duration_ms
+                        .map(|ms| format!("{ms:.1} ms"))
+                        .unwrap_or_default();
+                    let witness_display = record.witness.split_whitespace().join(" ");
+
+                    view! {
+                        <tr
+                            title="Click to restore this witness"
+                            on:click=restore_witness
+                        >
+                            <td>{time}</td>
+                            <td class=result.to_lowercase() title=error>{result}</td>
+                            <td>{duration}</td>
+                            <td class="history-witness">{witness_display}</td>
+                        </tr>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <Show when=history_is_nonempty>
+            <details class="execution-history">
+                <summary>{history_summary}</summary>
+                <table>
+                    <thead>
+                        <tr>
+                            <th>"Time"</th>
+                            <th>"Result"</th>
+                            <th>"Duration"</th>
+                            <th>"Witness"</th>
+                        </tr>
+                    </thead>
+                    <tbody>{history_rows}</tbody>
+                </table>
+            </details>
+        </Show>
+    }
+}
+
+#[component]
+fn FinalStack() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let stack_is_nonempty = move || runtime.final_stack.with(|stack| !stack.is_empty());
+    let stack_entries = move || {
+        runtime.final_stack.with(|stack| {
+            stack
+                .iter()
+                .rev()
+                .enumerate()
+                .map(|(depth, value)| {
+                    let content = value.to_string();
+                    view! {
+                        <li>
+                            <span class="stack-depth">{depth}</span>
+                            <ValueTree value=value.shallow_clone() />
+                            <CopyButton content=move || content.clone() />
+                        </li>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <Show when=stack_is_nonempty>
+            <details class="final-stack">
+                <summary>"Stack (top first)"</summary>
+                <ol>{stack_entries}</ol>
+            </details>
+        </Show>
+    }
+}
+
+#[component]
+fn StepControls() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let cannot_step = move || program.lazy_cmr.with(|cmr| cmr.is_err());
+    let step_label = move || match runtime.stepping.get() {
+        true => "Next step",
+        false => "Step",
+    };
+    let step = move |_event: ev::MouseEvent| runtime.step();
+    let stop = move |_event: ev::MouseEvent| runtime.stop_stepping();
+    let step_lines = move || {
+        runtime.step_result.with(|step| {
+            step.as_ref().map(|step| {
+                let jet = step
+                    .jet
+                    .as_ref()
+                    .map(|jet| format!("\nJet: {jet}"))
+                    .unwrap_or_default();
+                let input = step.input_stack.iter().rev().join("\n");
+                let output = step.output_stack.iter().rev().join("\n");
+                format!(
+                    "Executed: {}{jet}\n\nInput stack (top first):\n{input}\n\nOutput stack (top first):\n{output}",
+                    step.cursor
+                )
+            })
+        })
+    };
+
+    view! {
+        <div class="execution-display-row">
+            <button class="button" disabled=cannot_step on:click=step>
+                {step_label}
+            </button>
+            <Show when=move || runtime.stepping.get()>
+                <button class="button" on:click=stop>
+                    "Stop"
+                </button>
+            </Show>
+        </div>
+        <pre class="step-result">{step_lines}</pre>
+    }
+}
+
+#[component]
+fn Environment() -> impl IntoView {
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let params = tx_env.params;
+    let version_error = create_rw_signal(String::new());
+    let lock_time_error = create_rw_signal(String::new());
+    let input_index_error = create_rw_signal(String::new());
+    let value_error = create_rw_signal(String::new());
+    let script_pubkey_error = create_rw_signal(String::new());
+    let sequence_error = create_rw_signal(String::new());
+    let annex_error = create_rw_signal(String::new());
+    let import_error = create_rw_signal(String::new());
+
+    let update_version = move |e: ev::Event| match event_target_value(&e).parse::<u32>() {
+        Ok(version) => {
+            params.update(|x| x.version = version);
+            version_error.update(String::clear);
+        }
+        Err(error) => version_error.set(error.to_string()),
+    };
+    let update_lock_time = move |e: ev::Event| match event_target_value(&e).parse::<u32>() {
+        Ok(lock_time) => {
+            let lock_time = elements::LockTime::from_consensus(lock_time);
+            params.update(|x| x.lock_time = lock_time);
+            lock_time_error.update(String::clear);
+        }
+        Err(error) => lock_time_error.set(error.to_string()),
+    };
+    let update_input_index = move |e: ev::Event| match event_target_value(&e).parse::<u32>() {
+        Ok(input_index) if input_index < MAX_INPUT_INDEX => {
+            params.update(|x| x.input_index = input_index);
+            input_index_error.update(String::clear);
+        }
+        Ok(..) => input_index_error.set(format!("Input index must be below {MAX_INPUT_INDEX}")),
+        Err(error) => input_index_error.set(error.to_string()),
+    };
+    let update_value = move |e: ev::Event| match event_target_value(&e).parse::<u64>() {
+        Ok(value_in) => {
+            params.update(|x| x.value_in = value_in);
+            value_error.update(String::clear);
+        }
+        Err(error) => value_error.set(error.to_string()),
+    };
+    let update_script_pubkey = move |e: ev::Event| {
+        let text = event_target_value(&e);
+        let text = text.trim().trim_start_matches("0x");
+        match Vec::<u8>::from_hex(text) {
+            Ok(bytes) => {
+                let script_pubkey = match bytes.is_empty() {
+                    true => None,
+                    false => Some(elements::Script::from(bytes)),
+                };
+                params.update(|x| x.script_pubkey = script_pubkey);
+                script_pubkey_error.update(String::clear);
+            }
+            Err(error) => script_pubkey_error.set(error.to_string()),
+        }
+    };
+    let update_sequence = move |e: ev::Event| match event_target_value(&e).parse::<u32>() {
+        Ok(sequence) => {
+            let sequence = elements::Sequence::from_consensus(sequence);
+            params.update(|x| x.sequence = sequence);
+            sequence_error.update(String::clear);
+        }
+        Err(error) => sequence_error.set(error.to_string()),
+    };
+    let update_annex = move |e: ev::Event| {
+        let text = event_target_value(&e);
+        let text = text.trim().trim_start_matches("0x");
+        match Vec::<u8>::from_hex(text) {
+            Ok(bytes) if bytes.is_empty() => {
+                params.update(|x| x.annex = None);
+                annex_error.update(String::clear);
+            }
+            Ok(bytes) if bytes[0] == ANNEX_TAG => {
+                params.update(|x| x.annex = Some(bytes));
+                annex_error.update(String::clear);
+            }
+            Ok(..) => annex_error.set(format!("Annex must start with 0x{ANNEX_TAG:02x}")),
+            Err(error) => annex_error.set(error.to_string()),
+        }
+    };
+    let import_transaction = move |e: ev::Event| {
+        let tx_hex = event_target_value(&e);
+        if tx_hex.trim().is_empty() {
+            import_error.update(String::clear);
+            return;
+        }
+        let mut imported = params.get_untracked();
+        match imported.import_transaction(&tx_hex, imported.input_index) {
+            Ok(()) => {
+                params.set(imported);
+                import_error.update(String::clear);
+            }
+            Err(error) => import_error.set(error),
+        }
+    };
+
+    view! {
+        <details class="environment">
+            <summary>Environment</summary>
+            <textarea
+                class="input transaction-hex-input"
+                rows="3"
+                spellcheck="false"
+                placeholder="Paste transaction hex (uses the input index below)"
+                on:input=import_transaction
+            ></textarea>
+            <ErrorBox error=import_error />
+            <EnvironmentRow name="Version" error=version_error>
+                <input
+                    class="input"
+                    type="number"
+                    min=0
+                    on:input=update_version
+                    prop:value=move || params.with(|x| x.version.to_string())
+                />
+            </EnvironmentRow>
+            <EnvironmentRow name="Lock time" error=lock_time_error>
+                <input
+                    class="input"
+                    type="number"
+                    min=0
+                    on:input=update_lock_time
+                    prop:value=move || params.with(|x| x.lock_time.to_consensus_u32().to_string())
+                />
+            </EnvironmentRow>
+            <EnvironmentRow name="Input index" error=input_index_error>
+                <input
+                    class="input"
+                    type="number"
+                    min=0
+                    max=MAX_INPUT_INDEX - 1
+                    on:input=update_input_index
+                    prop:value=move || params.with(|x| x.input_index.to_string())
+                />
+            </EnvironmentRow>
+            <EnvironmentRow name="Value (sats)" error=value_error>
+                <input
+                    class="input"
+                    type="number"
+                    min=0
+                    on:input=update_value
+                    prop:value=move || params.with(|x| x.value_in.to_string())
+                />
+            </EnvironmentRow>
+            <EnvironmentRow name="Script pubkey" error=script_pubkey_error>
+                <input
+                    class="input"
+                    type="text"
+                    spellcheck="false"
+                    placeholder="(Address of the program)"
+                    on:input=update_script_pubkey
+                    prop:value=move || {
+                        params
+                            .with(|x| {
+                                x.script_pubkey
+                                    .as_ref()
+                                    .map(|script| script.as_bytes().to_lower_hex_string())
+                                    .unwrap_or_default()
+                            })
+                    }
+                />
+            </EnvironmentRow>
+            <EnvironmentRow name="Sequence" error=sequence_error>
+                <input
+                    class="input"
+                    type="number"
+                    min=0
+                    on:input=update_sequence
+                    prop:value=move || params.with(|x| x.sequence.to_consensus_u32().to_string())
+                />
+            </EnvironmentRow>
+            <EnvironmentRow name="Annex" error=annex_error>
+                <input
+                    class="input"
+                    type="text"
+                    spellcheck="false"
+                    placeholder="(No annex)"
+                    on:input=update_annex
+                    prop:value=move || {
+                        params
+                            .with(|x| {
+                                x.annex
+                                    .as_ref()
+                                    .map(|annex| annex.to_lower_hex_string())
+                                    .unwrap_or_default()
+                            })
+                    }
+                />
+            </EnvironmentRow>
+        </details>
+    }
+}
+
+/// First byte of a BIP-341 annex.
+const ANNEX_TAG: u8 = 0x50;
+
+/// Upper bound on the input index, which limits the number of dummy inputs.
+const MAX_INPUT_INDEX: u32 = 256;
+
+#[component]
+fn EnvironmentRow(
+    #[prop(into)] name: String,
+    #[prop(into)] error: Signal<String>,
+    children: Children,
+) -> impl IntoView {
+    view! {
+        <div class="execution-display-row">
+            <div class="display-row-label">{name}</div>
+            {children()}
+        </div>
+        <ErrorBox error=error />
+    }
+}
+
+#[component]
+fn JetTrace() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let toggle_verbose = move |event: ev::Event| runtime.verbose.set(event_target_checked(&event));
+    let trace_is_nonempty = move || runtime.jet_trace.with(|trace| !trace.is_empty());
+    let trace_summary = move || {
+        runtime
+            .jet_trace
+            .with(|trace| format!("Jet calls ({})", trace.len()))
+    };
+    let trace_lines = move || {
+        runtime.jet_trace.with(|trace| {
+            trace
+                .iter()
+                .map(|entry| match &entry.output {
+                    Ok(output) => format!("{}({}) = {}", entry.jet_name, entry.input, output),
+                    Err(error) => format!("{}({}): {}", entry.jet_name, entry.input, error),
+                })
+                .join("\n")
+        })
+    };
+
+    view! {
+        <label class="execution-checkbox">
+            <input
+                type="checkbox"
+                prop:checked=runtime.verbose
+                on:change=toggle_verbose
+            />
+            " Trace jet calls (slower)"
+        </label>
+        <Show when=trace_is_nonempty>
+            <details class="jet-trace">
+                <summary>{trace_summary}</summary>
+                <pre>{trace_lines}</pre>
+            </details>
+        </Show>
+    }
+}
+
+#[component]
+fn ProgramRoots() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let cmr_display = Signal::derive(move || {
+        program.lazy_cmr.with(|cmr| match cmr {
+            Ok(cmr) => format!("0x{}", cmr.as_ref().as_hex()),
+            Err(..) => "(not yet compiled)".to_string(),
+        })
+    });
+    // The AMR exists only for programs whose types have been fully checked
+    let amr_is_available = move || program.lazy_amr.with(Result::is_ok);
+    let amr_display = Signal::derive(move || {
+        program.lazy_amr.with(|amr| match amr {
+            Ok(amr) => format!("0x{}", amr.as_ref().as_hex()),
+            Err(..) => "".to_string(),
+        })
+    });
+
+    view! {
+        <h3 class="tab-title">
+            Program
+        </h3>
+        <div class="execution-display-row">
+            <div class="display-row-label">
+                CMR
+            </div>
+            <input class="input" type="text" readonly=true prop:value=cmr_display />
+            <CopyToClipboard content=cmr_display class="copy-button">
+                <i class="far fa-copy"></i>
+            </CopyToClipboard>
+        </div>
+        <ProgramSize />
+        <WeightEstimate />
+        <Show when=amr_is_available>
+            <div class="execution-display-row">
+                <div class="display-row-label">
+                    AMR
+                </div>
+                <input class="input" type="text" readonly=true prop:value=amr_display />
+                <CopyToClipboard content=amr_display class="copy-button">
+                    <i class="far fa-copy"></i>
+                </CopyToClipboard>
+            </div>
+        </Show>
+    }
+}
+
+/// Maximum size of a P2SH redeem script, in bytes.
+const MAX_REDEEM_SCRIPT_SIZE: usize = 520;
+/// Maximum size of a script, in bytes.
+const MAX_SCRIPT_SIZE: usize = 10_000;
+
+#[component]
+fn ProgramSize() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let size_display = move || match program.program_size.get() {
+        Some(bits) => format!("{bits} bits ({} bytes)", bits.div_ceil(8)),
+        None => "(not yet compiled)".to_string(),
+    };
+    let size_warning = move || {
+        let bytes = program.program_size.get()?.div_ceil(8);
+        if MAX_SCRIPT_SIZE < bytes {
+            Some(format!(
+                "Exceeds script size limit of {MAX_SCRIPT_SIZE} bytes"
+            ))
+        } else if MAX_REDEEM_SCRIPT_SIZE < bytes {
+            Some(format!(
+                "Exceeds P2SH redeem script limit of {MAX_REDEEM_SCRIPT_SIZE} bytes"
+            ))
+        } else {
+            None
+        }
+    };
+
+    view! {
+        <div class="execution-display-row">
+            <div class="display-row-label">
+                Size
+            </div>
+            <div>
+                {size_display}
+            </div>
+            {move || size_warning().map(|warning| view! {
+                <span class="warning-badge">
+                    <i class="fas fa-triangle-exclamation"></i>
+                    " "
+                    {warning}
+                </span>
+            })}
+        </div>
+    }
+}
+
+/// Estimated weight and fee of spending the program from a tap tree.
+#[component]
+fn WeightEstimate() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let proof_depth = create_rw_signal(0usize);
+    let fee_rate = create_rw_signal(1.0f64);
+    let weight = move || {
+        let program_bytes = program.program_size.get()?.div_ceil(8);
+        let witness_bytes = program.metrics.with(|metrics| {
+            metrics
+                .as_ref()
+                .map(|metrics| metrics.witness_bits.div_ceil(8))
+        })?;
+        Some(spend_witness_weight(
+            program_bytes,
+            witness_bytes,
+            proof_depth.get(),
+        ))
+    };
+    let vbytes = move || weight().map(|weight| weight.div_ceil(4));
+    let weight_display = move || match (weight(), vbytes()) {
+        (Some(weight), Some(vbytes)) => format!("{weight} WU ({vbytes} vB)"),
+        _ => "(not yet satisfied)".to_string(),
+    };
+    let fee_display = move || {
+        let fee = (vbytes()? as f64 * fee_rate.get()).ceil();
+        Some(format!("{fee} sat"))
+    };
+    let update_proof_depth = move |event: ev::Event| {
+        if let Ok(depth) = event_target_value(&event).parse::<usize>() {
+            proof_depth.set(depth.min(MAX_PROOF_DEPTH));
+        }
+    };
+    let update_fee_rate = move |event: ev::Event| {
+        if let Ok(rate) = event_target_value(&event).parse::<f64>() {
+            if rate.is_finite() && 0.0 <= rate {
+                fee_rate.set(rate);
+            }
+        }
+    };
+
+    view! {
+        <div class="execution-display-row">
+            <div class="display-row-label">
+                Weight
+            </div>
+            <div title="Witness of a Simplicity leaf: witness, program, CMR and control block">
+                {weight_display}
+            </div>
+        </div>
+        <div class="execution-display-row weight-estimate">
+            <label>
+                "Tap tree depth "
+                <input
+                    class="input"
+                    type="number"
+                    min="0"
+                    max=MAX_PROOF_DEPTH
+                    prop:value=move || proof_depth.get().to_string()
+                    on:input=update_proof_depth
+                />
+            </label>
+            <label>
+                "Fee rate (sat/vB) "
+                <input
+                    class="input"
+                    type="number"
+                    min="0"
+                    step="0.1"
+                    prop:value=move || fee_rate.get().to_string()
+                    on:input=update_fee_rate
+                />
+            </label>
+            <div>{fee_display}</div>
+        </div>
+    }
+}
+
+/// Maximum number of hashes in the merkle proof of a control block.
+const MAX_PROOF_DEPTH: usize = 128;
+
+/// Soft limit on the number of nodes of a program.
+const SOFT_MAX_NODES: usize = 1 << 15;
+/// Soft limit on the number of distinct types of a program.
+const SOFT_MAX_TYPES: usize = 1 << 10;
+/// Soft limit on the depth of a program.
+const SOFT_MAX_DEPTH: usize = 1 << 10;
+
+#[component]
+fn Metrics() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let metrics_are_available = move || program.metrics.with(Option::is_some);
+    let metric = move |get: fn(&ProgramMetrics) -> usize| {
+        move || {
+            program
+                .metrics
+                .with(|metrics| metrics.as_ref().map(get).unwrap_or(0))
+        }
+    };
+
+    view! {
+        <Show when=metrics_are_available>
+            <details class="program-metrics">
+                <summary>Metrics</summary>
+                <MetricRow label="Nodes" value=metric(|x| x.node_count) soft_max=Some(SOFT_MAX_NODES) />
+                <MetricRow label="Jets" value=metric(|x| x.jet_count) soft_max=None />
+                <MetricRow label="Types" value=metric(|x| x.type_count) soft_max=Some(SOFT_MAX_TYPES) />
+                <MetricRow label="Depth" value=metric(|x| x.max_depth) soft_max=Some(SOFT_MAX_DEPTH) />
+            </details>
+        </Show>
+    }
+}
+
+#[component]
+fn MetricRow<F>(label: &'static str, value: F, soft_max: Option<usize>) -> impl IntoView
+where
+    F: Fn() -> usize + Copy + 'static,
+{
+    let value_display = move || match soft_max {
+        Some(max) => format!("{} / {max}", value()),
+        None => value().to_string(),
+    };
+
+    view! {
+        <div class="execution-display-row">
+            <div class="display-row-label">
+                {label}
+            </div>
+            <div class="metric-value">
+                {value_display}
+            </div>
+            {soft_max.map(|max| view! {
+                <meter
+                    min="0"
+                    max=max
+                    high=max * 3 / 4
+                    value=value
+                />
+            })}
         </div>
     }
 }
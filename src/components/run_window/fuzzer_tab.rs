@@ -0,0 +1,388 @@
+use std::sync::Arc;
+
+use elements::secp256k1_zkp as secp256k1;
+use hex_conservative::DisplayHex;
+use leptos::{
+    component, create_rw_signal, ev, event_target_value, spawn_local, use_context, view, IntoView,
+    RwSignal, Show, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith,
+    SignalWithUntracked,
+};
+use secp256k1::rand::{self, Rng, SeedableRng};
+use simfony::parse::ParseFromStr;
+use simfony::simplicity::jet::elements::ElementsEnv;
+use simfony::{elements, CompiledProgram, WitnessValues};
+
+use super::key_store_tab::random_bytes;
+use super::TxEnv;
+use crate::components::program_window::Program;
+use crate::components::string_box::{ErrorBox, NeutralBox};
+use crate::function::Runner;
+use crate::util::strip_witness_module;
+
+/// Maximum number of fuzzing iterations.
+const MAX_ITERATIONS: usize = 1000;
+/// Number of iterations between two updates of the progress bar.
+const BATCH_SIZE: usize = 25;
+
+/// Types of witnesses that the fuzzer can generate, with their bit width.
+const WITNESS_TYPES: [(&str, usize); 13] = [
+    ("bool", 1),
+    ("u1", 1),
+    ("u2", 2),
+    ("u4", 4),
+    ("u8", 8),
+    ("u16", 16),
+    ("u32", 32),
+    ("u64", 64),
+    ("u128", 128),
+    ("u256", 256),
+    ("Pubkey", 256),
+    ("Message", 256),
+    ("Signature", 512),
+];
+
+/// Witness that is declared in the type description of the fuzzer.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WitnessField {
+    pub name: String,
+    pub ty: &'static str,
+    pub bit_width: usize,
+}
+
+/// Parse a witness type description with one `NAME: TYPE` pair per line.
+///
+/// Empty lines are skipped.
+pub fn parse_witness_fields(description: &str) -> Result<Vec<WitnessField>, String> {
+    description
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let (name, ty) = line
+                .split_once(':')
+                .ok_or_else(|| format!("Line {}: expected `NAME: TYPE`", index + 1))?;
+            let name = name.trim();
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("Line {}: invalid witness name `{name}`", index + 1));
+            }
+            let ty = ty.trim().trim_end_matches(';').trim();
+            let (ty, bit_width) = WITNESS_TYPES
+                .into_iter()
+                .find(|(supported, _)| *supported == ty)
+                .ok_or_else(|| format!("Line {}: unsupported type `{ty}`", index + 1))?;
+            Ok(WitnessField {
+                name: name.to_string(),
+                ty,
+                bit_width,
+            })
+        })
+        .collect()
+}
+
+/// Convert the given bits into a Simfony literal of the given field.
+///
+/// Bits are read from the most significant to the least significant.
+fn literal_from_bits(field: &WitnessField, bits: &[bool]) -> String {
+    debug_assert_eq!(field.bit_width, bits.len());
+    if field.ty == "bool" {
+        return bits[0].to_string();
+    }
+    if field.bit_width < 4 {
+        let value = bits
+            .iter()
+            .fold(0u8, |value, bit| (value << 1) | u8::from(*bit));
+        return value.to_string();
+    }
+    let digits = bits
+        .chunks(4)
+        .map(|nibble| {
+            let digit = nibble
+                .iter()
+                .fold(0u32, |digit, bit| (digit << 1) | u32::from(*bit));
+            char::from_digit(digit, 16).expect("nibble should be a hex digit")
+        })
+        .collect::<String>();
+    format!("0x{digits}")
+}
+
+/// Witness module of randomly generated values.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FuzzCase {
+    /// Simfony text of the witness module.
+    pub witness_text: String,
+    /// Bits of all witness values in declaration order.
+    pub bits: Vec<bool>,
+}
+
+impl FuzzCase {
+    /// Construct the witness module from the given bits.
+    ///
+    /// The number of bits must equal the combined bit width of the fields.
+    pub fn from_bits(fields: &[WitnessField], bits: Vec<bool>) -> Self {
+        let mut witness_text = "mod witness {\n".to_string();
+        let mut offset = 0;
+        for field in fields {
+            let literal = literal_from_bits(field, &bits[offset..offset + field.bit_width]);
+            offset += field.bit_width;
+            witness_text.push_str(&format!(
+                "    const {}: {} = {literal};\n",
+                field.name, field.ty
+            ));
+        }
+        witness_text.push('}');
+        Self { witness_text, bits }
+    }
+
+    /// Generate random witness values for the given fields.
+    pub fn random<R: Rng>(fields: &[WitnessField], rng: &mut R) -> Self {
+        let bit_width = fields.iter().map(|field| field.bit_width).sum::<usize>();
+        let bits = (0..bit_width).map(|_| rng.gen::<bool>()).collect();
+        Self::from_bits(fields, bits)
+    }
+
+    /// Hex encoding of the witness bits, padded with zeroes to full bytes.
+    pub fn to_hex(&self) -> String {
+        self.bits
+            .chunks(8)
+            .map(|byte| {
+                byte.iter().enumerate().fold(0u8, |value, (index, bit)| {
+                    value | (u8::from(*bit) << (7 - index))
+                })
+            })
+            .collect::<Vec<u8>>()
+            .to_lower_hex_string()
+    }
+}
+
+/// Compile the given program text with the parameters that it declares.
+pub fn compile_program(text: &str) -> Result<CompiledProgram, String> {
+    simfony::Arguments::parse_from_str(text)
+        .map_err(|error| error.to_string())
+        .and_then(|args| CompiledProgram::new(text, args))
+}
+
+/// Satisfy the compiled program with the given witness module and run it.
+///
+/// The witness is parsed from the program text without witness module,
+/// prefixed by the given witness module.
+pub fn run_with_witness(
+    compiled: &CompiledProgram,
+    program_text: &str,
+    witness_text: &str,
+    env: &ElementsEnv<Arc<elements::Transaction>>,
+) -> Result<(), String> {
+    let text = format!("{witness_text}\n{program_text}");
+    let witness = WitnessValues::parse_from_str(&text).map_err(|error| error.to_string())?;
+    let satisfied = compiled.satisfy(witness)?;
+    let mut runner = Runner::for_program(satisfied);
+    runner.run(env).map_err(|error| error.to_string())
+}
+
+/// First witness that made the program fail.
+#[derive(Clone, Debug)]
+struct Failure {
+    case: FuzzCase,
+    error: String,
+}
+
+#[component]
+pub fn FuzzerTab() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+
+    let description = create_rw_signal(String::new());
+    let iterations = create_rw_signal(MAX_ITERATIONS.to_string());
+    let running = create_rw_signal(false);
+    let total = create_rw_signal(0usize);
+    let passed = create_rw_signal(0usize);
+    let failed = create_rw_signal(0usize);
+    let first_failure = create_rw_signal(None::<Failure>);
+    let error = create_rw_signal(String::new());
+
+    let update_description = move |event: ev::Event| description.set(event_target_value(&event));
+    let update_iterations = move |event: ev::Event| iterations.set(event_target_value(&event));
+
+    let fuzz = move |_event: ev::MouseEvent| {
+        error.update(String::clear);
+        passed.set(0);
+        failed.set(0);
+        first_failure.set(None);
+
+        let count = match iterations.get_untracked().trim().parse::<usize>() {
+            Ok(count) if (1..=MAX_ITERATIONS).contains(&count) => count,
+            _ => {
+                error.set(format!(
+                    "Number of iterations must be between 1 and {MAX_ITERATIONS}"
+                ));
+                return;
+            }
+        };
+        let fields = match description.with_untracked(|text| parse_witness_fields(text)) {
+            Ok(fields) => fields,
+            Err(parse_error) => {
+                error.set(parse_error);
+                return;
+            }
+        };
+        let text = program.text.get_untracked();
+        let compiled = match compile_program(&text) {
+            Ok(compiled) => compiled,
+            Err(compile_error) => {
+                error.set(compile_error);
+                return;
+            }
+        };
+        let Some(seed) = random_bytes::<32>() else {
+            error.set("Browser failed to generate a random seed".to_string());
+            return;
+        };
+        let program_text = strip_witness_module(&text);
+        let mut rng = rand::rngs::StdRng::from_seed(seed);
+
+        total.set(count);
+        running.set(true);
+        spawn_local(async move {
+            for batch_start in (0..count).step_by(BATCH_SIZE) {
+                for _ in batch_start..count.min(batch_start + BATCH_SIZE) {
+                    let case = FuzzCase::random(&fields, &mut rng);
+                    let result = tx_env.lazy_env.with_untracked(|env| {
+                        run_with_witness(&compiled, &program_text, &case.witness_text, env)
+                    });
+                    match result {
+                        Ok(..) => passed.update(|n| *n += 1),
+                        Err(run_error) => {
+                            failed.update(|n| *n += 1);
+                            if first_failure.with_untracked(Option::is_none) {
+                                first_failure.set(Some(Failure {
+                                    case,
+                                    error: run_error,
+                                }));
+                            }
+                        }
+                    }
+                }
+                // Let the browser render the progress bar
+                gloo_timers::future::TimeoutFuture::new(0).await;
+            }
+            running.set(false);
+        });
+    };
+
+    let progress = move || passed.get() + failed.get();
+    let summary = Signal::derive(move || match total.get() {
+        0 => String::new(),
+        _ => format!(
+            "{} of {} iterations: {} passed, {} failed",
+            progress(),
+            total.get(),
+            passed.get(),
+            failed.get()
+        ),
+    });
+    let failure_text = Signal::derive(move || {
+        first_failure.with(|failure| match failure {
+            Some(failure) => format!(
+                "First failing witness (0x{}):\n{}\n\n{}",
+                failure.case.to_hex(),
+                failure.case.witness_text,
+                failure.error
+            ),
+            None => String::new(),
+        })
+    });
+
+    view! {
+        <div class="tab-content fuzzer-tab">
+            <div class="tab-title-group">
+                <h3 class="tab-title">"Fuzzer"</h3>
+            </div>
+            <p class="tab-description">
+                "Run the program with random witnesses. Declare one witness per line as NAME: TYPE, for example X: u32. The witness module of the program is replaced by the random values."
+            </p>
+            <textarea
+                class="input fuzzer-witness-input"
+                rows="4"
+                placeholder="X: u32"
+                spellcheck="false"
+                prop:value=description
+                on:input=update_description
+            />
+            <div class="key-store-display-row">
+                <div class="display-row-label">"Iterations"</div>
+                <input
+                    class="input"
+                    type="text"
+                    prop:value=iterations
+                    on:input=update_iterations
+                />
+                <button class="flat-button bordered" disabled=running on:click=fuzz>
+                    "Fuzz"
+                </button>
+            </div>
+            <Show when=move || total.get() != 0>
+                <progress class="fuzzer-progress" max=total value=progress />
+            </Show>
+            <NeutralBox neutral=summary />
+            <ErrorBox error=failure_text />
+            <ErrorBox error=error />
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_fields() {
+        let fields = parse_witness_fields("X: u8\n\n  FLAG : bool;\n").unwrap();
+        assert_eq!(
+            vec![
+                WitnessField {
+                    name: "X".to_string(),
+                    ty: "u8",
+                    bit_width: 8
+                },
+                WitnessField {
+                    name: "FLAG".to_string(),
+                    ty: "bool",
+                    bit_width: 1
+                },
+            ],
+            fields
+        );
+        assert!(parse_witness_fields("X u8").is_err());
+        assert!(parse_witness_fields("X: u3").is_err());
+        assert!(parse_witness_fields("X-1: u8").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn case_from_bits() {
+        let fields = parse_witness_fields("A: u8\nB: bool\nC: u2").unwrap();
+        let bits = [
+            false, false, false, true, true, true, true, true, true, true, false,
+        ];
+        let case = FuzzCase::from_bits(&fields, bits.to_vec());
+        assert_eq!(
+            "mod witness {\n    const A: u8 = 0x1f;\n    const B: bool = true;\n    const C: u2 = 2;\n}",
+            case.witness_text
+        );
+        assert_eq!("1fc0", case.to_hex());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn fuzz_program() {
+        let text = "fn main() {\n    let x: u8 = witness::X;\n    assert!(jet::le_8(x, 127));\n}";
+        let compiled = CompiledProgram::new(text, simfony::Arguments::default()).unwrap();
+        let fields = parse_witness_fields("X: u8").unwrap();
+        let env = crate::transaction::TxParams::default().tx_env(compiled.commit().cmr());
+
+        let small = FuzzCase::from_bits(&fields, vec![false; 8]);
+        assert!(run_with_witness(&compiled, text, &small.witness_text, &env).is_ok());
+        let large = FuzzCase::from_bits(&fields, vec![true; 8]);
+        assert!(run_with_witness(&compiled, text, &large.witness_text, &env).is_err());
+    }
+}
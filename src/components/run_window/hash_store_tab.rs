@@ -1,12 +1,17 @@
-use hashes::Hash;
-use hex_conservative::DisplayHex;
+use std::collections::HashMap;
+
+use hashes::{hash160, hmac, ripemd160, sha256, sha256d, Hash, HashEngine};
+use hex_conservative::{DisplayHex, FromHex};
 use leptos::{
-    component, create_rw_signal, use_context, view, For, IntoView, RwSignal, SignalGet,
-    SignalUpdate, View,
+    component, create_rw_signal, ev, event_target_value, store_value, use_context, view,
+    CollectView, For, IntoView, RwSignal, Show, SignalGet, SignalGetUntracked, SignalSet,
+    SignalUpdate, SignalWith, View,
 };
 use simfony::elements::hashes;
 
 use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::string_box::ErrorBox;
+use crate::components::toast::{ToastLevel, Toasts};
 use crate::util::{Counter26, HashedData};
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -18,12 +23,141 @@ impl HashCount {
     }
 }
 
+/// Hash function that can be computed in the browser.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Ripemd160,
+    /// SHA-256 followed by RIPEMD-160.
+    Hash160,
+    /// SHA-256 applied twice.
+    Sha256d,
+}
+
+impl HashAlgorithm {
+    pub const ALL: [Self; 4] = [Self::Sha256, Self::Ripemd160, Self::Hash160, Self::Sha256d];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sha256 => "SHA-256",
+            Self::Ripemd160 => "RIPEMD-160",
+            Self::Hash160 => "Hash160",
+            Self::Sha256d => "SHA256d",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|algorithm| algorithm.name() == name)
+    }
+
+    pub fn digest(self, preimage: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => sha256::Hash::hash(preimage).to_byte_array().to_vec(),
+            Self::Ripemd160 => ripemd160::Hash::hash(preimage).to_byte_array().to_vec(),
+            Self::Hash160 => hash160::Hash::hash(preimage).to_byte_array().to_vec(),
+            Self::Sha256d => sha256d::Hash::hash(preimage).to_byte_array().to_vec(),
+        }
+    }
+}
+
+/// Computes BIP-340 tagged hashes.
+///
+/// The hash of each tag is cached for repeated use of the same tag.
+#[derive(Clone, Debug, Default)]
+pub struct TaggedHasher {
+    tag_hashes: HashMap<String, sha256::Hash>,
+}
+
+impl TaggedHasher {
+    /// Compute `SHA256(SHA256(tag) || SHA256(tag) || message)`.
+    pub fn hash(&mut self, tag: &str, message: &[u8]) -> sha256::Hash {
+        let tag_hash = *self
+            .tag_hashes
+            .entry(tag.to_string())
+            .or_insert_with(|| sha256::Hash::hash(tag.as_bytes()));
+        let mut engine = sha256::Hash::engine();
+        engine.input(&tag_hash.to_byte_array());
+        engine.input(&tag_hash.to_byte_array());
+        engine.input(message);
+        sha256::Hash::from_engine(engine)
+    }
+}
+
+/// Compute the HMAC-SHA256 of a message under a key.
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut engine = hmac::HmacEngine::<sha256::Hash>::new(key);
+    engine.input(message);
+    hmac::Hmac::<sha256::Hash>::from_engine(engine)
+        .to_byte_array()
+        .to_vec()
+}
+
+/// Shorten the text to at most `max_len` characters, marking the cut with an ellipsis.
+fn truncate_label(text: &str, max_len: usize) -> String {
+    match text.chars().count() <= max_len {
+        true => text.to_string(),
+        false => {
+            let prefix: String = text.chars().take(max_len.saturating_sub(1)).collect();
+            format!("{prefix}…")
+        }
+    }
+}
+
+/// Hash that was computed in the browser.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComputedHash {
+    /// Name of the hash function that was applied.
+    pub function: String,
+    /// Preimage as entered by the user.
+    pub input: String,
+    pub digest: Vec<u8>,
+}
+
+impl ComputedHash {
+    /// Return the name of the hash at the given position in the list of computed hashes.
+    pub fn name(index: usize) -> String {
+        format!("H{}", index + 1)
+    }
+
+    /// Hash the digest of the hash at the given position in the list of computed hashes.
+    pub fn chain(&self, index: usize, algorithm: HashAlgorithm) -> Self {
+        Self {
+            function: algorithm.name().to_string(),
+            input: Self::name(index),
+            digest: algorithm.digest(&self.digest),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ComputedHashes(pub RwSignal<Vec<ComputedHash>>);
+
+/// Parse the preimage of a hash.
+///
+/// Input with a `0x` prefix is parsed as hex bytes.
+/// Any other input is taken as ASCII text.
+pub fn parse_preimage(input: &str) -> Result<Vec<u8>, String> {
+    match input.strip_prefix("0x") {
+        Some(hex) => Vec::<u8>::from_hex(hex).map_err(|error| error.to_string()),
+        None if input.is_ascii() => Ok(input.as_bytes().to_vec()),
+        None => Err("Expected ASCII text or hex bytes with 0x prefix".to_string()),
+    }
+}
+
 #[component]
 pub fn HashStoreTab() -> impl IntoView {
     view! {
         <div class="tab-content hash-store-tab">
             <CopyHashesToClipboard />
             <CopyPreimagesToClipboard />
+            <ComputeHash />
+            <ComputeTaggedHash />
+            <ComputeHmac />
+            <ComputedHashList />
+            <ChainHash />
         </div>
     }
 }
@@ -139,3 +273,447 @@ fn CopyPreimagesToClipboard() -> impl IntoView {
         </div>
     }
 }
+
+#[component]
+fn ComputeHash() -> impl IntoView {
+    let computed_hashes =
+        use_context::<ComputedHashes>().expect("computed hashes should exist in context");
+    let algorithm = create_rw_signal(HashAlgorithm::default());
+    let preimage = create_rw_signal(String::new());
+    let digest_hex = create_rw_signal(String::new());
+    let error_output = create_rw_signal(String::new());
+
+    let update_preimage = move |event: ev::Event| preimage.set(event_target_value(&event));
+    let select_algorithm = move |event: ev::Event| {
+        if let Some(selected) = HashAlgorithm::from_name(&event_target_value(&event)) {
+            algorithm.set(selected);
+        }
+    };
+    let compute_hash = move |_event: ev::MouseEvent| {
+        let input = preimage.get_untracked();
+        match parse_preimage(input.trim()) {
+            Ok(bytes) => {
+                let algorithm = algorithm.get_untracked();
+                let digest = algorithm.digest(&bytes);
+                digest_hex.set(format!("0x{}", digest.as_hex()));
+                error_output.update(String::clear);
+                computed_hashes.0.update(|hashes| {
+                    hashes.push(ComputedHash {
+                        function: algorithm.name().to_string(),
+                        input,
+                        digest,
+                    })
+                });
+            }
+            Err(error) => {
+                digest_hex.update(String::clear);
+                error_output.set(error);
+            }
+        }
+    };
+    let algorithm_options = HashAlgorithm::ALL
+        .into_iter()
+        .map(|algorithm| view! { <option value=algorithm.name()>{algorithm.name()}</option> })
+        .collect_view();
+
+    view! {
+        <div>
+            <div class="tab-title-group">
+                <h3 class="tab-title">
+                    Compute Hash
+                </h3>
+            </div>
+            <p class="tab-description">
+                "Compute the hash of hex bytes with 0x prefix or of ASCII text."
+            </p>
+            <textarea
+                class="input preimage-input"
+                rows="3"
+                placeholder="0x... or text"
+                spellcheck="false"
+                on:input=update_preimage
+            />
+            <div class="compute-hash-row">
+                <select class="input hash-algorithm-select" on:change=select_algorithm>
+                    {algorithm_options}
+                </select>
+                <button class="flat-button bordered" type="button" on:click=compute_hash>
+                    Hash
+                </button>
+                <DigestOutput digest_hex=digest_hex />
+            </div>
+            <ErrorBox error=error_output />
+        </div>
+    }
+}
+
+#[component]
+fn ComputeTaggedHash() -> impl IntoView {
+    let computed_hashes =
+        use_context::<ComputedHashes>().expect("computed hashes should exist in context");
+    let hasher = store_value(TaggedHasher::default());
+    let tag = create_rw_signal("TapLeaf".to_string());
+    let message = create_rw_signal(String::new());
+    let digest_hex = create_rw_signal(String::new());
+    let error_output = create_rw_signal(String::new());
+
+    let update_tag = move |event: ev::Event| tag.set(event_target_value(&event));
+    let update_message = move |event: ev::Event| message.set(event_target_value(&event));
+    let compute_hash = move |_event: ev::MouseEvent| {
+        let tag = tag.get_untracked();
+        let input = message.get_untracked();
+        match parse_preimage(input.trim()) {
+            Ok(bytes) => {
+                let digest = hasher
+                    .try_update_value(|hasher| hasher.hash(&tag, &bytes))
+                    .expect("tagged hasher should exist")
+                    .to_byte_array()
+                    .to_vec();
+                digest_hex.set(format!("0x{}", digest.as_hex()));
+                error_output.update(String::clear);
+                computed_hashes.0.update(|hashes| {
+                    hashes.push(ComputedHash {
+                        function: format!("Tagged({tag})"),
+                        input,
+                        digest,
+                    })
+                });
+            }
+            Err(error) => {
+                digest_hex.update(String::clear);
+                error_output.set(error);
+            }
+        }
+    };
+
+    view! {
+        <div>
+            <div class="tab-title-group">
+                <h3 class="tab-title">
+                    Tagged Hash
+                </h3>
+            </div>
+            <p class="tab-description">
+                "Compute the BIP-340 tagged hash SHA256(SHA256(tag) || SHA256(tag) || message)."
+            </p>
+            <div class="compute-hash-row">
+                <input
+                    class="input tag-input"
+                    type="text"
+                    placeholder="Tag"
+                    prop:value=tag
+                    on:input=update_tag
+                />
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="Message: 0x... or text"
+                    spellcheck="false"
+                    on:input=update_message
+                />
+            </div>
+            <div class="compute-hash-row">
+                <button class="flat-button bordered" type="button" on:click=compute_hash>
+                    Hash
+                </button>
+                <DigestOutput digest_hex=digest_hex />
+            </div>
+            <ErrorBox error=error_output />
+        </div>
+    }
+}
+
+#[component]
+fn ComputeHmac() -> impl IntoView {
+    let computed_hashes =
+        use_context::<ComputedHashes>().expect("computed hashes should exist in context");
+    let key = create_rw_signal(String::new());
+    let message = create_rw_signal(String::new());
+    let digest_hex = create_rw_signal(String::new());
+    let error_output = create_rw_signal(String::new());
+
+    let update_key = move |event: ev::Event| key.set(event_target_value(&event));
+    let update_message = move |event: ev::Event| message.set(event_target_value(&event));
+    let compute_hmac = move |_event: ev::MouseEvent| {
+        let key = key.get_untracked();
+        let message = message.get_untracked();
+        let key_bytes = parse_preimage(key.trim()).map_err(|error| format!("Key: {error}"));
+        let message_bytes =
+            parse_preimage(message.trim()).map_err(|error| format!("Message: {error}"));
+        match key_bytes.and_then(|key_bytes| Ok((key_bytes, message_bytes?))) {
+            Ok((key_bytes, message_bytes)) => {
+                let digest = hmac_sha256(&key_bytes, &message_bytes);
+                digest_hex.set(format!("0x{}", digest.as_hex()));
+                error_output.update(String::clear);
+                computed_hashes.0.update(|hashes| {
+                    hashes.push(ComputedHash {
+                        function: "HMAC-SHA256".to_string(),
+                        input: format!(
+                            "key: {}, msg: {}",
+                            truncate_label(&key, MAX_LABEL_LEN),
+                            truncate_label(&message, MAX_LABEL_LEN)
+                        ),
+                        digest,
+                    })
+                });
+            }
+            Err(error) => {
+                digest_hex.update(String::clear);
+                error_output.set(error);
+            }
+        }
+    };
+
+    view! {
+        <div>
+            <div class="tab-title-group">
+                <h3 class="tab-title">
+                    HMAC-SHA256
+                </h3>
+            </div>
+            <div class="compute-hash-row">
+                <input
+                    class="input tag-input"
+                    type="text"
+                    placeholder="Key: 0x... or text"
+                    spellcheck="false"
+                    on:input=update_key
+                />
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="Message: 0x... or text"
+                    spellcheck="false"
+                    on:input=update_message
+                />
+            </div>
+            <div class="compute-hash-row">
+                <button class="flat-button bordered" type="button" on:click=compute_hmac>
+                    HMAC
+                </button>
+                <DigestOutput digest_hex=digest_hex />
+            </div>
+            <ErrorBox error=error_output />
+        </div>
+    }
+}
+
+/// Maximum length of the key and message in the label of a computed HMAC.
+const MAX_LABEL_LEN: usize = 16;
+
+#[component]
+fn DigestOutput(digest_hex: RwSignal<String>) -> impl IntoView {
+    view! {
+        <input class="input" type="text" readonly=true prop:value=digest_hex />
+        <Show when=move || digest_hex.with(|hex| !hex.is_empty())>
+            <CopyToClipboard content=digest_hex class="copy-button">
+                <i class="far fa-copy"></i>
+            </CopyToClipboard>
+        </Show>
+    }
+}
+
+#[component]
+fn ComputedHashList() -> impl IntoView {
+    let computed_hashes =
+        use_context::<ComputedHashes>().expect("computed hashes should exist in context");
+    let computed_rows = move || -> View {
+        computed_hashes.0.with(|hashes| {
+            hashes
+                .iter()
+                .enumerate()
+                .map(|(index, computed)| {
+                    let hex = format!("0x{}", computed.digest.as_hex());
+                    let content = hex.clone();
+                    view! {
+                        <div class="computed-hash-row">
+                            <span class="computed-hash-algorithm">
+                                {format!("{} = {}", ComputedHash::name(index), computed.function)}
+                            </span>
+                            <span class="computed-hash-input">
+                                {format!("({})", computed.input)}
+                            </span>
+                            <CopyToClipboard content=move || content.clone() class="copy-button">
+                                {hex}
+                                <i class="far fa-copy"></i>
+                            </CopyToClipboard>
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <Show when=move || computed_hashes.0.with(|hashes| !hashes.is_empty())>
+            <div class="tab-title-group">
+                <h3 class="tab-title">
+                    Computed Hashes
+                </h3>
+            </div>
+            {computed_rows}
+        </Show>
+    }
+}
+
+#[component]
+fn ChainHash() -> impl IntoView {
+    let computed_hashes =
+        use_context::<ComputedHashes>().expect("computed hashes should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+    let selected_index = create_rw_signal(None::<usize>);
+    let algorithm = create_rw_signal(HashAlgorithm::default());
+
+    let select_index = move |event: ev::Event| {
+        selected_index.set(event_target_value(&event).parse().ok());
+    };
+    let select_algorithm = move |event: ev::Event| {
+        if let Some(selected) = HashAlgorithm::from_name(&event_target_value(&event)) {
+            algorithm.set(selected);
+        }
+    };
+    let chain_hash = move |_event: ev::MouseEvent| {
+        let algorithm = algorithm.get_untracked();
+        let chained = computed_hashes.0.try_update(|hashes| {
+            let index = selected_index
+                .get_untracked()
+                .unwrap_or(hashes.len().saturating_sub(1));
+            let chained = hashes.get(index).map(|x| x.chain(index, algorithm));
+            let is_chained = chained.is_some();
+            hashes.extend(chained);
+            is_chained
+        });
+        if chained != Some(true) {
+            toasts.add_toast(
+                ToastLevel::Error,
+                "Compute a hash to chain first".to_string(),
+            );
+        }
+    };
+    let index_options = move || -> View {
+        let len = computed_hashes.0.with(Vec::len);
+        (0..len)
+            .rev()
+            .map(|index| {
+                view! {
+                    <option value=index.to_string() selected=move || selected_index.get() == Some(index)>
+                        {ComputedHash::name(index)}
+                    </option>
+                }
+            })
+            .collect_view()
+    };
+    let algorithm_options = HashAlgorithm::ALL
+        .into_iter()
+        .map(|algorithm| view! { <option value=algorithm.name()>{algorithm.name()}</option> })
+        .collect_view();
+
+    view! {
+        <Show when=move || computed_hashes.0.with(|hashes| !hashes.is_empty())>
+            <p class="tab-description">
+                "Hash an existing hash to build a chain of hashes."
+            </p>
+            <div class="compute-hash-row">
+                <select class="input hash-algorithm-select" on:change=select_algorithm>
+                    {algorithm_options.clone()}
+                </select>
+                "of"
+                <select class="input hash-algorithm-select" on:change=select_index>
+                    {index_options}
+                </select>
+                <button class="flat-button bordered" type="button" on:click=chain_hash>
+                    Chain
+                </button>
+            </div>
+        </Show>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_preimage_hex_or_text() {
+        assert_eq!(Ok(vec![0xde, 0xad]), parse_preimage("0xdead"));
+        assert_eq!(Ok(b"hello".to_vec()), parse_preimage("hello"));
+        assert_eq!(Ok(vec![]), parse_preimage("0x"));
+        assert!(parse_preimage("0xabc").is_err());
+        assert!(parse_preimage("héllo").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn hash160_of_public_key() {
+        let public_key =
+            parse_preimage("0x0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        let digest = HashAlgorithm::Hash160.digest(&public_key);
+        assert_eq!(
+            "751e76e8199196d454941c45d1b3a323f1433bd6",
+            digest.to_lower_hex_string()
+        );
+        let sha256 = HashAlgorithm::Sha256.digest(&public_key);
+        assert_eq!(digest, HashAlgorithm::Ripemd160.digest(&sha256));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn sha256d_of_text() {
+        let digest = HashAlgorithm::Sha256d.digest(b"hello");
+        assert_eq!(
+            "9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50",
+            digest.to_lower_hex_string()
+        );
+        let sha256 = HashAlgorithm::Sha256.digest(b"hello");
+        assert_eq!(digest, HashAlgorithm::Sha256.digest(&sha256));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn tagged_hash_matches_tap_leaf_hash() {
+        use simfony::elements::bitcoin::taproot::{LeafVersion, TapLeafHash};
+        use simfony::elements::bitcoin::ScriptBuf;
+
+        let script = ScriptBuf::from_bytes(vec![0x51]);
+        let leaf_bytes = [0xc0, 0x01, 0x51];
+        let mut hasher = TaggedHasher::default();
+        let digest = hasher.hash("TapLeaf", &leaf_bytes);
+        let expected = TapLeafHash::from_script(&script, LeafVersion::TapScript);
+        assert_eq!(expected.to_byte_array(), digest.to_byte_array());
+        // Cached tag hash gives the same result
+        assert_eq!(digest, hasher.hash("TapLeaf", &leaf_bytes));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn hmac_sha256_rfc4231() {
+        // Test case 2 of RFC 4231
+        let digest = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            digest.to_lower_hex_string()
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn truncate_long_label() {
+        assert_eq!("short", truncate_label("short", 16));
+        assert_eq!("abcd…", truncate_label("abcdefgh", 5));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn chain_sha256_twice() {
+        let first = ComputedHash {
+            function: HashAlgorithm::Sha256.name().to_string(),
+            input: "hello".to_string(),
+            digest: HashAlgorithm::Sha256.digest(b"hello"),
+        };
+        let second = first.chain(0, HashAlgorithm::Sha256);
+        assert_eq!("H1", second.input);
+        assert_eq!(HashAlgorithm::Sha256d.digest(b"hello"), second.digest);
+    }
+}
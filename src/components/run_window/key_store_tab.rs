@@ -1,16 +1,31 @@
+use std::str::FromStr;
 use std::sync::Arc;
 
+use bip39::Mnemonic;
+use elements::bitcoin::bip32::{DerivationPath, Xpriv};
+use elements::bitcoin::key::{TapTweak, TweakedPublicKey, UntweakedPublicKey};
+use elements::bitcoin::taproot::{ControlBlock, LeafVersion, TapNodeHash, TaprootMerkleBranch};
+use elements::bitcoin::{Address, Network, NetworkKind, PrivateKey};
 use elements::hashes::{sha256, Hash};
 use elements::secp256k1_zkp as secp256k1;
 use hex_conservative::{DisplayHex, FromHex};
 use leptos::{
-    component, create_memo, create_rw_signal, ev, event_target_value, html, use_context, view, For,
-    IntoView, NodeRef, RwSignal, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
-    SignalWith, View,
+    component, create_memo, create_rw_signal, ev, event_target, event_target_checked,
+    event_target_value, html, spawn_local, use_context, view, with, CollectView, For, IntoView,
+    NodeRef, RwSignal, Show, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
+    SignalWith, SignalWithUntracked, View,
 };
+use simfony::num::U256;
 use simfony::{elements, simplicity};
+use wasm_bindgen_futures::JsFuture;
 
+use super::hash_store_tab::TaggedHasher;
+use crate::components::copy_button::CopyButton;
 use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::program_window::download_text;
+use crate::components::state::{update_local_storage, LocalStorage};
+use crate::components::string_box::{ErrorBox, SuccessBox};
+use crate::key_backup;
 use crate::util::{Counter26, SigningKeys};
 
 #[derive(Copy, Clone, Debug, Default)]
@@ -22,6 +37,77 @@ impl KeyCount {
     }
 }
 
+/// Key pair with a user-given name.
+#[derive(Clone, Debug)]
+pub struct NamedKey {
+    pub name: String,
+    pub keypair: secp256k1::Keypair,
+}
+
+/// List of named keys, one of which may be selected for signing.
+#[derive(Clone, Debug, Default)]
+pub struct KeySlots {
+    keys: Vec<NamedKey>,
+    selected: Option<usize>,
+}
+
+impl KeySlots {
+    pub fn keys(&self) -> &[NamedKey] {
+        &self.keys
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Add a key to the end of the list.
+    pub fn add(&mut self, name: String, keypair: secp256k1::Keypair) {
+        self.keys.push(NamedKey { name, keypair });
+    }
+
+    /// Remove the key at the given index.
+    ///
+    /// The selection moves along with the selected key.
+    /// If the selected key is removed, then no key is selected.
+    pub fn remove(&mut self, index: usize) {
+        if self.keys.len() <= index {
+            return;
+        }
+        self.keys.remove(index);
+        self.selected = match self.selected {
+            Some(selected) if selected == index => None,
+            Some(selected) if selected > index => Some(selected - 1),
+            selected => selected,
+        };
+    }
+
+    /// Select the key at the given index for signing.
+    pub fn select(&mut self, index: usize) {
+        if index < self.keys.len() {
+            self.selected = Some(index);
+        }
+    }
+
+    pub fn selected_index(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn selected(&self) -> Option<&NamedKey> {
+        self.selected.and_then(|index| self.keys.get(index))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NamedKeys(pub RwSignal<KeySlots>);
+
+/// BIP-32 root key that was imported from a mnemonic, if any.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct RootKey(pub RwSignal<Option<Xpriv>>);
+
 #[derive(Copy, Clone, Debug)]
 pub enum SignedDataMode {
     SighashAll,
@@ -36,6 +122,8 @@ pub struct SignedData {
     pub sighash_all: Signal<secp256k1::Message>,
     pub hash_preimage_bytes: RwSignal<Vec<u8>>,
     pub message: Signal<secp256k1::Message>,
+    /// Auxiliary randomness for signing, if the user supplied a custom nonce.
+    pub aux_rand: RwSignal<Option<[u8; 32]>>,
 }
 
 impl SignedData {
@@ -65,6 +153,21 @@ impl SignedData {
             sighash_all,
             hash_preimage_bytes,
             message,
+            aux_rand: create_rw_signal(None),
+        }
+    }
+
+    /// Sign the message with the given key pair.
+    ///
+    /// Use the custom auxiliary randomness if there is any,
+    /// otherwise use system randomness.
+    pub fn sign(self, keypair: &secp256k1::Keypair) -> secp256k1::schnorr::Signature {
+        let message = self.message.get();
+        match self.aux_rand.get() {
+            Some(aux_rand) => {
+                secp256k1::SECP256K1.sign_schnorr_with_aux_rand(&message, keypair, &aux_rand)
+            }
+            None => keypair.sign_schnorr(message),
         }
     }
 }
@@ -101,13 +204,1167 @@ fn key_name(index: usize) -> &'static str {
     }
 }
 
+/// Generate random bytes using the browser's cryptographically secure random number generator.
+pub fn random_bytes<const N: usize>() -> Option<[u8; N]> {
+    let crypto = web_sys::window()?.crypto().ok()?;
+    let mut bytes = [0; N];
+    crypto.get_random_values_with_u8_array(&mut bytes).ok()?;
+    Some(bytes)
+}
+
+fn random_seed() -> Option<U256> {
+    random_bytes::<32>().map(U256::from_byte_array)
+}
+
 #[component]
 pub fn KeyStoreTab() -> impl IntoView {
     view! {
         <div class="tab-content key-store-tab">
+            <GenerateKeys />
+            <KeyDetails />
+            <TaprootPanel />
+            <ControlBlockBuilder />
+            <MnemonicKey />
+            <NamedKeySlots />
+            <BackupKeys />
             <CopyPublicKeysToClipboard />
             <CopySignaturesToClipboard />
             <SelectSignedData />
+            <VerifySignature />
+        </div>
+    }
+}
+
+/// Replace the master key and reload the page.
+///
+/// Keys are loaded from local storage when the page is loaded.
+fn replace_master_key(random_seed: U256) {
+    update_local_storage();
+    SigningKeys::new(random_seed).store_in_storage();
+    if let Some(window) = web_sys::window() {
+        let _result = window.location().reload();
+    }
+}
+
+/// Encode the secret key of the key pair in Wallet Import Format (WIF).
+///
+/// The key is marked as compressed.
+pub fn key_to_wif(keypair: &secp256k1::Keypair, network: NetworkKind) -> String {
+    PrivateKey::from_slice(&keypair.secret_bytes(), network)
+        .expect("secret key of key pair should be valid")
+        .to_wif()
+}
+
+/// Decode a key pair from Wallet Import Format (WIF).
+///
+/// Both mainnet and testnet keys are accepted, compressed or not.
+pub fn key_from_wif(wif: &str) -> Result<secp256k1::Keypair, String> {
+    let key = PrivateKey::from_wif(wif).map_err(|error| error.to_string())?;
+    secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &key.inner.secret_bytes())
+        .map_err(|error| error.to_string())
+}
+
+/// Tag of the hash that tweaks taproot keys on Elements.
+const ELEMENTS_TAP_TWEAK_TAG: &str = "TapTweak/elements";
+
+/// Tweak the internal key with the optional merkle root of a tap tree, as in BIP-341.
+///
+/// The tweak is hashed with the given tag.
+pub fn tap_tweak(
+    tag: &str,
+    internal_key: secp256k1::XOnlyPublicKey,
+    merkle_root: Option<[u8; 32]>,
+) -> Result<secp256k1::XOnlyPublicKey, String> {
+    let mut message = internal_key.serialize().to_vec();
+    if let Some(merkle_root) = merkle_root {
+        message.extend_from_slice(&merkle_root);
+    }
+    let tweak = TaggedHasher::default().hash(tag, &message);
+    let tweak = secp256k1::Scalar::from_be_bytes(tweak.to_byte_array())
+        .map_err(|error| error.to_string())?;
+    internal_key
+        .add_tweak(secp256k1::SECP256K1, &tweak)
+        .map(|(output_key, _parity)| output_key)
+        .map_err(|error| error.to_string())
+}
+
+/// Compute the Bitcoin taproot output key and its mainnet `bc1p...` address
+/// from the given internal key and optional tap tree merkle root.
+pub fn taproot_output(
+    internal_key: secp256k1::XOnlyPublicKey,
+    merkle_root: Option<[u8; 32]>,
+) -> Result<(TweakedPublicKey, Address), String> {
+    let secp = elements::bitcoin::secp256k1::Secp256k1::verification_only();
+    let internal_key = UntweakedPublicKey::from_slice(&internal_key.serialize())
+        .map_err(|error| error.to_string())?;
+    let merkle_root = merkle_root.map(TapNodeHash::from_byte_array);
+    let (output_key, _parity) = internal_key.tap_tweak(&secp, merkle_root);
+    let address = Address::p2tr_tweaked(output_key, Network::Bitcoin);
+    Ok((output_key, address))
+}
+
+/// Build the control block that spends the given tap leaf via the script path.
+///
+/// The merkle path lists the sibling hashes from the leaf up to the root.
+pub fn control_block(
+    internal_key: secp256k1::XOnlyPublicKey,
+    leaf_version: u8,
+    leaf_hash: [u8; 32],
+    merkle_path: &[[u8; 32]],
+) -> Result<ControlBlock, String> {
+    let leaf_version =
+        LeafVersion::from_consensus(leaf_version).map_err(|error| error.to_string())?;
+    let merkle_path: Vec<TapNodeHash> = merkle_path
+        .iter()
+        .copied()
+        .map(TapNodeHash::from_byte_array)
+        .collect();
+    let merkle_root = merkle_path
+        .iter()
+        .fold(TapNodeHash::from_byte_array(leaf_hash), |node, sibling| {
+            TapNodeHash::from_node_hashes(node, *sibling)
+        });
+    let secp = elements::bitcoin::secp256k1::Secp256k1::verification_only();
+    let internal_key = UntweakedPublicKey::from_slice(&internal_key.serialize())
+        .map_err(|error| error.to_string())?;
+    let (_output_key, output_key_parity) = internal_key.tap_tweak(&secp, Some(merkle_root));
+    let merkle_branch =
+        TaprootMerkleBranch::try_from(merkle_path).map_err(|error| error.to_string())?;
+    Ok(ControlBlock {
+        leaf_version,
+        output_key_parity,
+        internal_key,
+        merkle_branch,
+    })
+}
+
+/// Derive a child key from the root key along the given BIP-32 derivation path.
+pub fn derive_key(root_key: &Xpriv, path: &str) -> Result<secp256k1::Keypair, String> {
+    let path = DerivationPath::from_str(path.trim()).map_err(|error| error.to_string())?;
+    let secp = elements::bitcoin::secp256k1::Secp256k1::new();
+    let child = root_key
+        .derive_priv(&secp, &path)
+        .map_err(|error| error.to_string())?;
+    secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &child.private_key.secret_bytes())
+        .map_err(|error| error.to_string())
+}
+
+/// Derive the BIP-32 root key from a BIP-39 mnemonic and an optional passphrase.
+///
+/// Fail if the words or the checksum of the mnemonic are invalid.
+pub fn mnemonic_root_key(
+    phrase: &str,
+    passphrase: &str,
+    network: NetworkKind,
+) -> Result<Xpriv, String> {
+    let mnemonic = Mnemonic::parse_normalized(phrase.trim()).map_err(|error| error.to_string())?;
+    let seed = mnemonic.to_seed_normalized(passphrase);
+    Xpriv::new_master(network, &seed).map_err(|error| error.to_string())
+}
+
+#[component]
+fn GenerateKeys() -> impl IntoView {
+    let generate_keys = move |_event: ev::MouseEvent| {
+        if let Some(random_seed) = random_seed() {
+            replace_master_key(random_seed);
+        }
+    };
+
+    view! {
+        <div class="tab-title-group">
+            <h3 class="tab-title">
+                Master Key
+            </h3>
+            <div class="button-row is-small">
+                <button class="flat-button bordered" type="button" on:click=generate_keys>
+                    <i class="fas fa-dice"></i>
+                    " Generate"
+                </button>
+            </div>
+        </div>
+        <p class="tab-description">
+            "Generating a new master key replaces all keys below and reloads the page."
+        </p>
+    }
+}
+
+#[component]
+fn KeyDetails() -> impl IntoView {
+    let signing_keys = use_context::<SigningKeys>().expect("signing keys should exist in context");
+    let signed_data = use_context::<SignedData>().expect("signed data should exist in context");
+    let key_count = use_context::<KeyCount>().expect("key count should exist in context");
+    let selected_index = create_rw_signal(0usize);
+    let reveal_secret = create_rw_signal(false);
+
+    let keys = signing_keys.clone();
+    let secret_hex = move || {
+        let index = selected_index.get();
+        format!("0x{}", keys.secret_keys[index].secret_bytes().as_hex())
+    };
+    let keys = signing_keys.clone();
+    let compressed_hex = move || {
+        let index = selected_index.get();
+        format!(
+            "0x{}",
+            keys.secret_keys[index].public_key().serialize().as_hex()
+        )
+    };
+    let keys = signing_keys.clone();
+    let signature = Signal::derive(move || {
+        let index = selected_index.get();
+        signed_data.sign(&keys.secret_keys[index])
+    });
+    let keys = signing_keys;
+    let xonly_hex = move || {
+        let index = selected_index.get();
+        format!("0x{}", keys.public_keys[index].serialize().as_hex())
+    };
+    let secret_display = {
+        let secret_hex = secret_hex.clone();
+        move || match reveal_secret.get() {
+            true => secret_hex(),
+            false => "•".repeat(66),
+        }
+    };
+
+    let select_key = move |event: ev::Event| {
+        if let Ok(index) = event_target_value(&event).parse::<usize>() {
+            selected_index.set(index);
+        }
+    };
+    let toggle_reveal = move |event: ev::Event| reveal_secret.set(event_target_checked(&event));
+    let key_options = move || {
+        (0..key_count.0.get().get())
+            .map(|index| view! { <option value=index.to_string()>{key_name(index)}</option> })
+            .collect_view()
+    };
+
+    view! {
+        <div class="key-details">
+            <div class="key-store-display-row">
+                <div class="display-row-label">Key</div>
+                <select class="input key-select" on:change=select_key>
+                    {key_options}
+                </select>
+            </div>
+            <div class="key-store-display-row">
+                <div class="display-row-label">Secret key</div>
+                <input class="input" type="text" readonly=true prop:value=secret_display />
+                <label class="key-reveal">
+                    <input type="checkbox" on:change=toggle_reveal />
+                    " Reveal"
+                </label>
+                <CopyToClipboard content=secret_hex class="copy-button">
+                    <i class="far fa-copy"></i>
+                </CopyToClipboard>
+            </div>
+            <div class="key-store-display-row">
+                <div class="display-row-label">Public key</div>
+                <input class="input" type="text" readonly=true prop:value=compressed_hex.clone() />
+                <CopyToClipboard content=compressed_hex class="copy-button">
+                    <i class="far fa-copy"></i>
+                </CopyToClipboard>
+            </div>
+            <div class="key-store-display-row">
+                <div class="display-row-label">X-only key</div>
+                <input class="input" type="text" readonly=true prop:value=xonly_hex.clone() />
+                <CopyToClipboard content=xonly_hex class="copy-button">
+                    <i class="far fa-copy"></i>
+                </CopyToClipboard>
+            </div>
+            <SchnorrSigDisplay signature=signature />
+        </div>
+    }
+}
+
+#[component]
+fn TaprootPanel() -> impl IntoView {
+    let signing_keys = use_context::<SigningKeys>().expect("signing keys should exist in context");
+    let key_count = use_context::<KeyCount>().expect("key count should exist in context");
+    let selected_index = create_rw_signal(0usize);
+    let merkle_root_text = create_rw_signal(String::new());
+    let output_key = create_rw_signal(String::new());
+    let address = create_rw_signal(String::new());
+    let elements_output_key = create_rw_signal(String::new());
+    let taproot_error = create_rw_signal(String::new());
+
+    let select_key = move |event: ev::Event| {
+        if let Ok(index) = event_target_value(&event).parse::<usize>() {
+            selected_index.set(index);
+        }
+    };
+    let update_merkle_root =
+        move |event: ev::Event| merkle_root_text.set(event_target_value(&event));
+    let key_options = move || {
+        (0..key_count.0.get().get())
+            .map(|index| view! { <option value=index.to_string()>{key_name(index)}</option> })
+            .collect_view()
+    };
+    let derive_output_key = move |_event: ev::MouseEvent| {
+        let internal_key = signing_keys.public_keys[selected_index.get_untracked()];
+        let merkle_root = merkle_root_text.with_untracked(|text| {
+            let text = text.trim().trim_start_matches("0x");
+            match text.is_empty() {
+                true => Ok(None),
+                false => <[u8; 32]>::from_hex(text)
+                    .map(Some)
+                    .map_err(|_| "Expected exactly 64 hex digits".to_string()),
+            }
+        });
+        let derived = merkle_root.and_then(|merkle_root| {
+            let (bitcoin_key, bitcoin_address) = taproot_output(internal_key, merkle_root)?;
+            let elements_key = tap_tweak(ELEMENTS_TAP_TWEAK_TAG, internal_key, merkle_root)?;
+            Ok((bitcoin_key, bitcoin_address, elements_key))
+        });
+        match derived {
+            Ok((bitcoin_key, bitcoin_address, elements_key)) => {
+                output_key.set(format!("0x{}", bitcoin_key.serialize().as_hex()));
+                address.set(bitcoin_address.to_string());
+                elements_output_key.set(format!("0x{}", elements_key.serialize().as_hex()));
+                taproot_error.update(String::clear);
+            }
+            Err(error) => {
+                output_key.update(String::clear);
+                address.update(String::clear);
+                elements_output_key.update(String::clear);
+                taproot_error.set(error);
+            }
+        }
+    };
+
+    view! {
+        <div>
+            <div class="tab-title-group">
+                <h3 class="tab-title">Taproot</h3>
+            </div>
+            <div class="key-details">
+                <div class="key-store-display-row">
+                    <div class="display-row-label">Internal key</div>
+                    <select class="input key-select" on:change=select_key>
+                        {key_options}
+                    </select>
+                </div>
+                <div class="key-store-display-row">
+                    <div class="display-row-label">Merkle root</div>
+                    <input
+                        class="input"
+                        type="text"
+                        placeholder="Tap tree merkle root (empty for key-path only)"
+                        spellcheck="false"
+                        on:input=update_merkle_root
+                    />
+                    <button class="flat-button bordered" on:click=derive_output_key>
+                        Derive output key
+                    </button>
+                </div>
+                <div class="key-store-display-row">
+                    <div class="display-row-label">Output key</div>
+                    <input class="input" type="text" readonly=true prop:value=output_key />
+                    <CopyToClipboard content=output_key class="copy-button">
+                        <i class="far fa-copy"></i>
+                    </CopyToClipboard>
+                </div>
+                <div class="key-store-display-row">
+                    <div class="display-row-label">Address</div>
+                    <input class="input" type="text" readonly=true prop:value=address />
+                    <CopyToClipboard content=address class="copy-button">
+                        <i class="far fa-copy"></i>
+                    </CopyToClipboard>
+                </div>
+                <div class="key-store-display-row">
+                    <div class="display-row-label">Elements</div>
+                    <input class="input" type="text" readonly=true prop:value=elements_output_key />
+                    <CopyToClipboard content=elements_output_key class="copy-button">
+                        <i class="far fa-copy"></i>
+                    </CopyToClipboard>
+                </div>
+            </div>
+            <ErrorBox error=taproot_error />
+        </div>
+    }
+}
+
+#[component]
+fn ControlBlockBuilder() -> impl IntoView {
+    let signing_keys = use_context::<SigningKeys>().expect("signing keys should exist in context");
+    let key_count = use_context::<KeyCount>().expect("key count should exist in context");
+    let selected_index = create_rw_signal(0usize);
+    let leaf_version = create_rw_signal("0xbe".to_string());
+    let leaf_hash = create_rw_signal(String::new());
+    let proof_nodes = create_rw_signal(Vec::<(usize, RwSignal<String>)>::new());
+    let next_node_id = create_rw_signal(0usize);
+    let control_block_hex = create_rw_signal(String::new());
+    let control_block_error = create_rw_signal(String::new());
+
+    let select_key = move |event: ev::Event| {
+        if let Ok(index) = event_target_value(&event).parse::<usize>() {
+            selected_index.set(index);
+        }
+    };
+    let key_options = move || {
+        (0..key_count.0.get().get())
+            .map(|index| view! { <option value=index.to_string()>{key_name(index)}</option> })
+            .collect_view()
+    };
+    let update_leaf_version = move |event: ev::Event| leaf_version.set(event_target_value(&event));
+    let update_leaf_hash = move |event: ev::Event| leaf_hash.set(event_target_value(&event));
+    let add_node = move |_event: ev::MouseEvent| {
+        let id = next_node_id.get_untracked();
+        next_node_id.set(id + 1);
+        proof_nodes.update(|nodes| nodes.push((id, create_rw_signal(String::new()))));
+    };
+    let proof_node = move |(id, text): (usize, RwSignal<String>)| {
+        let update_node = move |event: ev::Event| text.set(event_target_value(&event));
+        let remove_node = move |_event: ev::MouseEvent| {
+            proof_nodes.update(|nodes| nodes.retain(|(node_id, _)| *node_id != id));
+        };
+        view! {
+            <div class="key-store-display-row">
+                <div class="display-row-label">Sibling</div>
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="32-byte sibling hash"
+                    spellcheck="false"
+                    prop:value=text
+                    on:input=update_node
+                />
+                <button class="flat-button bordered" on:click=remove_node>
+                    Remove
+                </button>
+            </div>
+        }
+    };
+    let build_control_block = move |_event: ev::MouseEvent| {
+        let internal_key = signing_keys.public_keys[selected_index.get_untracked()];
+        let parse_hash = |text: &str| {
+            <[u8; 32]>::from_hex(text.trim().trim_start_matches("0x"))
+                .map_err(|_| format!("Expected exactly 64 hex digits: {}", text.trim()))
+        };
+        let built = leaf_version
+            .with_untracked(|text| {
+                u8::from_str_radix(text.trim().trim_start_matches("0x"), 16)
+                    .map_err(|_| "Expected a leaf version byte in hex".to_string())
+            })
+            .and_then(|version| {
+                let hash = leaf_hash.with_untracked(|text| parse_hash(text))?;
+                let path = proof_nodes.with_untracked(|nodes| {
+                    nodes
+                        .iter()
+                        .map(|(_, text)| text.with_untracked(|text| parse_hash(text)))
+                        .collect::<Result<Vec<_>, String>>()
+                })?;
+                control_block(internal_key, version, hash, &path)
+            });
+        match built {
+            Ok(control_block) => {
+                control_block_hex.set(control_block.serialize().to_lower_hex_string());
+                control_block_error.update(String::clear);
+            }
+            Err(error) => {
+                control_block_hex.update(String::clear);
+                control_block_error.set(error);
+            }
+        }
+    };
+
+    view! {
+        <div>
+            <div class="tab-title-group">
+                <h3 class="tab-title">Control block</h3>
+            </div>
+            <div class="key-details">
+                <div class="key-store-display-row">
+                    <div class="display-row-label">Internal key</div>
+                    <select class="input key-select" on:change=select_key>
+                        {key_options}
+                    </select>
+                </div>
+                <div class="key-store-display-row">
+                    <div class="display-row-label">Leaf version</div>
+                    <input
+                        class="input leaf-version-input"
+                        type="text"
+                        spellcheck="false"
+                        prop:value=leaf_version
+                        on:input=update_leaf_version
+                    />
+                </div>
+                <div class="key-store-display-row">
+                    <div class="display-row-label">Leaf hash</div>
+                    <input
+                        class="input"
+                        type="text"
+                        placeholder="Hash of the spent tap leaf"
+                        spellcheck="false"
+                        on:input=update_leaf_hash
+                    />
+                </div>
+                <For each=move || proof_nodes.get() key=|(id, _)| *id children=proof_node />
+                <div class="button-row">
+                    <button class="flat-button bordered" on:click=add_node>
+                        Add proof node
+                    </button>
+                    <button class="flat-button bordered" on:click=build_control_block>
+                        Build control block
+                    </button>
+                </div>
+                <div class="key-store-display-row">
+                    <div class="display-row-label">Control block</div>
+                    <input class="input" type="text" readonly=true prop:value=control_block_hex />
+                    <CopyToClipboard content=control_block_hex class="copy-button">
+                        <i class="far fa-copy"></i>
+                    </CopyToClipboard>
+                </div>
+            </div>
+            <ErrorBox error=control_block_error />
+        </div>
+    }
+}
+
+#[component]
+fn MnemonicKey() -> impl IntoView {
+    let root_key = use_context::<RootKey>().expect("root key should exist in context");
+    let phrase = create_rw_signal(String::new());
+    let passphrase = create_rw_signal(String::new());
+    let mnemonic_error = create_rw_signal(String::new());
+
+    let generate = move |entropy: Option<Vec<u8>>| match entropy
+        .map(|entropy| Mnemonic::from_entropy(&entropy))
+    {
+        Some(Ok(mnemonic)) => {
+            phrase.set(mnemonic.to_string());
+            mnemonic_error.update(String::clear);
+        }
+        Some(Err(error)) => mnemonic_error.set(error.to_string()),
+        None => mnemonic_error.set("Browser cannot generate random bytes".to_string()),
+    };
+    let update_phrase = move |event: ev::Event| phrase.set(event_target_value(&event));
+    let update_passphrase = move |event: ev::Event| passphrase.set(event_target_value(&event));
+    let import = move |_event: ev::MouseEvent| {
+        let imported =
+            with!(|phrase, passphrase| mnemonic_root_key(phrase, passphrase, NetworkKind::Test));
+        match imported {
+            Ok(imported) => {
+                root_key.0.set(Some(imported));
+                mnemonic_error.update(String::clear);
+            }
+            Err(error) => mnemonic_error.set(error),
+        }
+    };
+    let root_key_text = move || {
+        root_key
+            .0
+            .with(|root_key| root_key.map(|root_key| root_key.to_string()))
+            .unwrap_or_default()
+    };
+    let word_grid = move || {
+        phrase.with(|phrase| {
+            phrase
+                .split_whitespace()
+                .enumerate()
+                .map(|(index, word)| {
+                    view! {
+                        <div class="mnemonic-word">
+                            <span class="mnemonic-index">{index + 1}</span>
+                            {word.to_string()}
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    };
+
+    view! {
+        <div class="tab-title-group">
+            <h3 class="tab-title">
+                Mnemonic
+            </h3>
+            <div class="button-row is-small">
+                <button
+                    class="flat-button bordered"
+                    type="button"
+                    on:click=move |_| generate(random_bytes::<16>().map(Vec::from))
+                >
+                    "12 words"
+                </button>
+                <button
+                    class="flat-button bordered"
+                    type="button"
+                    on:click=move |_| generate(random_bytes::<32>().map(Vec::from))
+                >
+                    "24 words"
+                </button>
+            </div>
+        </div>
+        <p class="tab-description">
+            "Generate a BIP-39 mnemonic or enter your own. Importing the mnemonic sets its BIP-32 root key, from which named keys are derived."
+        </p>
+        <textarea
+            class="input mnemonic-input"
+            rows="2"
+            placeholder="Enter mnemonic words"
+            spellcheck="false"
+            prop:value=phrase
+            on:input=update_phrase
+        />
+        <div class="mnemonic-grid">
+            {word_grid}
+        </div>
+        <div class="key-store-display-row">
+            <div class="display-row-label">Passphrase</div>
+            <input
+                class="input"
+                type="password"
+                placeholder="Optional passphrase"
+                on:input=update_passphrase
+            />
+            <button class="flat-button bordered" type="button" on:click=import>
+                Import
+            </button>
+            <CopyToClipboard content=phrase class="copy-button">
+                "Copy words"
+                <i class="far fa-copy"></i>
+            </CopyToClipboard>
+        </div>
+        <div class="key-store-display-row">
+            <div class="display-row-label">Root key</div>
+            <input class="input" type="text" readonly=true prop:value=root_key_text.clone() />
+            <CopyToClipboard content=root_key_text class="copy-button">
+                <i class="far fa-copy"></i>
+            </CopyToClipboard>
+        </div>
+        <ErrorBox error=mnemonic_error />
+    }
+}
+
+#[component]
+fn DeriveKey() -> impl IntoView {
+    let root_key = use_context::<RootKey>().expect("root key should exist in context");
+    let named_keys = use_context::<NamedKeys>().expect("named keys should exist in context");
+    let path = create_rw_signal(String::new());
+    let path_error = create_rw_signal(String::new());
+
+    let update_path = move |event: ev::Event| path.set(event_target_value(&event));
+    let derive = move |_event: ev::MouseEvent| {
+        let path = path.get_untracked();
+        let derived = root_key.0.with_untracked(|root_key| match root_key {
+            Some(root_key) => derive_key(root_key, &path),
+            None => Err("Import a mnemonic to set the root key".to_string()),
+        });
+        match derived {
+            Ok(keypair) => {
+                path_error.update(String::clear);
+                named_keys
+                    .0
+                    .update(|slots| slots.add(path.trim().to_string(), keypair));
+            }
+            Err(error) => path_error.set(error),
+        }
+    };
+
+    view! {
+        <div class="key-store-display-row">
+            <div class="display-row-label">Path</div>
+            <input
+                class="input"
+                type="text"
+                placeholder="m/84'/0'/0'/0/0"
+                spellcheck="false"
+                on:input=update_path
+            />
+            <button class="flat-button bordered" type="button" on:click=derive>
+                Derive
+            </button>
+        </div>
+        <ErrorBox error=path_error />
+    }
+}
+
+#[component]
+fn WifKey() -> impl IntoView {
+    let named_keys = use_context::<NamedKeys>().expect("named keys should exist in context");
+    let wif_input = create_rw_signal(String::new());
+    let wif_output = create_rw_signal(String::new());
+    let wif_error = create_rw_signal(String::new());
+
+    let update_wif_input = move |event: ev::Event| wif_input.set(event_target_value(&event));
+    let import_wif = move |_event: ev::MouseEvent| match wif_input
+        .with_untracked(|wif| key_from_wif(wif.trim()))
+    {
+        Ok(keypair) => {
+            named_keys.0.update(|slots| {
+                let name = format!("Key {}", slots.len() + 1);
+                slots.add(name, keypair);
+            });
+            wif_error.update(String::clear);
+        }
+        Err(error) => wif_error.set(error),
+    };
+    let export_wif = move |network: NetworkKind| {
+        let wif = named_keys.0.with_untracked(|slots| {
+            slots
+                .selected()
+                .map(|key| key_to_wif(&key.keypair, network))
+        });
+        match wif {
+            Some(wif) => {
+                wif_output.set(wif);
+                wif_error.update(String::clear);
+            }
+            None => {
+                wif_output.update(String::clear);
+                wif_error.set("Select a key to export".to_string());
+            }
+        }
+    };
+
+    view! {
+        <div class="key-store-display-row">
+            <div class="display-row-label">WIF</div>
+            <input
+                class="input"
+                type="text"
+                placeholder="Enter WIF private key"
+                spellcheck="false"
+                on:input=update_wif_input
+            />
+            <button class="flat-button bordered" type="button" on:click=import_wif>
+                Import
+            </button>
+        </div>
+        <div class="key-store-display-row">
+            <div class="display-row-label">Export</div>
+            <button
+                class="flat-button bordered"
+                type="button"
+                on:click=move |_| export_wif(NetworkKind::Main)
+            >
+                Mainnet
+            </button>
+            <button
+                class="flat-button bordered"
+                type="button"
+                on:click=move |_| export_wif(NetworkKind::Test)
+            >
+                Testnet
+            </button>
+            <input class="input" type="text" readonly=true prop:value=wif_output />
+            <CopyToClipboard content=wif_output class="copy-button">
+                <i class="far fa-copy"></i>
+            </CopyToClipboard>
+        </div>
+        <ErrorBox error=wif_error />
+    }
+}
+
+#[component]
+fn NamedKeySlots() -> impl IntoView {
+    let named_keys = use_context::<NamedKeys>().expect("named keys should exist in context");
+    let signed_data = use_context::<SignedData>().expect("signed data should exist in context");
+    let name = create_rw_signal(String::new());
+    let add_error = create_rw_signal(String::new());
+
+    let update_name = move |event: ev::Event| name.set(event_target_value(&event));
+    let add_key = move |_event: ev::MouseEvent| {
+        let keypair = random_bytes::<32>().and_then(|bytes| {
+            secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &bytes).ok()
+        });
+        match keypair {
+            Some(keypair) => {
+                let name = match name.get_untracked().trim() {
+                    "" => format!("Key {}", named_keys.0.with_untracked(KeySlots::len) + 1),
+                    name => name.to_string(),
+                };
+                named_keys.0.update(|slots| slots.add(name, keypair));
+                add_error.update(String::clear);
+            }
+            None => add_error.set("Browser cannot generate random bytes".to_string()),
+        }
+    };
+    let key_rows = move || {
+        named_keys.0.with(|slots| {
+            slots
+                .keys()
+                .iter()
+                .enumerate()
+                .map(|(index, key)| {
+                    let is_selected = slots.selected_index() == Some(index);
+                    let xonly = key.keypair.x_only_public_key().0;
+                    let xonly_hex = move || format!("0x{}", xonly.serialize().as_hex());
+                    view! {
+                        <div class="key-slot" class:selected=is_selected>
+                            <span class="key-slot-name">{key.name.clone()}</span>
+                            <CopyToClipboard content=xonly_hex class="copy-button">
+                                "Public key"
+                                <i class="far fa-copy"></i>
+                            </CopyToClipboard>
+                            <button
+                                class="flat-button bordered"
+                                type="button"
+                                on:click=move |_| named_keys.0.update(|slots| slots.select(index))
+                            >
+                                Select
+                            </button>
+                            <button
+                                class="flat-button bordered"
+                                type="button"
+                                on:click=move |_| named_keys.0.update(|slots| slots.remove(index))
+                            >
+                                <i class="fas fa-trash"></i>
+                            </button>
+                        </div>
+                    }
+                })
+                .collect_view()
+        })
+    };
+    let selected_signature = move || {
+        named_keys.0.with(|slots| {
+            slots
+                .selected()
+                .map(|key| {
+                    let signature = signed_data.sign(&key.keypair);
+                    format!("0x{}", signature.serialize().as_hex())
+                })
+                .unwrap_or_default()
+        })
+    };
+    let has_selection = move || named_keys.0.with(|slots| slots.selected().is_some());
+
+    view! {
+        <div class="tab-title-group">
+            <h3 class="tab-title">
+                Named Keys
+            </h3>
+        </div>
+        <div class="key-store-display-row">
+            <div class="display-row-label">Name</div>
+            <input
+                class="input"
+                type="text"
+                placeholder="Alice"
+                on:input=update_name
+            />
+            <button class="flat-button bordered" type="button" on:click=add_key>
+                <i class="fas fa-plus"></i>
+                " Add"
+            </button>
+        </div>
+        <ErrorBox error=add_error />
+        <DeriveKey />
+        <WifKey />
+        <div class="key-slots">
+            {key_rows}
+        </div>
+        <Show when=has_selection>
+            <div class="key-store-display-row">
+                <div class="display-row-label">Signature</div>
+                <input class="input" type="text" readonly=true prop:value=selected_signature />
+                <CopyToClipboard content=selected_signature class="copy-button">
+                    <i class="far fa-copy"></i>
+                </CopyToClipboard>
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+fn BackupKeys() -> impl IntoView {
+    let named_keys = use_context::<NamedKeys>().expect("named keys should exist in context");
+    let password = create_rw_signal(String::new());
+    let backup_error = create_rw_signal(String::new());
+    let backup_success = create_rw_signal(String::new());
+
+    let update_password = move |event: ev::Event| password.set(event_target_value(&event));
+    let backup_keys = move |_event: ev::MouseEvent| {
+        backup_success.update(String::clear);
+        let (salt, nonce) = match (random_bytes(), random_bytes()) {
+            (Some(salt), Some(nonce)) => (salt, nonce),
+            _ => {
+                backup_error.set("Browser cannot generate random bytes".to_string());
+                return;
+            }
+        };
+        let keys: Vec<(String, [u8; 32])> = named_keys.0.with_untracked(|slots| {
+            slots
+                .keys()
+                .iter()
+                .map(|key| (key.name.clone(), key.keypair.secret_bytes()))
+                .collect()
+        });
+        let backup = password
+            .with_untracked(|password| key_backup::encrypt_keys(&keys, password, salt, nonce))
+            .and_then(|backup| {
+                download_text("keys.json", &backup).map_err(|error| format!("{error:?}"))
+            });
+        match backup {
+            Ok(()) => backup_error.update(String::clear),
+            Err(error) => backup_error.set(format!("Failed to back up keys: {error}")),
+        }
+    };
+    let restore_keys = move |event: ev::Event| {
+        let input = event_target::<web_sys::HtmlInputElement>(&event);
+        let file = match input.files().and_then(|files| files.get(0)) {
+            Some(file) => file,
+            None => return,
+        };
+        // Allow the same file to be restored again
+        input.set_value("");
+
+        spawn_local(async move {
+            let text = match JsFuture::from(file.text()).await {
+                Ok(text) => text.as_string().unwrap_or_default(),
+                Err(error) => {
+                    backup_error.set(format!("Failed to read file: {error:?}"));
+                    return;
+                }
+            };
+            let restored = password
+                .with_untracked(|password| key_backup::decrypt_keys(&text, password))
+                .and_then(|keys| {
+                    keys.into_iter()
+                        .map(|(name, secret_key)| {
+                            secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &secret_key)
+                                .map(|keypair| (name, keypair))
+                                .map_err(|error| error.to_string())
+                        })
+                        .collect::<Result<Vec<_>, String>>()
+                });
+            match restored {
+                Ok(keys) => {
+                    let count = keys.len();
+                    named_keys.0.update(|slots| {
+                        for (name, keypair) in keys {
+                            slots.add(name, keypair);
+                        }
+                    });
+                    backup_error.update(String::clear);
+                    backup_success.set(format!("Restored {count} keys."));
+                }
+                Err(error) => {
+                    backup_success.update(String::clear);
+                    backup_error.set(format!("Failed to restore keys: {error}"));
+                }
+            }
+        });
+    };
+
+    view! {
+        <div class="key-store-display-row">
+            <div class="display-row-label">Password</div>
+            <input
+                class="input"
+                type="password"
+                placeholder="Password of the backup"
+                on:input=update_password
+            />
+            <button class="flat-button bordered" type="button" on:click=backup_keys>
+                <i class="fa-solid fa-download"></i>
+                " Backup"
+            </button>
+            <label class="flat-button bordered">
+                <i class="fa-solid fa-upload"></i>
+                " Restore"
+                <input class="hidden" type="file" accept=".json" on:change=restore_keys />
+            </label>
+        </div>
+        <SuccessBox success=backup_success />
+        <ErrorBox error=backup_error />
+    }
+}
+
+/// Parse hex bytes with an optional `0x` prefix.
+fn parse_hex_bytes(text: &str) -> Result<Vec<u8>, String> {
+    Vec::<u8>::from_hex(text.trim().trim_start_matches("0x")).map_err(|error| error.to_string())
+}
+
+/// Parse a 32-byte message digest from hex.
+fn parse_message(message_hex: &str) -> Result<secp256k1::Message, String> {
+    let bytes = parse_hex_bytes(message_hex).map_err(|error| format!("Message: {error}"))?;
+    secp256k1::Message::from_digest_slice(&bytes)
+        .map_err(|_| "Message: Expected exactly 32 bytes".to_string())
+}
+
+/// Check if the BIP-340 Schnorr signature of the message is valid under the x-only public key.
+///
+/// Return an error if any input is ill-formatted.
+pub fn verify_schnorr(
+    public_key_hex: &str,
+    message_hex: &str,
+    signature_hex: &str,
+) -> Result<bool, String> {
+    let public_key = parse_hex_bytes(public_key_hex)
+        .and_then(|bytes| {
+            secp256k1::XOnlyPublicKey::from_slice(&bytes).map_err(|error| error.to_string())
+        })
+        .map_err(|error| format!("Public key: {error}"))?;
+    let message = parse_message(message_hex)?;
+    let signature = parse_hex_bytes(signature_hex)
+        .and_then(|bytes| {
+            secp256k1::schnorr::Signature::from_slice(&bytes).map_err(|error| error.to_string())
+        })
+        .map_err(|error| format!("Signature: {error}"))?;
+    Ok(secp256k1::SECP256K1
+        .verify_schnorr(&signature, &message, &public_key)
+        .is_ok())
+}
+
+/// Check if the DER-encoded ECDSA signature of the message is valid under the public key.
+///
+/// Return an error if any input is ill-formatted.
+pub fn verify_ecdsa(
+    public_key_hex: &str,
+    message_hex: &str,
+    signature_hex: &str,
+) -> Result<bool, String> {
+    let public_key = parse_hex_bytes(public_key_hex)
+        .and_then(|bytes| {
+            secp256k1::PublicKey::from_slice(&bytes).map_err(|error| error.to_string())
+        })
+        .map_err(|error| format!("Public key: {error}"))?;
+    let message = parse_message(message_hex)?;
+    let signature = parse_hex_bytes(signature_hex)
+        .and_then(|bytes| {
+            secp256k1::ecdsa::Signature::from_der(&bytes).map_err(|error| error.to_string())
+        })
+        .map_err(|error| format!("Signature: {error}"))?;
+    Ok(secp256k1::SECP256K1
+        .verify_ecdsa(&message, &signature, &public_key)
+        .is_ok())
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum SignatureKind {
+    Schnorr,
+    Ecdsa,
+}
+
+#[component]
+fn VerifySignature() -> impl IntoView {
+    let kind = create_rw_signal(SignatureKind::Schnorr);
+    let public_key = create_rw_signal(String::new());
+    let message = create_rw_signal(String::new());
+    let signature = create_rw_signal(String::new());
+    let result = create_rw_signal(None::<Result<bool, String>>);
+
+    let verify = move |_event: ev::MouseEvent| {
+        let verified = with!(
+            |public_key, message, signature| match kind.get_untracked() {
+                SignatureKind::Schnorr => verify_schnorr(public_key, message, signature),
+                SignatureKind::Ecdsa => verify_ecdsa(public_key, message, signature),
+            }
+        );
+        result.set(Some(verified));
+    };
+    let select_kind = move |event: ev::Event| {
+        let selected = match event_target_value(&event).as_str() {
+            "ecdsa" => SignatureKind::Ecdsa,
+            _ => SignatureKind::Schnorr,
+        };
+        kind.set(selected);
+        result.set(None);
+    };
+    let signature_placeholder = move || match kind.get() {
+        SignatureKind::Schnorr => "64-byte Schnorr signature",
+        SignatureKind::Ecdsa => "DER-encoded ECDSA signature",
+    };
+    let public_key_placeholder = move || match kind.get() {
+        SignatureKind::Schnorr => "32-byte x-only public key",
+        SignatureKind::Ecdsa => "33-byte or 65-byte public key",
+    };
+    let result_view = move || match result.get() {
+        Some(Ok(true)) => view! {
+            <span class="verify-valid"><i class="fas fa-check"></i>" Valid"</span>
+        }
+        .into_view(),
+        Some(Ok(false)) => view! {
+            <span class="verify-invalid"><i class="fas fa-xmark"></i>" Invalid"</span>
+        }
+        .into_view(),
+        Some(Err(error)) => view! { <span class="verify-invalid">{error}</span> }.into_view(),
+        None => ().into_view(),
+    };
+
+    view! {
+        <div>
+            <div class="tab-title-group">
+                <h3 class="tab-title">
+                    Verify Signature
+                </h3>
+            </div>
+            <div class="key-store-display-row">
+                <div class="display-row-label">Scheme</div>
+                <select class="input key-select" on:change=select_kind>
+                    <option value="schnorr">Schnorr (BIP-340)</option>
+                    <option value="ecdsa">ECDSA (DER)</option>
+                </select>
+            </div>
+            <div class="key-store-display-row">
+                <div class="display-row-label">Public key</div>
+                <input
+                    class="input"
+                    type="text"
+                    placeholder=public_key_placeholder
+                    spellcheck="false"
+                    on:input=move |event| public_key.set(event_target_value(&event))
+                />
+            </div>
+            <div class="key-store-display-row">
+                <div class="display-row-label">Message</div>
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="32-byte message digest"
+                    spellcheck="false"
+                    on:input=move |event| message.set(event_target_value(&event))
+                />
+            </div>
+            <div class="key-store-display-row">
+                <div class="display-row-label">Signature</div>
+                <input
+                    class="input"
+                    type="text"
+                    placeholder=signature_placeholder
+                    spellcheck="false"
+                    on:input=move |event| signature.set(event_target_value(&event))
+                />
+            </div>
+            <div class="key-store-display-row">
+                <button class="flat-button bordered" type="button" on:click=verify>
+                    Verify
+                </button>
+                <div class="display-row-label">{result_view}</div>
+            </div>
+        </div>
+    }
+}
+
+/// Display a BIP-340 Schnorr signature as its R and S components and as a whole.
+#[component]
+fn SchnorrSigDisplay(signature: Signal<secp256k1::schnorr::Signature>) -> impl IntoView {
+    let bytes = move || signature.get().serialize();
+    let r_hex = move || format!("0x{}", bytes()[..32].as_hex());
+    let s_hex = move || format!("0x{}", bytes()[32..].as_hex());
+    let signature_hex = move || format!("0x{}", bytes().as_hex());
+
+    view! {
+        <div class="key-store-display-row">
+            <div class="display-row-label">Signature</div>
+            <input class="input" type="text" readonly=true prop:value=signature_hex />
+            <CopyToClipboard content=signature_hex class="copy-button">
+                <i class="far fa-copy"></i>
+            </CopyToClipboard>
+        </div>
+        <div class="key-store-display-row">
+            <div class="display-row-label">R</div>
+            <input class="input" type="text" readonly=true prop:value=r_hex.clone() />
+            <CopyButton content=r_hex />
+        </div>
+        <div class="key-store-display-row">
+            <div class="display-row-label">S</div>
+            <input class="input" type="text" readonly=true prop:value=s_hex.clone() />
+            <CopyButton content=s_hex />
         </div>
     }
 }
@@ -176,9 +1433,7 @@ fn CopySignaturesToClipboard() -> impl IntoView {
     let signed_data = use_context::<SignedData>().expect("signed data should exist in context");
     let key_count = use_context::<KeyCount>().expect("key count should exist in context");
     let signatures = create_memo(move |_| -> [secp256k1::schnorr::Signature; 26] {
-        std::array::from_fn(|index| {
-            signing_keys.secret_keys[index].sign_schnorr(signed_data.message.get())
-        })
+        std::array::from_fn(|index| signed_data.sign(&signing_keys.secret_keys[index]))
     });
 
     let copy_single_signature =
@@ -382,6 +1637,302 @@ fn SelectSignedData() -> impl IntoView {
                     />
                 </label>
             </fieldset>
+            <CustomNonce />
         </div>
     }
 }
+
+#[component]
+fn CustomNonce() -> impl IntoView {
+    let signed_data = use_context::<SignedData>().expect("signed data should exist in context");
+    let nonce_error = create_rw_signal(String::new());
+    let initial_value = signed_data
+        .aux_rand
+        .get_untracked()
+        .map(|aux_rand| format!("0x{}", aux_rand.as_hex()))
+        .unwrap_or_default();
+
+    let update_aux_rand = move |event: ev::Event| {
+        let text = event_target_value(&event);
+        let text = text.trim().trim_start_matches("0x");
+        match text.is_empty() {
+            true => {
+                signed_data.aux_rand.set(None);
+                nonce_error.update(String::clear);
+            }
+            false => match <[u8; 32]>::from_hex(text) {
+                Ok(aux_rand) => {
+                    signed_data.aux_rand.set(Some(aux_rand));
+                    nonce_error.update(String::clear);
+                }
+                Err(..) => nonce_error.set("Expected exactly 64 hex digits".to_string()),
+            },
+        }
+    };
+
+    view! {
+        <div class="key-store-display-row">
+            <div class="display-row-label">
+                Custom nonce (aux rand)
+            </div>
+            <input
+                class="input"
+                type="text"
+                placeholder="Leave empty to use system randomness"
+                spellcheck="false"
+                value=initial_value
+                on:input=update_aux_rand
+            />
+        </div>
+        <p class="tab-description">
+            "Warning: Signing with fixed auxiliary randomness is only meant for reproducing test vectors. Never use non-random nonces in production."
+        </p>
+        <ErrorBox error=nonce_error />
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn wif_roundtrip() {
+        // Secret key 1 in compressed form
+        for wif in [
+            "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn",
+            "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN87JcbXMTcA",
+        ] {
+            let keypair = key_from_wif(wif).unwrap();
+            let network = match wif.starts_with('c') {
+                true => NetworkKind::Test,
+                false => NetworkKind::Main,
+            };
+            assert_eq!(wif, key_to_wif(&keypair, network));
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn wif_known_key() {
+        let wif = "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dxnNQWbqYxxxrJKpc";
+        let keypair = key_from_wif(wif).unwrap();
+        assert_eq!(
+            "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d",
+            keypair.secret_bytes().to_lower_hex_string()
+        );
+        assert!(key_from_wif("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dxnNQWbqYxxxrJKpd").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn wif_key_signs() {
+        let keypair = key_from_wif("KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn").unwrap();
+        let mut slots = KeySlots::default();
+        slots.add("Key 1".to_string(), keypair);
+        slots.select(0);
+
+        // The public key of secret key 1 is the generator point
+        let public_key = secp256k1::XOnlyPublicKey::from_slice(
+            &<[u8; 32]>::from_hex(
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let message = secp256k1::Message::from_digest([1; 32]);
+        let signature = slots.selected().unwrap().keypair.sign_schnorr(message);
+        assert!(secp256k1::SECP256K1
+            .verify_schnorr(&signature, &message, &public_key)
+            .is_ok());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn tap_tweak_bip341() {
+        // Test vectors from the BIP-341 wallet test vectors (scriptPubKey 0 and 1)
+        let vectors = [
+            (
+                "d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d",
+                None,
+                "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343",
+            ),
+            (
+                "187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27",
+                Some("5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21"),
+                "147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+            ),
+        ];
+        for (internal_key, merkle_root, expected) in vectors {
+            let internal_key =
+                secp256k1::XOnlyPublicKey::from_slice(&<[u8; 32]>::from_hex(internal_key).unwrap())
+                    .unwrap();
+            let merkle_root = merkle_root.map(|root| <[u8; 32]>::from_hex(root).unwrap());
+            let output_key = tap_tweak("TapTweak", internal_key, merkle_root).unwrap();
+            assert_eq!(expected, output_key.serialize().to_lower_hex_string());
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn taproot_output_bip341() {
+        // Test vectors from the BIP-341 wallet test vectors (scriptPubKey 0 and 1)
+        let vectors = [
+            (
+                "d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d",
+                None,
+                "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343",
+                "bc1p2wsldez5mud2yam29q22wgfh9439spgduvct83k3pm50fcxa5dps59h4z5",
+            ),
+            (
+                "187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27",
+                Some("5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21"),
+                "147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+                "bc1pz37fc4cn9ah8anwm4xqqhvxygjf9rjf2resrw8h8w4tmvcs0863sa2e586",
+            ),
+        ];
+        for (internal_key, merkle_root, expected_key, expected_address) in vectors {
+            let internal_key =
+                secp256k1::XOnlyPublicKey::from_slice(&<[u8; 32]>::from_hex(internal_key).unwrap())
+                    .unwrap();
+            let merkle_root = merkle_root.map(|root| <[u8; 32]>::from_hex(root).unwrap());
+            let (output_key, address) = taproot_output(internal_key, merkle_root).unwrap();
+            assert_eq!(expected_key, output_key.serialize().to_lower_hex_string());
+            assert_eq!(expected_address, address.to_string());
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn control_block_bip341() {
+        // Test vector from the BIP-341 wallet test vectors (scriptPubKey 1)
+        let internal_key = secp256k1::XOnlyPublicKey::from_slice(
+            &<[u8; 32]>::from_hex(
+                "187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let leaf_hash = <[u8; 32]>::from_hex(
+            "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21",
+        )
+        .unwrap();
+        let single_leaf = control_block(internal_key, 0xc0, leaf_hash, &[]).unwrap();
+        assert_eq!(
+            "c1187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27",
+            single_leaf.serialize().to_lower_hex_string()
+        );
+
+        let path = [[1; 32], [2; 32]];
+        let serialized = control_block(internal_key, 0xbe, leaf_hash, &path)
+            .unwrap()
+            .serialize();
+        assert_eq!(33 + 32 * path.len(), serialized.len());
+        assert_eq!(0xbe, serialized[0] & 0xfe);
+        assert!(control_block(internal_key, 0xc1, leaf_hash, &path).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn derive_key_from_path() {
+        // Test vector from BIP-84 (first receiving address of account 0)
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let root_key = mnemonic_root_key(phrase, "", NetworkKind::Main).unwrap();
+        let hardened = derive_key(&root_key, "m/84'/0'/0'/0/0").unwrap();
+        assert_eq!(
+            "KyZpNDKnfs94vbrwhJneDi77V6jF64PWPF8x5cdJb8ifgg2DUc9d",
+            key_to_wif(&hardened, NetworkKind::Main)
+        );
+        assert_eq!(
+            "0330d54fd0dd420a6e5f8d3624f5f3482cae350f79d5f0753bf5beef9c2d91af3c",
+            hardened.public_key().serialize().to_lower_hex_string()
+        );
+
+        let non_hardened = derive_key(&root_key, "m/84/0/0/0/0").unwrap();
+        assert_ne!(hardened.secret_bytes(), non_hardened.secret_bytes());
+        assert_eq!(
+            hardened.secret_bytes(),
+            derive_key(&root_key, "m/84h/0h/0h/0/0")
+                .unwrap()
+                .secret_bytes()
+        );
+        assert!(derive_key(&root_key, "m/foo").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn mnemonic_root_key_bip39() {
+        // Test vector from BIP-39 (Trezor reference implementation)
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let root_key = mnemonic_root_key(phrase, "TREZOR", NetworkKind::Main).unwrap();
+        assert_eq!(
+            "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF",
+            root_key.to_string()
+        );
+        let bad_checksum = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert!(mnemonic_root_key(bad_checksum, "", NetworkKind::Main).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn add_and_remove_key_slots() {
+        let signing_keys = SigningKeys::default();
+        let mut slots = KeySlots::default();
+        assert!(slots.is_empty());
+        slots.add("Alice".to_string(), signing_keys.secret_keys[0]);
+        slots.add("Bob".to_string(), signing_keys.secret_keys[1]);
+        slots.add("Charlie".to_string(), signing_keys.secret_keys[2]);
+        assert_eq!(3, slots.len());
+
+        slots.select(2);
+        slots.remove(0);
+        assert_eq!(2, slots.len());
+        assert_eq!("Charlie", slots.selected().unwrap().name);
+
+        slots.remove(1);
+        assert_eq!(1, slots.len());
+        assert!(slots.selected().is_none());
+
+        slots.remove(5);
+        assert_eq!(1, slots.len());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn verify_signatures() {
+        let signing_keys = SigningKeys::default();
+        let keypair = signing_keys.secret_keys[0];
+        let message = secp256k1::Message::from_digest([7; 32]);
+        let message_hex = format!("0x{}", [7u8; 32].as_hex());
+        let other_message_hex = format!("0x{}", [8u8; 32].as_hex());
+
+        let xonly_hex = signing_keys.public_keys[0].serialize().as_hex().to_string();
+        let schnorr_hex = keypair
+            .sign_schnorr(message)
+            .serialize()
+            .as_hex()
+            .to_string();
+        assert_eq!(
+            Ok(true),
+            verify_schnorr(&xonly_hex, &message_hex, &schnorr_hex)
+        );
+        assert_eq!(
+            Ok(false),
+            verify_schnorr(&xonly_hex, &other_message_hex, &schnorr_hex)
+        );
+
+        let public_key_hex = keypair.public_key().serialize().as_hex().to_string();
+        let ecdsa = secp256k1::SECP256K1.sign_ecdsa(&message, &keypair.secret_key());
+        let ecdsa_hex = ecdsa.serialize_der().as_hex().to_string();
+        assert_eq!(
+            Ok(true),
+            verify_ecdsa(&public_key_hex, &message_hex, &ecdsa_hex)
+        );
+        assert_eq!(
+            Ok(false),
+            verify_ecdsa(&public_key_hex, &other_message_hex, &ecdsa_hex)
+        );
+
+        assert!(verify_schnorr(&xonly_hex, "0x1234", &schnorr_hex).is_err());
+    }
+}
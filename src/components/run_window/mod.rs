@@ -1,19 +1,35 @@
+mod call_graph;
+mod debugger_tab;
+mod examples_tab;
 mod execution_tab;
+mod fuzzer_tab;
 mod hash_store_tab;
 mod key_store_tab;
+mod psbt_tab;
+mod resource_meter;
+mod saved_programs_panel;
+mod test_cases_panel;
 mod transaction_tab;
+mod witness_form;
 
 use crate::components::app::ActiveRunTab;
 use leptos::{component, use_context, view, IntoView};
 
+use self::debugger_tab::DebuggerTab;
+use self::examples_tab::ExamplesTab;
 use self::execution_tab::ExecutionTab;
+use self::fuzzer_tab::FuzzerTab;
 use self::hash_store_tab::HashStoreTab;
 use self::key_store_tab::KeyStoreTab;
+use self::psbt_tab::PsbtTab;
+use self::saved_programs_panel::SavedProgramsPanel;
 use self::transaction_tab::TransactionTab;
 use crate::components::navbar::{Navbar, Tab};
+use crate::components::type_explorer::TypeExplorer;
 
-pub use self::hash_store_tab::HashCount;
-pub use self::key_store_tab::{KeyCount, SignedData};
+pub use self::debugger_tab::Breakpoints;
+pub use self::hash_store_tab::{ComputedHashes, HashCount};
+pub use self::key_store_tab::{KeyCount, NamedKeys, RootKey, SignedData};
 pub use self::transaction_tab::TxEnv;
 
 #[component]
@@ -21,19 +37,39 @@ pub fn RunWindow() -> impl IntoView {
     let active_run_tab =
         use_context::<ActiveRunTab>().expect("active run tab should exist in context");
     view! {
-        <Navbar default_tab="Execution" active_tab=active_run_tab.0>
+        <Navbar default_tab="Execution" active_tab=active_run_tab.0 route=true>
             <Tab name="Execution">
                 <ExecutionTab />
             </Tab>
+            <Tab name="Debugger">
+                <DebuggerTab />
+            </Tab>
+            <Tab name="Fuzzer">
+                <FuzzerTab />
+            </Tab>
             <Tab name="Transaction">
                 <TransactionTab />
             </Tab>
+            <Tab name="PSBT">
+                <PsbtTab />
+            </Tab>
             <Tab name="Key Store">
                 <KeyStoreTab />
             </Tab>
             <Tab name="Hash Store">
                 <HashStoreTab />
             </Tab>
+            <Tab name="Types">
+                <div class="tab-content">
+                    <TypeExplorer />
+                </div>
+            </Tab>
+            <Tab name="Examples">
+                <ExamplesTab />
+            </Tab>
+            <Tab name="Programs">
+                <SavedProgramsPanel />
+            </Tab>
         </Navbar>
     }
 }
@@ -0,0 +1,718 @@
+use std::str::FromStr;
+
+use bitcoin::hashes::Hash;
+use bitcoin::key::{TapTweak, TweakedPublicKey, UntweakedPublicKey};
+use bitcoin::psbt::Psbt;
+use bitcoin::sighash::{Prevouts, SighashCache, TapSighashType};
+use bitcoin::taproot::{LeafVersion, TapLeafHash, TapNodeHash, TaprootBuilder};
+use bitcoin::{Address, Network, ScriptBuf};
+use hex_conservative::{DisplayHex, FromHex};
+use leptos::{
+    component, create_rw_signal, ev, event_target_value, use_context, view, CollectView, For,
+    IntoView, RwSignal, Show, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
+    SignalWith, SignalWithUntracked,
+};
+use simfony::elements::bitcoin;
+use simfony::elements::secp256k1_zkp as secp256k1;
+use simfony::simplicity;
+
+use super::NamedKeys;
+
+use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::navbar::{Navbar, Tab};
+use crate::components::program_window::Program;
+use crate::components::string_box::ErrorBox;
+use crate::util;
+
+/// Maximum number of inputs or outputs of an assembled transaction.
+const MAX_ENTRIES: usize = 16;
+
+/// Text fields of a transaction input.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InputFields {
+    pub txid: String,
+    pub vout: String,
+    pub sequence: String,
+}
+
+impl Default for InputFields {
+    fn default() -> Self {
+        Self {
+            txid: String::new(),
+            vout: "0".to_string(),
+            sequence: "0xffffffff".to_string(),
+        }
+    }
+}
+
+/// Text fields of a transaction output.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OutputFields {
+    pub value: String,
+    pub script_pubkey: String,
+}
+
+/// Parse a 32-bit integer in decimal or in hex with a `0x` prefix.
+fn parse_u32(text: &str) -> Result<u32, String> {
+    let text = text.trim();
+    match text.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => text.parse(),
+    }
+    .map_err(|error| format!("{text}: {error}"))
+}
+
+/// Assemble a Bitcoin transaction from the given form fields.
+pub fn assemble_transaction(
+    version: &str,
+    lock_time: &str,
+    inputs: &[InputFields],
+    outputs: &[OutputFields],
+) -> Result<bitcoin::Transaction, String> {
+    let version = parse_u32(version).map_err(|error| format!("Version: {error}"))?;
+    let lock_time = parse_u32(lock_time).map_err(|error| format!("Lock time: {error}"))?;
+    let input = inputs
+        .iter()
+        .enumerate()
+        .map(|(index, fields)| {
+            let txid = bitcoin::Txid::from_str(fields.txid.trim())
+                .map_err(|error| format!("Input {index} txid: {error}"))?;
+            let vout =
+                parse_u32(&fields.vout).map_err(|error| format!("Input {index} vout: {error}"))?;
+            let sequence = parse_u32(&fields.sequence)
+                .map_err(|error| format!("Input {index} sequence: {error}"))?;
+            Ok(bitcoin::TxIn {
+                previous_output: bitcoin::OutPoint { txid, vout },
+                script_sig: bitcoin::ScriptBuf::new(),
+                sequence: bitcoin::Sequence::from_consensus(sequence),
+                witness: bitcoin::Witness::new(),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let output = outputs
+        .iter()
+        .enumerate()
+        .map(|(index, fields)| {
+            let value = fields
+                .value
+                .trim()
+                .parse::<u64>()
+                .map_err(|error| format!("Output {index} value: {error}"))?;
+            let script_pubkey = Vec::<u8>::from_hex(fields.script_pubkey.trim())
+                .map_err(|error| format!("Output {index} script pubkey: {error}"))?;
+            Ok(bitcoin::TxOut {
+                value: bitcoin::Amount::from_sat(value),
+                script_pubkey: bitcoin::ScriptBuf::from_bytes(script_pubkey),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    Ok(bitcoin::Transaction {
+        version: bitcoin::transaction::Version(version as i32),
+        lock_time: bitcoin::absolute::LockTime::from_consensus(lock_time),
+        input,
+        output,
+    })
+}
+
+/// Leaf version of Simplicity programs in tap leaves.
+const SIMPLICITY_LEAF_VERSION: u8 = 0xbe;
+
+/// Bitcoin taproot output whose tap tree consists of a single Simplicity leaf.
+#[derive(Clone, Debug)]
+pub struct ProgramTaproot {
+    pub leaf_hash: TapLeafHash,
+    pub merkle_root: TapNodeHash,
+    pub output_key: TweakedPublicKey,
+    pub address: Address,
+}
+
+/// Derive the Bitcoin taproot output that commits to the program with the given CMR.
+///
+/// The CMR is the script of the only leaf of the tap tree.
+pub fn program_taproot(
+    cmr: simplicity::Cmr,
+    internal_key: UntweakedPublicKey,
+) -> Result<ProgramTaproot, String> {
+    let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+    let script = ScriptBuf::from_bytes(cmr.as_ref().to_vec());
+    let version =
+        LeafVersion::from_consensus(SIMPLICITY_LEAF_VERSION).map_err(|error| error.to_string())?;
+    let spend_info = TaprootBuilder::new()
+        .add_leaf_with_ver(0, script.clone(), version)
+        .map_err(|error| error.to_string())?
+        .finalize(&secp, internal_key)
+        .map_err(|_| "Tap tree should be complete".to_string())?;
+    let merkle_root = spend_info
+        .merkle_root()
+        .ok_or_else(|| "Tap tree should have a merkle root".to_string())?;
+    let output_key = spend_info.output_key();
+
+    Ok(ProgramTaproot {
+        leaf_hash: TapLeafHash::from_script(&script, version),
+        merkle_root,
+        output_key,
+        address: Address::p2tr_tweaked(output_key, Network::Bitcoin),
+    })
+}
+
+/// Create a PSBT that spends the given UTXOs with the given unsigned transaction.
+///
+/// There must be one UTXO for each input of the transaction.
+pub fn create_psbt(tx: bitcoin::Transaction, utxos: &[OutputFields]) -> Result<Psbt, String> {
+    if tx.input.len() != utxos.len() {
+        return Err(format!(
+            "Expected {} UTXOs but got {}",
+            tx.input.len(),
+            utxos.len()
+        ));
+    }
+    let mut psbt = Psbt::from_unsigned_tx(tx).map_err(|error| error.to_string())?;
+    for (index, (input, fields)) in psbt.inputs.iter_mut().zip(utxos).enumerate() {
+        let value = fields
+            .value
+            .trim()
+            .parse::<u64>()
+            .map_err(|error| format!("UTXO {index} value: {error}"))?;
+        let script_pubkey = Vec::<u8>::from_hex(fields.script_pubkey.trim())
+            .map_err(|error| format!("UTXO {index} script pubkey: {error}"))?;
+        input.witness_utxo = Some(bitcoin::TxOut {
+            value: bitcoin::Amount::from_sat(value),
+            script_pubkey: bitcoin::ScriptBuf::from_bytes(script_pubkey),
+        });
+    }
+    Ok(psbt)
+}
+
+/// Sign the given input of the PSBT via the taproot key path.
+///
+/// The input must spend a P2TR output without script tree whose internal key is the given key.
+pub fn sign_key_spend(
+    psbt: &mut Psbt,
+    input_index: usize,
+    keypair: &secp256k1::Keypair,
+) -> Result<(), String> {
+    let secp = bitcoin::secp256k1::Secp256k1::new();
+    let keypair = bitcoin::secp256k1::Keypair::from_seckey_slice(&secp, &keypair.secret_bytes())
+        .map_err(|error| error.to_string())?;
+    let prevouts = psbt
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| {
+            input
+                .witness_utxo
+                .clone()
+                .ok_or_else(|| format!("Input {index} is missing its UTXO"))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let utxo = prevouts
+        .get(input_index)
+        .ok_or_else(|| format!("There is no input {input_index}"))?;
+    let (internal_key, _parity) = keypair.x_only_public_key();
+    if utxo.script_pubkey != ScriptBuf::new_p2tr(&secp, internal_key, None) {
+        return Err(format!(
+            "Input {input_index} is not a key spend of the selected key"
+        ));
+    }
+    let sighash = SighashCache::new(&psbt.unsigned_tx)
+        .taproot_key_spend_signature_hash(
+            input_index,
+            &Prevouts::All(&prevouts),
+            TapSighashType::Default,
+        )
+        .map_err(|error| error.to_string())?;
+    let message = bitcoin::secp256k1::Message::from_digest(sighash.to_byte_array());
+    let tweaked = keypair.tap_tweak(&secp, None);
+    let signature = secp.sign_schnorr_no_aux_rand(&message, &tweaked.to_inner());
+    psbt.inputs[input_index].tap_key_sig = Some(bitcoin::taproot::Signature {
+        signature,
+        sighash_type: TapSighashType::Default,
+    });
+    Ok(())
+}
+
+/// Move the key path signatures of the PSBT into the final witnesses of their inputs.
+///
+/// Return an error if an input is not signed.
+pub fn finalize_key_spends(psbt: &mut Psbt) -> Result<(), String> {
+    let is_unsigned = |input: &bitcoin::psbt::Input| {
+        input.final_script_witness.is_none() && input.tap_key_sig.is_none()
+    };
+    if let Some(index) = psbt.inputs.iter().position(is_unsigned) {
+        return Err(format!("Input {index} is not signed"));
+    }
+    for input in &mut psbt.inputs {
+        if let Some(signature) = input.tap_key_sig.take() {
+            input.final_script_witness = Some(bitcoin::Witness::p2tr_key_spend(&signature));
+        }
+    }
+    Ok(())
+}
+
+/// Form fields of a transaction input.
+#[derive(Copy, Clone, Debug)]
+struct InputRow {
+    txid: RwSignal<String>,
+    vout: RwSignal<String>,
+    sequence: RwSignal<String>,
+    /// Output that the input spends.
+    utxo: OutputRow,
+}
+
+impl Default for InputRow {
+    fn default() -> Self {
+        let fields = InputFields::default();
+        Self {
+            txid: create_rw_signal(fields.txid),
+            vout: create_rw_signal(fields.vout),
+            sequence: create_rw_signal(fields.sequence),
+            utxo: OutputRow::default(),
+        }
+    }
+}
+
+impl InputRow {
+    fn fields(self) -> InputFields {
+        InputFields {
+            txid: self.txid.get(),
+            vout: self.vout.get(),
+            sequence: self.sequence.get(),
+        }
+    }
+}
+
+/// Form fields of a transaction output.
+#[derive(Copy, Clone, Debug, Default)]
+struct OutputRow {
+    value: RwSignal<String>,
+    script_pubkey: RwSignal<String>,
+}
+
+impl OutputRow {
+    fn fields(self) -> OutputFields {
+        OutputFields {
+            value: self.value.get(),
+            script_pubkey: self.script_pubkey.get(),
+        }
+    }
+}
+
+#[component]
+pub fn PsbtTab() -> impl IntoView {
+    let version = create_rw_signal("2".to_string());
+    let lock_time = create_rw_signal("0".to_string());
+    let inputs = create_rw_signal(vec![InputRow::default()]);
+    let outputs = create_rw_signal(vec![OutputRow::default()]);
+
+    let assembled = Signal::derive(move || {
+        let inputs = inputs.with(|rows| rows.iter().map(|row| row.fields()).collect::<Vec<_>>());
+        let outputs = outputs.with(|rows| rows.iter().map(|row| row.fields()).collect::<Vec<_>>());
+        version.with(|version| {
+            lock_time.with(|lock_time| assemble_transaction(version, lock_time, &inputs, &outputs))
+        })
+    });
+    let tx_hex = Signal::derive(move || match assembled.get() {
+        Ok(tx) => bitcoin::consensus::serialize(&tx).to_lower_hex_string(),
+        Err(..) => String::new(),
+    });
+    let tx_error = Signal::derive(move || assembled.get().err().unwrap_or_default());
+    let input_rows = move |(index, row): (usize, InputRow)| {
+        view! {
+            <TextRow label=format!("Input {index} txid") text=row.txid />
+            <TextRow label=format!("Input {index} vout") text=row.vout />
+            <TextRow label=format!("Input {index} sequence") text=row.sequence />
+        }
+    };
+    let output_rows = move |(index, row): (usize, OutputRow)| {
+        view! {
+            <TextRow label=format!("Output {index} value") text=row.value />
+            <TextRow label=format!("Output {index} script pubkey") text=row.script_pubkey />
+        }
+    };
+
+    let builder = move || {
+        view! {
+            <div class="tab-title-group">
+                <h3 class="tab-title">"Transaction builder"</h3>
+            </div>
+            <p class="tab-description">
+                "Assemble a Bitcoin transaction and paste its hex into the Transaction tab."
+            </p>
+            <TextRow label="Version" text=version />
+            <TextRow label="Lock time" text=lock_time />
+            <EntryCount label="Inputs" entries=inputs />
+            <For
+                each=move || inputs.get().into_iter().enumerate()
+                key=|(index, _)| *index
+                children=input_rows
+            />
+            <EntryCount label="Outputs" entries=outputs />
+            <For
+                each=move || outputs.get().into_iter().enumerate()
+                key=|(index, _)| *index
+                children=output_rows
+            />
+            <ReadonlyRow label="Transaction" value=tx_hex />
+            <ErrorBox error=tx_error />
+            <DeriveP2trAddress />
+        }
+    };
+    let active_tab = create_rw_signal("Builder");
+
+    view! {
+        <div class="tab-content psbt-tab">
+            <Navbar default_tab="Builder" active_tab=active_tab>
+                <Tab name="Builder">{builder}</Tab>
+                <Tab name="Signing">
+                    <PsbtPanel assembled=assembled inputs=inputs />
+                </Tab>
+            </Navbar>
+        </div>
+    }
+}
+
+#[component]
+fn PsbtPanel(
+    assembled: Signal<Result<bitcoin::Transaction, String>>,
+    inputs: RwSignal<Vec<InputRow>>,
+) -> impl IntoView {
+    let named_keys = use_context::<NamedKeys>().expect("named keys should exist in context");
+    let psbt = create_rw_signal(None::<Psbt>);
+    let psbt_error = create_rw_signal(String::new());
+    let selected_key = create_rw_signal(0usize);
+    let selected_input = create_rw_signal(0usize);
+
+    let set_result = move |result: Result<(), String>| match result {
+        Ok(()) => psbt_error.update(String::clear),
+        Err(error) => psbt_error.set(error),
+    };
+    let create = move |_event: ev::MouseEvent| {
+        let utxos = inputs
+            .with_untracked(|rows| rows.iter().map(|row| row.utxo.fields()).collect::<Vec<_>>());
+        let created = assembled
+            .get_untracked()
+            .and_then(|tx| create_psbt(tx, &utxos));
+        set_result(created.map(|created| psbt.set(Some(created))));
+    };
+    let sign = move |_event: ev::MouseEvent| {
+        let keypair = named_keys.0.with_untracked(|slots| {
+            slots
+                .keys()
+                .get(selected_key.get_untracked())
+                .map(|key| key.keypair)
+                .ok_or_else(|| "Add a key in the Key Store tab first".to_string())
+        });
+        let signed = keypair.and_then(|keypair| {
+            psbt.try_update(|psbt| match psbt {
+                Some(psbt) => sign_key_spend(psbt, selected_input.get_untracked(), &keypair),
+                None => Err("Create a PSBT first".to_string()),
+            })
+            .unwrap_or(Ok(()))
+        });
+        set_result(signed);
+    };
+    let finalize = move |_event: ev::MouseEvent| {
+        let finalized = psbt
+            .try_update(|psbt| match psbt {
+                Some(psbt) => finalize_key_spends(psbt),
+                None => Err("Create a PSBT first".to_string()),
+            })
+            .unwrap_or(Ok(()));
+        set_result(finalized);
+    };
+    let psbt_hex = Signal::derive(move || {
+        psbt.with(|psbt| {
+            psbt.as_ref()
+                .map(|psbt| psbt.serialize().to_lower_hex_string())
+                .unwrap_or_default()
+        })
+    });
+    let utxo_rows = move |(index, row): (usize, InputRow)| {
+        view! {
+            <TextRow label=format!("UTXO {index} value") text=row.utxo.value />
+            <TextRow label=format!("UTXO {index} script pubkey") text=row.utxo.script_pubkey />
+        }
+    };
+    let key_options = move || {
+        named_keys.0.with(|slots| {
+            slots
+                .keys()
+                .iter()
+                .enumerate()
+                .map(|(index, key)| {
+                    view! { <option value=index.to_string()>{key.name.clone()}</option> }
+                })
+                .collect_view()
+        })
+    };
+    let input_options = move || {
+        (0..inputs.with(Vec::len))
+            .map(|index| view! { <option value=index.to_string()>{format!("Input {index}")}</option> })
+            .collect_view()
+    };
+    let select_key = move |event: ev::Event| {
+        if let Ok(index) = event_target_value(&event).parse::<usize>() {
+            selected_key.set(index);
+        }
+    };
+    let select_input = move |event: ev::Event| {
+        if let Ok(index) = event_target_value(&event).parse::<usize>() {
+            selected_input.set(index);
+        }
+    };
+
+    view! {
+        <div class="tab-title-group">
+            <h3 class="tab-title">"PSBT"</h3>
+        </div>
+        <p class="tab-description">
+            "Create a PSBT from the assembled transaction and sign its P2TR key path inputs with keys from the Key Store tab."
+        </p>
+        <For
+            each=move || inputs.get().into_iter().enumerate()
+            key=|(index, _)| *index
+            children=utxo_rows
+        />
+        <div class="button-row">
+            <button class="flat-button bordered" on:click=create>
+                "Create PSBT"
+            </button>
+        </div>
+        <Show when=move || psbt.with(Option::is_some)>
+            <div class="key-store-display-row">
+                <select class="input key-select" on:change=select_key>
+                    {key_options}
+                </select>
+                <select class="input" on:change=select_input>
+                    {input_options}
+                </select>
+                <button class="flat-button bordered" on:click=sign>
+                    "Sign input"
+                </button>
+                <button class="flat-button bordered" on:click=finalize>
+                    "Finalize"
+                </button>
+            </div>
+        </Show>
+        <ReadonlyRow label="PSBT" value=psbt_hex />
+        <ErrorBox error=psbt_error />
+    }
+}
+
+#[component]
+fn DeriveP2trAddress() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let internal_key = create_rw_signal(util::unspendable_internal_key().to_string());
+
+    let derived = Signal::derive(move || {
+        let cmr = program.lazy_cmr.get()?;
+        let internal_key = internal_key.with(|text| {
+            UntweakedPublicKey::from_str(text.trim().trim_start_matches("0x"))
+                .map_err(|error| format!("Internal key: {error}"))
+        })?;
+        program_taproot(cmr, internal_key).map(|taproot| (cmr, taproot))
+    });
+    let field = move |f: fn(&simplicity::Cmr, &ProgramTaproot) -> String| {
+        Signal::derive(move || {
+            derived.with(|derived| {
+                derived
+                    .as_ref()
+                    .map(|(cmr, taproot)| f(cmr, taproot))
+                    .unwrap_or_default()
+            })
+        })
+    };
+    let cmr = field(|cmr, _| cmr.to_string());
+    let leaf_hash = field(|_, taproot| taproot.leaf_hash.to_string());
+    let merkle_root = field(|_, taproot| taproot.merkle_root.to_string());
+    let output_key = field(|_, taproot| taproot.output_key.to_string());
+    let address = field(|_, taproot| taproot.address.to_string());
+    let derive_error = Signal::derive(move || {
+        derived.with(|derived| derived.as_ref().err().cloned().unwrap_or_default())
+    });
+
+    view! {
+        <div class="tab-title-group">
+            <h3 class="tab-title">"Derive P2TR address"</h3>
+        </div>
+        <p class="tab-description">
+            "Derive the Bitcoin address whose tap tree has the program CMR as its only leaf."
+        </p>
+        <TextRow label="Internal key" text=internal_key />
+        <ReadonlyRow label="CMR" value=cmr />
+        <ReadonlyRow label="Leaf hash" value=leaf_hash />
+        <ReadonlyRow label="Merkle root" value=merkle_root />
+        <ReadonlyRow label="Output key" value=output_key />
+        <ReadonlyRow label="Address" value=address />
+        <ErrorBox error=derive_error />
+    }
+}
+
+#[component]
+fn EntryCount<T: Default + 'static>(
+    label: &'static str,
+    entries: RwSignal<Vec<T>>,
+) -> impl IntoView {
+    let count = move || entries.with(Vec::len).to_string();
+    let update_count = move |event: ev::Event| {
+        if let Ok(count) = event_target_value(&event).parse::<usize>() {
+            entries.update(|entries| entries.resize_with(count.min(MAX_ENTRIES), T::default));
+        }
+    };
+
+    view! {
+        <div class="key-store-display-row">
+            <div class="display-row-label">{label}</div>
+            <input
+                class="input"
+                type="number"
+                min="0"
+                max=MAX_ENTRIES
+                prop:value=count
+                on:change=update_count
+            />
+        </div>
+    }
+}
+
+#[component]
+fn ReadonlyRow(label: &'static str, value: Signal<String>) -> impl IntoView {
+    view! {
+        <div class="key-store-display-row">
+            <div class="display-row-label">{label}</div>
+            <input class="input" type="text" readonly=true prop:value=value />
+            <CopyToClipboard content=value class="copy-button">
+                <i class="far fa-copy"></i>
+            </CopyToClipboard>
+        </div>
+    }
+}
+
+#[component]
+fn TextRow(#[prop(into)] label: String, text: RwSignal<String>) -> impl IntoView {
+    let update_text = move |event: ev::Event| text.set(event_target_value(&event));
+
+    view! {
+        <div class="key-store-display-row">
+            <div class="display-row-label">{label}</div>
+            <input
+                class="input"
+                type="text"
+                spellcheck="false"
+                prop:value=text
+                on:input=update_text
+            />
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn assemble_bitcoin_transaction() {
+        let txid = "0707070707070707070707070707070707070707070707070707070707070707";
+        let inputs = [InputFields {
+            txid: txid.to_string(),
+            vout: "3".to_string(),
+            sequence: "0xfffffffd".to_string(),
+        }];
+        let outputs = [OutputFields {
+            value: "50000".to_string(),
+            script_pubkey: "0014".to_string() + &"00".repeat(20),
+        }];
+        let tx = assemble_transaction("2", "800000", &inputs, &outputs).unwrap();
+        assert_eq!(2, tx.version.0);
+        assert_eq!(800_000, tx.lock_time.to_consensus_u32());
+        assert_eq!(txid, tx.input[0].previous_output.txid.to_string());
+        assert_eq!(3, tx.input[0].previous_output.vout);
+        assert_eq!(0xfffffffd, tx.input[0].sequence.to_consensus_u32());
+        assert_eq!(50_000, tx.output[0].value.to_sat());
+        assert!(tx.output[0].script_pubkey.is_p2wpkh());
+
+        let bytes = bitcoin::consensus::serialize(&tx);
+        let decoded: bitcoin::Transaction = bitcoin::consensus::deserialize(&bytes).unwrap();
+        assert_eq!(tx, decoded);
+
+        let bad_txid = [InputFields {
+            txid: "00".to_string(),
+            ..InputFields::default()
+        }];
+        assert!(assemble_transaction("2", "0", &bad_txid, &outputs).is_err());
+        assert!(assemble_transaction("two", "0", &inputs, &outputs).is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn psbt_roundtrip_and_key_spend() {
+        let secp = bitcoin::secp256k1::Secp256k1::new();
+        let keypair =
+            secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &[1; 32]).unwrap();
+        let bitcoin_keypair =
+            bitcoin::secp256k1::Keypair::from_seckey_slice(&secp, &[1; 32]).unwrap();
+        let (internal_key, _parity) = bitcoin_keypair.x_only_public_key();
+        let script_pubkey = ScriptBuf::new_p2tr(&secp, internal_key, None);
+
+        let inputs = [InputFields {
+            txid: "07".repeat(32),
+            ..InputFields::default()
+        }];
+        let outputs = [OutputFields {
+            value: "40000".to_string(),
+            script_pubkey: script_pubkey.to_hex_string(),
+        }];
+        let utxos = [OutputFields {
+            value: "50000".to_string(),
+            script_pubkey: script_pubkey.to_hex_string(),
+        }];
+        let tx = assemble_transaction("2", "0", &inputs, &outputs).unwrap();
+        let mut psbt = create_psbt(tx.clone(), &utxos).unwrap();
+        assert!(create_psbt(tx, &[]).is_err());
+
+        // Serialization is stable across a roundtrip
+        let bytes = psbt.serialize();
+        let decoded = Psbt::deserialize(&bytes).unwrap();
+        assert_eq!(psbt, decoded);
+        assert_eq!(bytes, decoded.serialize());
+
+        assert!(finalize_key_spends(&mut psbt.clone()).is_err());
+        sign_key_spend(&mut psbt, 0, &keypair).unwrap();
+        assert!(psbt.inputs[0].tap_key_sig.is_some());
+        let bytes = psbt.serialize();
+        assert_eq!(bytes, Psbt::deserialize(&bytes).unwrap().serialize());
+
+        let other_keypair =
+            secp256k1::Keypair::from_seckey_slice(secp256k1::SECP256K1, &[2; 32]).unwrap();
+        assert!(sign_key_spend(&mut psbt.clone(), 0, &other_keypair).is_err());
+        assert!(sign_key_spend(&mut psbt.clone(), 1, &keypair).is_err());
+
+        finalize_key_spends(&mut psbt).unwrap();
+        let signed = psbt.extract_tx_unchecked_fee_rate();
+        assert_eq!(1, signed.input[0].witness.len());
+        assert_eq!(64, signed.input[0].witness[0].len());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn derive_program_taproot() {
+        let cmr = simplicity::Cmr::unit();
+        let internal_key = util::unspendable_internal_key();
+        let untweaked = UntweakedPublicKey::from_slice(&internal_key.serialize()).unwrap();
+        let taproot = program_taproot(cmr, untweaked).unwrap();
+
+        let script = ScriptBuf::from_bytes(cmr.as_ref().to_vec());
+        let version = LeafVersion::from_consensus(SIMPLICITY_LEAF_VERSION).unwrap();
+        let leaf_hash = TapLeafHash::from_script(&script, version);
+        assert_eq!(leaf_hash, taproot.leaf_hash);
+        // The merkle root of a single leaf is its leaf hash
+        assert_eq!(TapNodeHash::from(leaf_hash), taproot.merkle_root);
+
+        let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+        let (output_key, _parity) = untweaked.tap_tweak(&secp, Some(taproot.merkle_root));
+        assert_eq!(output_key, taproot.output_key);
+        assert!(taproot.address.to_string().starts_with("bc1p"));
+    }
+}
@@ -0,0 +1,161 @@
+use leptos::{component, use_context, view, IntoView, Show, SignalGet, SignalWith};
+
+use crate::components::program_window::{Program, Runtime};
+use crate::function::ExecutionResources;
+use crate::util::spend_witness_weight;
+
+/// Maximum number of cells that the Bit Machine may allocate under consensus rules.
+const MAX_CELLS: u64 = 5 * 1024 * 1024;
+/// Weight that every program may cost on top of the weight of its witness.
+const BUDGET_OVERHEAD: usize = 50;
+
+/// Return the budget of a program under consensus rules, in milliweight.
+///
+/// A program may not cost more than the weight of the witness that spends it plus 50 WU.
+pub fn cost_budget(witness_weight: usize) -> u64 {
+    (witness_weight + BUDGET_OVERHEAD) as u64 * 1000
+}
+
+/// Usage of a resource relative to its limit.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum UsageLevel {
+    /// Below 50%.
+    Low,
+    /// From 50% to 90%.
+    Medium,
+    /// Above 90%.
+    High,
+}
+
+impl UsageLevel {
+    pub fn new(value: u64, max: u64) -> Self {
+        match value.saturating_mul(100) {
+            usage if usage < max.saturating_mul(50) => Self::Low,
+            usage if usage <= max.saturating_mul(90) => Self::Medium,
+            _ => Self::High,
+        }
+    }
+
+    fn class(self) -> &'static str {
+        match self {
+            Self::Low => "resource-low",
+            Self::Medium => "resource-medium",
+            Self::High => "resource-high",
+        }
+    }
+}
+
+#[component]
+pub fn ResourceMeter() -> impl IntoView {
+    let runtime = use_context::<Runtime>().expect("runtime should exist in context");
+    let program = use_context::<Program>().expect("program should exist in context");
+    // Budget of a leaf at the root of the tap tree, without padding the witness
+    let budget = move || {
+        let program_bytes = program.program_size.get()?.div_ceil(8);
+        let witness_bytes = program.metrics.with(|metrics| {
+            metrics
+                .as_ref()
+                .map(|metrics| metrics.witness_bits.div_ceil(8))
+        })?;
+        Some(cost_budget(spend_witness_weight(
+            program_bytes,
+            witness_bytes,
+            0,
+        )))
+    };
+    let resources_are_available = move || runtime.resources.with(Option::is_some);
+    let resource = move |get: fn(&ExecutionResources) -> u64| {
+        move || {
+            runtime
+                .resources
+                .with(|resources| resources.as_ref().map(get).unwrap_or(0))
+        }
+    };
+
+    view! {
+        <Show when=resources_are_available>
+            <details class="resource-meter">
+                <summary>"Resources"</summary>
+                <Show when=move || budget().is_some()>
+                    <ResourceRow
+                        label="Jet cost"
+                        unit="mWU"
+                        value=resource(|x| x.jet_cost)
+                        max=move || budget().unwrap_or_default()
+                    />
+                </Show>
+                <ResourceRow
+                    label="Cells"
+                    unit="bits"
+                    value=resource(|x| x.max_cells as u64)
+                    max=|| MAX_CELLS
+                />
+                <div class="execution-display-row">
+                    <div class="display-row-label">"Nodes run"</div>
+                    <div class="metric-value">
+                        {resource(|x| x.node_executions as u64)}
+                    </div>
+                </div>
+                <div class="execution-display-row">
+                    <div class="display-row-label">"Jet calls"</div>
+                    <div class="metric-value">{resource(|x| x.jet_calls as u64)}</div>
+                </div>
+            </details>
+        </Show>
+    }
+}
+
+#[component]
+fn ResourceRow<F, M>(label: &'static str, unit: &'static str, value: F, max: M) -> impl IntoView
+where
+    F: Fn() -> u64 + Copy + 'static,
+    M: Fn() -> u64 + Copy + 'static,
+{
+    let value_display = move || {
+        let percent = value() as f64 * 100.0 / max() as f64;
+        format!("{} / {} {unit} ({percent:.1}%)", value(), max())
+    };
+    let level_class = move || UsageLevel::new(value(), max()).class();
+
+    view! {
+        <div class="execution-display-row">
+            <div class="display-row-label">
+                {label}
+            </div>
+            <div class=move || format!("metric-value {}", level_class())>
+                {value_display}
+            </div>
+            <meter
+                min="0"
+                max=max
+                low=move || max() / 2
+                high=move || max() / 10 * 9
+                optimum="0"
+                value=value
+            />
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn usage_levels() {
+        assert_eq!(UsageLevel::Low, UsageLevel::new(0, 100));
+        assert_eq!(UsageLevel::Low, UsageLevel::new(49, 100));
+        assert_eq!(UsageLevel::Medium, UsageLevel::new(50, 100));
+        assert_eq!(UsageLevel::Medium, UsageLevel::new(90, 100));
+        assert_eq!(UsageLevel::High, UsageLevel::new(91, 100));
+        assert_eq!(UsageLevel::High, UsageLevel::new(u64::MAX, MAX_CELLS));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn budget_of_witness() {
+        assert_eq!(50_000, cost_budget(0));
+        assert_eq!(150_000, cost_budget(100));
+    }
+}
@@ -0,0 +1,178 @@
+use leptos::{
+    component, create_rw_signal, ev, event_target_checked, event_target_value, use_context, view,
+    For, IntoView, Show, Signal, SignalGet, SignalGetUntracked, SignalSet, SignalUpdate,
+    SignalWith, SignalWithUntracked,
+};
+
+use crate::components::diff_viewer::DiffViewer;
+use crate::components::program_window::Program;
+use crate::components::state::{delete_program, load_program, save_program, saved_program_names};
+use crate::components::string_box::ErrorBox;
+use crate::components::toast::{ToastLevel, Toasts};
+
+#[component]
+pub fn SavedProgramsPanel() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+    let names = create_rw_signal(saved_program_names());
+    let new_name = create_rw_signal("".to_string());
+    let save_error = create_rw_signal("".to_string());
+    // Names of the programs that are selected for comparison, oldest selection first
+    let selected = create_rw_signal(Vec::<String>::new());
+    // Names and sources of the compared programs
+    let comparison = create_rw_signal(None::<[(String, String); 2]>);
+
+    let update_new_name = move |event: ev::Event| new_name.set(event_target_value(&event));
+    let save_current_program = move |_event: ev::MouseEvent| {
+        let name = new_name.with(|name| name.trim().to_string());
+        if name.is_empty() {
+            save_error.set("Enter a name for the program".to_string());
+            return;
+        }
+        program
+            .text
+            .with_untracked(|text| save_program(&name, text));
+        names.set(saved_program_names());
+        new_name.set("".to_string());
+        save_error.set("".to_string());
+        toasts.add_toast(ToastLevel::Success, format!("Program \"{name}\" saved"));
+    };
+    let compare_error = create_rw_signal("".to_string());
+    let compare = move |_event: ev::MouseEvent| {
+        let sources = selected
+            .get_untracked()
+            .into_iter()
+            .map(|name| load_program(&name).map(|source| (name, source)))
+            .collect::<Option<Vec<_>>>();
+        match sources.and_then(|sources| <[(String, String); 2]>::try_from(sources).ok()) {
+            Some(sources) => {
+                comparison.set(Some(sources));
+                compare_error.update(String::clear);
+            }
+            None => {
+                comparison.set(None);
+                compare_error.set("Select two saved programs to compare".to_string());
+            }
+        }
+    };
+    let compared = move |index: usize| {
+        comparison.with(|comparison| {
+            comparison
+                .as_ref()
+                .map(|sources| sources[index].clone())
+                .unwrap_or_default()
+        })
+    };
+    let saved_program_row = move |name: String| {
+        let load_name = name.clone();
+        let delete_name = name.clone();
+        let load = move |_event: ev::MouseEvent| match load_program(&load_name) {
+            Some(source) => program.text.set(source),
+            None => {
+                toasts.add_toast(
+                    ToastLevel::Error,
+                    format!("Program \"{load_name}\" is no longer saved"),
+                );
+                names.set(saved_program_names());
+            }
+        };
+        let delete = move |_event: ev::MouseEvent| {
+            delete_program(&delete_name);
+            names.set(saved_program_names());
+            selected.update(|selected| selected.retain(|name| name != &delete_name));
+        };
+        let select_name = name.clone();
+        let checked_name = name.clone();
+        let toggle_selected = move |event: ev::Event| {
+            let name = select_name.clone();
+            selected.update(|selected| {
+                selected.retain(|selected_name| selected_name != &name);
+                if event_target_checked(&event) {
+                    if selected.len() == 2 {
+                        selected.remove(0);
+                    }
+                    selected.push(name);
+                }
+            });
+        };
+        let is_selected = move || selected.with(|selected| selected.contains(&checked_name));
+
+        view! {
+            <div class="saved-program-row">
+                <input
+                    type="checkbox"
+                    title="Select for comparison"
+                    prop:checked=is_selected
+                    on:change=toggle_selected
+                />
+                <div class="display-row-label">
+                    {name}
+                </div>
+                <div class="button-row is-small">
+                    <button class="flat-button bordered" type="button" on:click=load>
+                        <i class="fas fa-folder-open"></i>
+                        Load
+                    </button>
+                    <button class="flat-button bordered" type="button" on:click=delete>
+                        <i class="fas fa-trash"></i>
+                        Delete
+                    </button>
+                </div>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="tab-content saved-programs-tab">
+            <p class="tab-description">
+                "Programs are saved in the browser's local storage. "
+                "The program you are editing is saved automatically and restored when you come back."
+            </p>
+            <div class="saved-program-row">
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="Program name"
+                    prop:value=new_name
+                    on:input=update_new_name
+                />
+                <button class="flat-button bordered" type="button" on:click=save_current_program>
+                    <i class="fas fa-floppy-disk"></i>
+                    Save
+                </button>
+            </div>
+            <ErrorBox error=save_error />
+
+            <div class="tab-title-group">
+                <h3 class="tab-title">
+                    Saved Programs
+                </h3>
+            </div>
+            <For
+                each=move || names.get()
+                key=|name| name.clone()
+                children=saved_program_row
+            />
+            <div class="button-row">
+                <button
+                    class="flat-button bordered"
+                    type="button"
+                    disabled=move || selected.with(|selected| selected.len() != 2)
+                    on:click=compare
+                >
+                    <i class="fas fa-code-compare"></i>
+                    Compare
+                </button>
+            </div>
+            <ErrorBox error=compare_error />
+            <Show when=move || comparison.with(Option::is_some)>
+                <DiffViewer
+                    old_title=Signal::derive(move || compared(0).0)
+                    old=Signal::derive(move || compared(0).1)
+                    new_title=Signal::derive(move || compared(1).0)
+                    new=Signal::derive(move || compared(1).1)
+                />
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use leptos::{
+    component, create_rw_signal, ev, event_target_value, use_context, view, For, IntoView, Show,
+    Signal, SignalGet, SignalSet, SignalUpdate, SignalWith, SignalWithUntracked,
+};
+
+use super::fuzzer_tab::{compile_program, run_with_witness};
+use super::TxEnv;
+use crate::components::program_window::Program;
+use crate::components::state::{load_test_cases, store_test_cases};
+use crate::components::string_box::{ErrorBox, NeutralBox};
+use crate::components::toast::{ToastLevel, Toasts};
+use crate::util::{strip_witness_module, witness_module};
+
+/// Run the current program with each of the given witnesses.
+fn run_test_cases<'a>(
+    program: Program,
+    tx_env: TxEnv,
+    cases: impl IntoIterator<Item = (&'a String, &'a String)>,
+) -> Vec<(String, Result<(), String>)> {
+    let text = program.text.get_untracked();
+    let compiled = compile_program(&text);
+    let program_text = strip_witness_module(&text);
+
+    cases
+        .into_iter()
+        .map(|(name, witness)| {
+            let result = compiled
+                .as_ref()
+                .map_err(Clone::clone)
+                .and_then(|compiled| {
+                    tx_env.lazy_env.with_untracked(|env| {
+                        run_with_witness(compiled, &program_text, witness, env)
+                    })
+                });
+            (name.clone(), result)
+        })
+        .collect()
+}
+
+#[component]
+pub fn TestCasesPanel() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let toasts = use_context::<Toasts>().expect("toasts should exist in context");
+    let store = create_rw_signal(load_test_cases());
+    let results = create_rw_signal(BTreeMap::<String, Result<(), String>>::new());
+    let new_name = create_rw_signal("".to_string());
+    let save_error = create_rw_signal("".to_string());
+
+    let update_new_name = move |event: ev::Event| new_name.set(event_target_value(&event));
+    let save_test_case = move |_event: ev::MouseEvent| {
+        let name = new_name.with(|name| name.trim().to_string());
+        if name.is_empty() {
+            save_error.set("Enter a name for the test case".to_string());
+            return;
+        }
+        let witness = program
+            .text
+            .with_untracked(|text| witness_module(text).unwrap_or("mod witness {}").to_string());
+        store.update(|store| store.insert(name.clone(), witness));
+        store.with_untracked(store_test_cases);
+        results.update(|results| {
+            results.remove(&name);
+        });
+        new_name.set("".to_string());
+        save_error.set("".to_string());
+        toasts.add_toast(ToastLevel::Success, format!("Test case \"{name}\" saved"));
+    };
+    let run_all = move |_event: ev::MouseEvent| {
+        let ran = store.with_untracked(|store| {
+            run_test_cases(
+                program,
+                tx_env,
+                store.cases().iter().map(|(name, witness)| (name, witness)),
+            )
+        });
+        results.set(ran.into_iter().collect());
+    };
+    let summary = Signal::derive(move || {
+        results.with(|results| match results.is_empty() {
+            true => String::new(),
+            false => {
+                let passed = results.values().filter(|result| result.is_ok()).count();
+                let failed = results.len() - passed;
+                format!("{passed} passed, {failed} failed")
+            }
+        })
+    });
+    let failures = Signal::derive(move || {
+        results.with(|results| {
+            results
+                .iter()
+                .filter_map(|(name, result)| {
+                    result
+                        .as_ref()
+                        .err()
+                        .map(|error| format!("{name}: {error}"))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    });
+    let test_case_row = move |name: String| {
+        let run_name = name.clone();
+        let delete_name = name.clone();
+        let status_name = name.clone();
+        let run = move |_event: ev::MouseEvent| {
+            let ran = store.with_untracked(|store| {
+                store
+                    .get(&run_name)
+                    .map(|witness| run_test_cases(program, tx_env, [(&run_name, witness)]))
+                    .unwrap_or_default()
+            });
+            results.update(|results| results.extend(ran));
+        };
+        let delete = move |_event: ev::MouseEvent| {
+            store.update(|store| store.remove(&delete_name));
+            store.with_untracked(store_test_cases);
+            results.update(|results| {
+                results.remove(&delete_name);
+            });
+        };
+        let status = move || {
+            results.with(|results| match results.get(&status_name) {
+                Some(Ok(..)) => view! { <i class="fas fa-check test-passed"></i> }.into_view(),
+                Some(Err(error)) => view! {
+                    <i class="fas fa-xmark test-failed" title=error.clone()></i>
+                }
+                .into_view(),
+                None => ().into_view(),
+            })
+        };
+
+        view! {
+            <div class="test-case-row">
+                <div class="display-row-label">
+                    {status}
+                    " "
+                    {name}
+                </div>
+                <div class="button-row is-small">
+                    <button class="flat-button bordered" type="button" on:click=run>
+                        <i class="fas fa-play"></i>
+                        Run
+                    </button>
+                    <button class="flat-button bordered" type="button" on:click=delete>
+                        <i class="fas fa-trash"></i>
+                        Delete
+                    </button>
+                </div>
+            </div>
+        }
+    };
+
+    view! {
+        <div class="test-cases-panel">
+            <div class="tab-title-group">
+                <h3 class="tab-title">
+                    Tests
+                </h3>
+            </div>
+            <p class="tab-description">
+                "Save the witness module of the program as a named test case and replay it after editing the program."
+            </p>
+            <div class="test-case-row">
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="Test name"
+                    prop:value=new_name
+                    on:input=update_new_name
+                />
+                <button class="flat-button bordered" type="button" on:click=save_test_case>
+                    <i class="fas fa-floppy-disk"></i>
+                    "Save as test"
+                </button>
+            </div>
+            <ErrorBox error=save_error />
+            <Show when=move || store.with(|store| !store.cases().is_empty())>
+                <For
+                    each=move || {
+                        store.with(|store| {
+                            store.cases().iter().map(|(name, _)| name.clone()).collect::<Vec<_>>()
+                        })
+                    }
+                    key=|name| name.clone()
+                    children=test_case_row
+                />
+                <div class="button-row">
+                    <button class="flat-button bordered" type="button" on:click=run_all>
+                        <i class="fas fa-play"></i>
+                        "Run all"
+                    </button>
+                </div>
+            </Show>
+            <NeutralBox neutral=summary />
+            <ErrorBox error=failures />
+        </div>
+    }
+}
@@ -3,7 +3,7 @@ use std::sync::Arc;
 
 use leptos::{
     component, create_rw_signal, ev, event_target_value, use_context, view, with, Children,
-    IntoView, RwSignal, Signal, SignalGetUntracked, SignalSet, SignalUpdate,
+    IntoView, RwSignal, Signal, SignalSet, SignalUpdate, SignalWith,
 };
 use simfony::{elements, simplicity};
 use simplicity::jet::elements::ElementsEnv;
@@ -35,6 +35,7 @@ impl TxEnv {
 #[component]
 pub fn TransactionTab() -> impl IntoView {
     let tx_env = use_context::<TxEnv>().expect("transaction environment should exist in context");
+    let params = tx_env.params;
     let txid_parse_error = create_rw_signal("".to_string());
     let vout_parse_error = create_rw_signal("".to_string());
     let value_in_parse_error = create_rw_signal("".to_string());
@@ -45,21 +46,21 @@ pub fn TransactionTab() -> impl IntoView {
 
     let update_txid = move |e: ev::Event| match elements::Txid::from_str(&event_target_value(&e)) {
         Ok(txid) => {
-            tx_env.params.update(|x| x.txid = txid);
+            params.update(|x| x.txid = txid);
             txid_parse_error.update(String::clear);
         }
         Err(error) => txid_parse_error.set(error.to_string()),
     };
     let update_vout = move |e: ev::Event| match event_target_value(&e).parse::<u32>() {
         Ok(vout) => {
-            tx_env.params.update(|x| x.vout = vout);
+            params.update(|x| x.vout = vout);
             vout_parse_error.update(String::clear);
         }
         Err(error) => vout_parse_error.set(error.to_string()),
     };
     let update_value_in = move |e: ev::Event| match event_target_value(&e).parse::<u64>() {
         Ok(value_in) => {
-            tx_env.params.update(|x| x.value_in = value_in);
+            params.update(|x| x.value_in = value_in);
             value_in_parse_error.update(String::clear);
         }
         Err(error) => value_in_parse_error.set(error.to_string()),
@@ -68,13 +69,11 @@ pub fn TransactionTab() -> impl IntoView {
         let s = event_target_value(&e);
         match elements::Address::parse_with_params(&s, &elements::AddressParams::LIQUID_TESTNET) {
             Ok(address) => {
-                tx_env
-                    .params
-                    .update(|x| x.recipient_address = Some(address));
+                params.update(|x| x.recipient_address = Some(address));
                 recipient_address_parse_error.update(String::clear);
             }
             Err(..) if s.is_empty() => {
-                tx_env.params.update(|x| x.recipient_address = None);
+                params.update(|x| x.recipient_address = None);
                 recipient_address_parse_error.update(String::clear);
             }
             Err(error) => recipient_address_parse_error.set(error.to_string()),
@@ -82,7 +81,7 @@ pub fn TransactionTab() -> impl IntoView {
     };
     let update_fee = move |e: ev::Event| match event_target_value(&e).parse::<u64>() {
         Ok(fee) => {
-            tx_env.params.update(|x| x.fee = fee);
+            params.update(|x| x.fee = fee);
             fee_parse_error.update(String::clear);
         }
         Err(error) => fee_parse_error.set(error.to_string()),
@@ -90,7 +89,7 @@ pub fn TransactionTab() -> impl IntoView {
     let update_lock_time = move |e: ev::Event| match event_target_value(&e).parse::<u32>() {
         Ok(lock_time) => {
             let lock_time = elements::LockTime::from_consensus(lock_time);
-            tx_env.params.update(|x| x.lock_time = lock_time);
+            params.update(|x| x.lock_time = lock_time);
             lock_time_parse_error.update(String::clear);
         }
         Err(error) => lock_time_parse_error.set(error.to_string()),
@@ -98,7 +97,7 @@ pub fn TransactionTab() -> impl IntoView {
     let update_sequence = move |e: ev::Event| match event_target_value(&e).parse::<u32>() {
         Ok(sequence) => {
             let sequence = elements::Sequence::from_consensus(sequence);
-            tx_env.params.update(|x| x.sequence = sequence);
+            params.update(|x| x.sequence = sequence);
             sequence_parse_error.update(String::clear);
         }
         Err(error) => sequence_parse_error.set(error.to_string()),
@@ -116,7 +115,7 @@ pub fn TransactionTab() -> impl IntoView {
                         class="input"
                         type="text"
                         on:input=update_txid
-                        value=tx_env.params.get_untracked().txid.to_string()
+                        prop:value=move || params.with(|x| x.txid.to_string())
                     />
                 </Item>
                 <Item name="vout" error=vout_parse_error>
@@ -125,7 +124,7 @@ pub fn TransactionTab() -> impl IntoView {
                         type="number"
                         min=0
                         on:input=update_vout
-                        value=tx_env.params.get_untracked().vout
+                        prop:value=move || params.with(|x| x.vout.to_string())
                     />
                 </Item>
                 <Item name="value (sats)" error=value_in_parse_error>
@@ -134,7 +133,7 @@ pub fn TransactionTab() -> impl IntoView {
                         type="number"
                         min=0
                         on:input=update_value_in
-                        value=tx_env.params.get_untracked().value_in
+                        prop:value=move || params.with(|x| x.value_in.to_string())
                     />
                 </Item>
             </Section>
@@ -144,7 +143,15 @@ pub fn TransactionTab() -> impl IntoView {
                         class="input"
                         type="text"
                         on:input=update_recipient_address
-                        value=tx_env.params.get_untracked().recipient_address.as_ref().map(ToString::to_string).unwrap_or_default()
+                        prop:value=move || {
+                            params
+                                .with(|x| {
+                                    x.recipient_address
+                                        .as_ref()
+                                        .map(ToString::to_string)
+                                        .unwrap_or_default()
+                                })
+                        }
                         placeholder="(Send back to faucet)"
                     />
                 </Item>
@@ -154,8 +161,7 @@ pub fn TransactionTab() -> impl IntoView {
                         type="number"
                         on:input=update_fee
                         min=0
-                        value=10000
-                        value=tx_env.params.get_untracked().fee
+                        prop:value=move || params.with(|x| x.fee.to_string())
                     />
                 </Item>
                 <Item name="nLockTime" error=lock_time_parse_error>
@@ -164,7 +170,9 @@ pub fn TransactionTab() -> impl IntoView {
                         type="number"
                         on:input=update_lock_time
                         min=0
-                        value=tx_env.params.get_untracked().lock_time.to_string()
+                        prop:value=move || {
+                            params.with(|x| x.lock_time.to_consensus_u32().to_string())
+                        }
                     />
                 </Item>
                 <Item name="nSequence" error=sequence_parse_error>
@@ -173,7 +181,9 @@ pub fn TransactionTab() -> impl IntoView {
                         type="number"
                         on:input=update_sequence
                         min=0
-                        value=tx_env.params.get_untracked().sequence.to_string()
+                        prop:value=move || {
+                            params.with(|x| x.sequence.to_consensus_u32().to_string())
+                        }
                     />
                 </Item>
             </Section>
@@ -0,0 +1,722 @@
+use leptos::{
+    component, create_effect, create_memo, create_rw_signal, ev, event_target_value, use_context,
+    view, Callback, CollectView, IntoView, RwSignal, Show, Signal, SignalGet, SignalGetUntracked,
+    SignalSet, SignalUpdate, SignalWith, SignalWithUntracked, View,
+};
+
+use simfony::parse::ParseFromStr;
+use simfony::simplicity;
+use simfony::str::WitnessName;
+use simfony::value::StructuralValue;
+use simfony::WitnessValues;
+
+use crate::components::program_window::Program;
+use crate::components::string_box::ErrorBox;
+use crate::components::value_tree::ValueTree;
+use crate::util::{replace_witness_module, replace_witness_value, witness_module};
+
+/// Type of a witness, as far as the witness form can build values of it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WitnessType {
+    Unit,
+    Bool,
+    /// Unsigned integer of the given bit width.
+    UInt(usize),
+    /// Array of the given number of bytes.
+    Bytes(usize),
+    Either(Box<WitnessType>, Box<WitnessType>),
+    Option(Box<WitnessType>),
+    Tuple(Vec<WitnessType>),
+    Array(Box<WitnessType>, usize),
+}
+
+/// Resolve the given type alias of Simfony.
+fn alias(name: &str) -> Option<WitnessType> {
+    let ty = match name {
+        "Pubkey" | "Message" | "Ctx8" => WitnessType::UInt(256),
+        "Signature" | "Message64" => WitnessType::Bytes(64),
+        "Height" | "Time" | "Lock" => WitnessType::UInt(32),
+        "Distance" | "Duration" => WitnessType::UInt(16),
+        _ => return None,
+    };
+    Some(ty)
+}
+
+/// Parse a Simfony type.
+///
+/// Integers, `bool`, `()`, `Either`, `Option`, tuples, arrays and common aliases are supported.
+pub fn parse_witness_type(s: &str) -> Result<WitnessType, String> {
+    let tokens = tokenize(s);
+    let mut position = 0;
+    let ty = parse_type(&tokens, &mut position)?;
+    match tokens.get(position) {
+        None => Ok(ty),
+        Some(token) => Err(format!("Unexpected `{token}` in type `{s}`")),
+    }
+}
+
+fn tokenize(s: &str) -> Vec<&str> {
+    let mut tokens = vec![];
+    let mut rest = s.trim_start();
+    while let Some(c) = rest.chars().next() {
+        let len = match c.is_ascii_alphanumeric() || c == '_' {
+            true => rest
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                .unwrap_or(rest.len()),
+            false => c.len_utf8(),
+        };
+        tokens.push(&rest[..len]);
+        rest = rest[len..].trim_start();
+    }
+    tokens
+}
+
+fn expect(tokens: &[&str], position: &mut usize, expected: &str) -> Result<(), String> {
+    match tokens.get(*position) {
+        Some(token) if *token == expected => {
+            *position += 1;
+            Ok(())
+        }
+        Some(token) => Err(format!("Expected `{expected}` but found `{token}`")),
+        None => Err(format!("Expected `{expected}` but found end of type")),
+    }
+}
+
+fn parse_type(tokens: &[&str], position: &mut usize) -> Result<WitnessType, String> {
+    let token = *tokens
+        .get(*position)
+        .ok_or_else(|| "Unexpected end of type".to_string())?;
+    *position += 1;
+    match token {
+        "bool" => Ok(WitnessType::Bool),
+        "Either" => {
+            expect(tokens, position, "<")?;
+            let left = parse_type(tokens, position)?;
+            expect(tokens, position, ",")?;
+            let right = parse_type(tokens, position)?;
+            expect(tokens, position, ">")?;
+            Ok(WitnessType::Either(Box::new(left), Box::new(right)))
+        }
+        "Option" => {
+            expect(tokens, position, "<")?;
+            let inner = parse_type(tokens, position)?;
+            expect(tokens, position, ">")?;
+            Ok(WitnessType::Option(Box::new(inner)))
+        }
+        "(" => {
+            let mut elements = vec![];
+            while tokens.get(*position) != Some(&")") {
+                elements.push(parse_type(tokens, position)?);
+                if tokens.get(*position) == Some(&",") {
+                    *position += 1;
+                } else {
+                    break;
+                }
+            }
+            expect(tokens, position, ")")?;
+            match elements.is_empty() {
+                true => Ok(WitnessType::Unit),
+                false => Ok(WitnessType::Tuple(elements)),
+            }
+        }
+        "[" => {
+            let element = parse_type(tokens, position)?;
+            expect(tokens, position, ";")?;
+            let size = tokens
+                .get(*position)
+                .and_then(|size| size.parse::<usize>().ok())
+                .ok_or_else(|| "Expected array size".to_string())?;
+            *position += 1;
+            expect(tokens, position, "]")?;
+            match element {
+                WitnessType::UInt(8) => Ok(WitnessType::Bytes(size)),
+                _ => Ok(WitnessType::Array(Box::new(element), size)),
+            }
+        }
+        _ => match token
+            .strip_prefix('u')
+            .and_then(|bits| bits.parse::<usize>().ok())
+        {
+            Some(bits @ (1 | 2 | 4 | 8 | 16 | 32 | 64 | 128 | 256)) => Ok(WitnessType::UInt(bits)),
+            _ => alias(token).ok_or_else(|| format!("Unsupported type `{token}`")),
+        },
+    }
+}
+
+/// Parse the `const NAME: TYPE = VALUE;` declarations of the witness module of the program text.
+///
+/// Return the names and the type text of the witnesses.
+pub fn witness_declarations(text: &str) -> Vec<(String, String)> {
+    let Some(module) = witness_module(text) else {
+        return vec![];
+    };
+    let body = module
+        .find('{')
+        .map(|start| &module[start + 1..module.len() - 1])
+        .unwrap_or_default();
+    body.split(';')
+        .filter_map(|declaration| {
+            let declaration = declaration.trim().strip_prefix("const")?;
+            let (name, rest) = declaration.split_once(':')?;
+            let (ty, _) = rest.split_once('=')?;
+            Some((name.trim().to_string(), ty.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Value of the witness form, with the same shape as its type.
+#[derive(Clone, Debug)]
+pub enum FormValue {
+    Unit,
+    /// Value of a boolean or of a single bit.
+    Bit(RwSignal<bool>),
+    /// Text of a small integer in decimal or of a larger integer or byte array in hex.
+    Text(RwSignal<String>),
+    /// Value of `Either` or `Option`, where `Option` has a unit on the left.
+    Choice {
+        is_right: RwSignal<bool>,
+        left: Box<FormValue>,
+        right: Box<FormValue>,
+    },
+    /// Values of the elements of a tuple or array.
+    List(Vec<FormValue>),
+}
+
+impl FormValue {
+    /// Create the default value of the given type, which is zero or left.
+    pub fn new(ty: &WitnessType) -> Self {
+        match ty {
+            WitnessType::Unit => FormValue::Unit,
+            WitnessType::Bool | WitnessType::UInt(1) => FormValue::Bit(create_rw_signal(false)),
+            WitnessType::UInt(bits) if *bits < 8 => {
+                FormValue::Text(create_rw_signal("0".to_string()))
+            }
+            WitnessType::UInt(bits) => FormValue::Text(create_rw_signal("0".repeat(bits / 4))),
+            WitnessType::Bytes(len) => FormValue::Text(create_rw_signal("00".repeat(*len))),
+            WitnessType::Either(left, right) => FormValue::Choice {
+                is_right: create_rw_signal(false),
+                left: Box::new(FormValue::new(left)),
+                right: Box::new(FormValue::new(right)),
+            },
+            WitnessType::Option(inner) => FormValue::Choice {
+                is_right: create_rw_signal(false),
+                left: Box::new(FormValue::Unit),
+                right: Box::new(FormValue::new(inner)),
+            },
+            WitnessType::Tuple(elements) => {
+                FormValue::List(elements.iter().map(FormValue::new).collect())
+            }
+            WitnessType::Array(element, size) => {
+                FormValue::List((0..*size).map(|_| FormValue::new(element)).collect())
+            }
+        }
+    }
+
+    /// Convert the value of the given type into a Simfony literal.
+    pub fn to_literal(&self, ty: &WitnessType) -> Result<String, String> {
+        match (ty, self) {
+            (WitnessType::Unit, _) => Ok("()".to_string()),
+            (WitnessType::Bool, FormValue::Bit(bit)) => Ok(bit.get_untracked().to_string()),
+            (WitnessType::UInt(1), FormValue::Bit(bit)) => {
+                Ok(u8::from(bit.get_untracked()).to_string())
+            }
+            (WitnessType::UInt(bits), FormValue::Text(text)) if *bits < 8 => {
+                let text = text.get_untracked();
+                match text.trim().parse::<u8>() {
+                    Ok(value) if value < 1 << bits => Ok(value.to_string()),
+                    _ => Err(format!("`{text}` is not a u{bits}")),
+                }
+            }
+            (WitnessType::UInt(bits), FormValue::Text(text)) => hex_literal(text, bits / 4),
+            (WitnessType::Bytes(len), FormValue::Text(text)) => hex_literal(text, len * 2),
+            (
+                WitnessType::Either(left_type, right_type),
+                FormValue::Choice {
+                    is_right,
+                    left,
+                    right,
+                },
+            ) => match is_right.get_untracked() {
+                false => Ok(format!("Left({})", left.to_literal(left_type)?)),
+                true => Ok(format!("Right({})", right.to_literal(right_type)?)),
+            },
+            (
+                WitnessType::Option(inner),
+                FormValue::Choice {
+                    is_right, right, ..
+                },
+            ) => match is_right.get_untracked() {
+                false => Ok("None".to_string()),
+                true => Ok(format!("Some({})", right.to_literal(inner)?)),
+            },
+            (WitnessType::Tuple(elements), FormValue::List(values)) => {
+                let literals = literals(elements.iter(), values)?;
+                match literals.len() {
+                    1 => Ok(format!("({},)", literals[0])),
+                    _ => Ok(format!("({})", literals.join(", "))),
+                }
+            }
+            (WitnessType::Array(element, _), FormValue::List(values)) => {
+                let literals = literals(std::iter::repeat(element.as_ref()), values)?;
+                Ok(format!("[{}]", literals.join(", ")))
+            }
+            _ => Err("Form value does not match its type".to_string()),
+        }
+    }
+}
+
+fn literals<'a>(
+    types: impl Iterator<Item = &'a WitnessType>,
+    values: &[FormValue],
+) -> Result<Vec<String>, String> {
+    types
+        .zip(values)
+        .map(|(ty, value)| value.to_literal(ty))
+        .collect()
+}
+
+/// Convert the hex text into a hex literal of the given number of digits.
+fn hex_literal(text: &RwSignal<String>, digits: usize) -> Result<String, String> {
+    let text = text.get_untracked();
+    let hex = text.trim().trim_start_matches("0x");
+    if hex.len() != digits || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("`{text}` is not {digits} hex digits"));
+    }
+    Ok(format!("0x{hex}"))
+}
+
+/// Witness with its declared type and the value of the form.
+#[derive(Clone, Debug)]
+struct WitnessEntry {
+    name: String,
+    type_text: String,
+    ty: Result<WitnessType, String>,
+    value: Option<FormValue>,
+}
+
+/// Serialize the witness entries as witness module.
+fn witness_module_text(entries: &[WitnessEntry]) -> Result<String, String> {
+    let mut text = "mod witness {\n".to_string();
+    for entry in entries {
+        let ty = entry.ty.as_ref().map_err(Clone::clone)?;
+        let value = entry.value.as_ref().expect("supported types have a value");
+        let literal = value
+            .to_literal(ty)
+            .map_err(|error| format!("{}: {error}", entry.name))?;
+        text.push_str(&format!(
+            "    const {}: {} = {literal};\n",
+            entry.name, entry.type_text
+        ));
+    }
+    text.push('}');
+    Ok(text)
+}
+
+#[component]
+pub fn WitnessForm() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let declarations = create_memo(move |_| program.text.with(|text| witness_declarations(text)));
+    let error = create_rw_signal(String::new());
+    let entries = create_rw_signal(Vec::<WitnessEntry>::new());
+    // Rebuild the form only if the declared witnesses change
+    create_effect(move |_| {
+        let new_entries = declarations.with(|declarations| {
+            declarations
+                .iter()
+                .map(|(name, type_text)| {
+                    let ty = parse_witness_type(type_text);
+                    let value = ty.as_ref().ok().map(FormValue::new);
+                    WitnessEntry {
+                        name: name.clone(),
+                        type_text: type_text.clone(),
+                        ty,
+                        value,
+                    }
+                })
+                .collect()
+        });
+        entries.set(new_entries);
+    });
+
+    let apply = move |_event: ev::MouseEvent| match entries
+        .with_untracked(|entries| witness_module_text(entries))
+    {
+        Ok(module) => {
+            program
+                .text
+                .update(|text| *text = replace_witness_module(text, &module));
+            error.update(String::clear);
+        }
+        Err(apply_error) => error.set(apply_error),
+    };
+    let form_rows = move || {
+        entries
+            .get()
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let input = match (&entry.ty, &entry.value) {
+                    (Ok(ty), Some(value)) => value_form(ty, value, format!("witness-{index}")),
+                    (Err(error), _) => {
+                        view! { <span class="form-error">{error.clone()}</span> }.into_view()
+                    }
+                    _ => ().into_view(),
+                };
+                view! {
+                    <div class="execution-display-row">
+                        <div class="display-row-label" title=entry.type_text.clone()>
+                            {entry.name}
+                        </div>
+                        {input}
+                    </div>
+                }
+            })
+            .collect_view()
+    };
+
+    view! {
+        <Show when=move || declarations.with(|declarations| !declarations.is_empty())>
+            <details class="witness-form">
+                <summary>"Witness form"</summary>
+                {form_rows}
+                <button class="flat-button bordered" type="button" on:click=apply>
+                    "Apply to program"
+                </button>
+                <ErrorBox error=error />
+            </details>
+        </Show>
+    }
+}
+
+/// Return the value of the witness with the given name in the program text.
+fn witness_value(text: &str, name: &str) -> Option<simfony::value::Value> {
+    let values = WitnessValues::parse_from_str(text).ok()?;
+    values.get(&WitnessName::from_str_unchecked(name)).cloned()
+}
+
+/// Editable trees of the witness values of the program.
+///
+/// Edited values are checked against the declared type of the witness
+/// before they replace the value in the witness module.
+#[component]
+pub fn WitnessTrees() -> impl IntoView {
+    let program = use_context::<Program>().expect("program should exist in context");
+    let declarations = create_memo(move |_| program.text.with(|text| witness_declarations(text)));
+    let trees = move || {
+        declarations.with(|declarations| {
+            declarations
+                .iter()
+                .map(|(name, _)| witness_tree(program, name.clone()))
+                .collect_view()
+        })
+    };
+
+    view! {
+        <Show when=move || declarations.with(|declarations| !declarations.is_empty())>
+            <details class="witness-trees">
+                <summary>"Witness values"</summary>
+                <p class="tab-description">
+                    "Click on bits or bytes to edit them. Press Enter to apply or Escape to cancel."
+                </p>
+                {trees}
+            </details>
+        </Show>
+    }
+}
+
+fn witness_tree(program: Program, name: String) -> View {
+    let witness_name = name.clone();
+    let witness =
+        Signal::derive(move || program.text.with(|text| witness_value(text, &witness_name)));
+    let value = Signal::derive(move || {
+        witness.with(|witness| {
+            witness
+                .as_ref()
+                .map(|witness| simplicity::Value::from(StructuralValue::from(witness)))
+                .unwrap_or_else(simplicity::Value::unit)
+        })
+    });
+    let label = name.clone();
+    let on_edit = Callback::new(move |edited: simplicity::Value| -> Result<(), String> {
+        let ty = witness
+            .with_untracked(|witness| witness.as_ref().map(|witness| witness.ty().clone()))
+            .ok_or_else(|| format!("The value of `{name}` does not parse"))?;
+        let literal = simfony::value::Value::reconstruct(&StructuralValue::from(edited), &ty)
+            .ok_or_else(|| format!("The edited value does not have the declared type `{ty}`"))?;
+        let text = program
+            .text
+            .with_untracked(|text| replace_witness_value(text, &name, &literal.to_string()))
+            .ok_or_else(|| format!("The witness module declares no `{name}`"))?;
+        program.text.set(text);
+        Ok(())
+    });
+
+    view! {
+        <div class="execution-display-row">
+            <div class="display-row-label">{label}</div>
+            <Show
+                when=move || witness.with(Option::is_some)
+                fallback=|| view! { <span class="form-error">"Invalid value"</span> }
+            >
+                <ValueTree value=value on_edit=on_edit />
+            </Show>
+        </div>
+    }
+    .into_view()
+}
+
+/// Render the form for a value of the given type.
+///
+/// The name identifies the radio buttons of sums.
+fn value_form(ty: &WitnessType, value: &FormValue, name: String) -> View {
+    match (ty, value) {
+        (_, FormValue::Unit) => ().into_view(),
+        (_, FormValue::Bit(bit)) => {
+            let bit = *bit;
+            let toggle = move |_event: ev::MouseEvent| bit.update(|bit| *bit = !*bit);
+            view! {
+                <button class="flat-button bordered bit-toggle" type="button" on:click=toggle>
+                    {move || match bit.get() {
+                        true => "1",
+                        false => "0",
+                    }}
+                </button>
+            }
+            .into_view()
+        }
+        (_, FormValue::Text(text)) => {
+            let text = *text;
+            let placeholder = match ty {
+                WitnessType::UInt(bits) if *bits < 8 => "decimal".to_string(),
+                WitnessType::UInt(bits) => format!("{} hex digits", bits / 4),
+                WitnessType::Bytes(len) => format!("{} hex digits", len * 2),
+                _ => String::new(),
+            };
+            let update_text = move |event: ev::Event| text.set(event_target_value(&event));
+            let slider = match ty {
+                WitnessType::UInt(bits @ (8 | 16 | 32 | 64)) => Some(word_slider(text, *bits)),
+                _ => None,
+            };
+            view! {
+                <input
+                    class="input"
+                    type="text"
+                    spellcheck="false"
+                    placeholder=placeholder
+                    prop:value=text
+                    on:input=update_text
+                />
+                {slider}
+            }
+            .into_view()
+        }
+        (
+            _,
+            FormValue::Choice {
+                is_right,
+                left,
+                right,
+            },
+        ) => {
+            let is_right = *is_right;
+            let (labels, left_form, right_form) = match ty {
+                WitnessType::Either(left_type, right_type) => (
+                    ["Left", "Right"],
+                    value_form(left_type, left, format!("{name}-l")),
+                    value_form(right_type, right, format!("{name}-r")),
+                ),
+                WitnessType::Option(inner) => (
+                    ["None", "Some"],
+                    ().into_view(),
+                    value_form(inner, right, format!("{name}-r")),
+                ),
+                _ => return ().into_view(),
+            };
+            let select_left = move |_event: ev::Event| is_right.set(false);
+            let select_right = move |_event: ev::Event| is_right.set(true);
+            view! {
+                <div class="form-sum">
+                    <label>
+                        <input
+                            type="radio"
+                            name=name.clone()
+                            prop:checked=move || !is_right.get()
+                            on:change=select_left
+                        />
+                        {labels[0]}
+                    </label>
+                    <label>
+                        <input
+                            type="radio"
+                            name=name
+                            prop:checked=is_right
+                            on:change=select_right
+                        />
+                        {labels[1]}
+                    </label>
+                    <div class="form-sum-value">
+                        <Show when=move || !is_right.get()>{left_form.clone()}</Show>
+                        <Show when=move || is_right.get()>{right_form.clone()}</Show>
+                    </div>
+                </div>
+            }
+            .into_view()
+        }
+        (_, FormValue::List(values)) => {
+            let types: Vec<&WitnessType> = match ty {
+                WitnessType::Tuple(elements) => elements.iter().collect(),
+                WitnessType::Array(element, _) => vec![element.as_ref(); values.len()],
+                _ => return ().into_view(),
+            };
+            let forms = types
+                .into_iter()
+                .zip(values)
+                .enumerate()
+                .map(|(index, (ty, value))| value_form(ty, value, format!("{name}-{index}")))
+                .map(|form| view! { <div class="form-product-element">{form}</div> })
+                .collect_view();
+            view! { <div class="form-product">{forms}</div> }.into_view()
+        }
+    }
+}
+
+/// Parse the hex text of a word of the given bit width.
+fn hex_to_word(text: &str, bits: usize) -> Option<u64> {
+    let hex = text.trim().trim_start_matches("0x");
+    match hex.len() == bits / 4 {
+        true => u64::from_str_radix(hex, 16).ok(),
+        false => None,
+    }
+}
+
+/// Format the word as hex text of the given bit width.
+fn word_to_hex(word: u64, bits: usize) -> String {
+    format!("{word:0width$x}", width = bits / 4)
+}
+
+/// Render a slider from 0 to the maximum word of the given bit width
+/// that stays in sync with the hex text of the word.
+///
+/// Sliders of 64-bit words are only as precise as floating-point numbers.
+fn word_slider(text: RwSignal<String>, bits: usize) -> View {
+    let max = u64::MAX >> (64 - bits);
+    let word = move || text.with(|text| hex_to_word(text, bits));
+    let update_word = move |event: ev::Event| {
+        let value = event_target_value(&event);
+        let word = value
+            .parse::<u64>()
+            .ok()
+            .or_else(|| value.parse::<f64>().ok().map(|word| word as u64))
+            .unwrap_or(0)
+            .min(max);
+        text.set(word_to_hex(word, bits));
+    };
+
+    view! {
+        <input
+            class="word-slider"
+            type="range"
+            min="0"
+            max=max.to_string()
+            step="1"
+            prop:value=move || word().unwrap_or(0).to_string()
+            on:input=update_word
+        />
+        <span class="word-value">
+            {move || word().map(|word| word.to_string()).unwrap_or_default()}
+        </span>
+    }
+    .into_view()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_types() {
+        assert_eq!(Ok(WitnessType::UInt(8)), parse_witness_type("u8"));
+        assert_eq!(Ok(WitnessType::Bytes(64)), parse_witness_type("Signature"));
+        assert_eq!(Ok(WitnessType::Bytes(3)), parse_witness_type("[u8; 3]"));
+        assert_eq!(
+            Ok(WitnessType::Either(
+                Box::new(WitnessType::Tuple(vec![
+                    WitnessType::UInt(256),
+                    WitnessType::Bool
+                ])),
+                Box::new(WitnessType::Option(Box::new(WitnessType::Unit)))
+            )),
+            parse_witness_type("Either<(u256, bool), Option<()>>")
+        );
+        assert_eq!(
+            Ok(WitnessType::Array(Box::new(WitnessType::Bool), 2)),
+            parse_witness_type("[bool; 2]")
+        );
+        assert!(parse_witness_type("u3").is_err());
+        assert!(parse_witness_type("Either<u8>").is_err());
+        assert!(parse_witness_type("u8 u8").is_err());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn word_hex_roundtrip() {
+        for (bits, word) in [(8, 0), (8, 127), (8, 255), (16, 256), (64, u64::MAX)] {
+            let hex = word_to_hex(word, bits);
+            assert_eq!(bits / 4, hex.len());
+            assert_eq!(Some(word), hex_to_word(&hex, bits));
+        }
+        assert_eq!(Some(128), hex_to_word("0x80", 8));
+        assert_eq!(None, hex_to_word("080", 8));
+        assert_eq!(None, hex_to_word("zz", 8));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_declarations() {
+        let text = "mod witness {\n    const SIG: Signature = 0x00;\n    const PATH: Either<u8, ()> = Left(1);\n}\n\nfn main() {}";
+        assert_eq!(
+            vec![
+                ("SIG".to_string(), "Signature".to_string()),
+                ("PATH".to_string(), "Either<u8, ()>".to_string()),
+            ],
+            witness_declarations(text)
+        );
+        assert!(witness_declarations("fn main() {}").is_empty());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn serialize_values() {
+        let runtime = leptos::create_runtime();
+        let ty = parse_witness_type("(Either<u4, bool>, Option<u8>, [bool; 2])").unwrap();
+        let value = FormValue::new(&ty);
+        assert_eq!(
+            Ok("(Left(0), None, [false, false])".to_string()),
+            value.to_literal(&ty)
+        );
+
+        let FormValue::List(elements) = &value else {
+            panic!("tuple should have a list value");
+        };
+        let FormValue::Choice {
+            is_right, right, ..
+        } = &elements[1]
+        else {
+            panic!("option should have a choice value");
+        };
+        is_right.set(true);
+        let FormValue::Text(text) = right.as_ref() else {
+            panic!("u8 should have a text value");
+        };
+        text.set("ff".to_string());
+        assert_eq!(
+            Ok("(Left(0), Some(0xff), [false, false])".to_string()),
+            value.to_literal(&ty)
+        );
+
+        text.set("fff".to_string());
+        assert!(value.to_literal(&ty).is_err());
+        runtime.dispose();
+    }
+}
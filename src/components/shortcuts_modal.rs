@@ -0,0 +1,79 @@
+use leptos::{
+    component, document, ev, on_cleanup, use_context, view, window_event_listener, CollectView,
+    IntoView, RwSignal, Show, SignalGet, SignalGetUntracked, SignalSet,
+};
+
+/// Keyboard shortcuts of the IDE and what they do.
+pub const SHORTCUTS: &[(&str, &str)] = &[
+    ("Ctrl / ⌘ + Enter", "Run program"),
+    ("F11", "Toggle fullscreen editor (in the editor)"),
+    ("Tab", "Indent"),
+    ("Shift + Tab", "Unindent"),
+    ("← / →", "Previous / next tab"),
+    ("Home / End", "First / last tab"),
+    ("?", "Show keyboard shortcuts"),
+    ("Escape", "Close dialog"),
+];
+
+/// Whether the keyboard shortcuts modal is open.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ShortcutsModalOpen(pub RwSignal<bool>);
+
+/// Return whether keyboard focus is in a text field, where `?` is typed as text.
+fn focus_is_in_text_field() -> bool {
+    document()
+        .active_element()
+        .is_some_and(|element| matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA"))
+}
+
+#[component]
+pub fn KeyboardShortcutsModal() -> impl IntoView {
+    let open = use_context::<ShortcutsModalOpen>()
+        .expect("shortcuts modal state should exist in context")
+        .0;
+    let shortcut_handle =
+        window_event_listener(ev::keydown, move |event| match event.key().as_str() {
+            "?" if !focus_is_in_text_field() => {
+                event.prevent_default();
+                open.set(true);
+            }
+            "Escape" if open.get_untracked() => open.set(false),
+            _ => {}
+        });
+    on_cleanup(move || shortcut_handle.remove());
+
+    let close = move |_event: ev::MouseEvent| open.set(false);
+    let shortcut_rows = SHORTCUTS
+        .iter()
+        .map(|(keys, action)| {
+            view! {
+                <tr>
+                    <td><kbd>{*keys}</kbd></td>
+                    <td>{*action}</td>
+                </tr>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="modal-overlay" on:click=close>
+                <div
+                    class="modal"
+                    role="dialog"
+                    aria-modal="true"
+                    aria-label="Keyboard shortcuts"
+                    on:click=|event: ev::MouseEvent| event.stop_propagation()
+                >
+                    <div class="modal-header">
+                        <h3>"Keyboard shortcuts"</h3>
+                        <button class="modal-close" aria-label="Close" on:click=close>
+                            <i class="fa-solid fa-xmark"></i>
+                        </button>
+                    </div>
+                    <table class="shortcut-table">{shortcut_rows.clone()}</table>
+                </div>
+            </div>
+        </Show>
+    }
+}
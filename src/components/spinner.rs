@@ -0,0 +1,13 @@
+use leptos::{component, view, IntoView, Show, Signal, SignalGet};
+
+/// Spinning circle that covers its positioned parent while `active` is true.
+#[component]
+pub fn Spinner(#[prop(into)] active: Signal<bool>) -> impl IntoView {
+    view! {
+        <Show when=move || active.get()>
+            <div class="spinner-overlay" role="progressbar" aria-label="Running program">
+                <div class="spinner"></div>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,74 @@
+use leptos::{
+    component, create_rw_signal, ev, html, on_cleanup, view, window_event_listener, IntoView,
+    NodeRef, SignalGet, SignalGetUntracked, SignalSet, ViewFn,
+};
+
+use crate::components::state::local_storage;
+
+/// Local storage key of the width of the left pane.
+const SPLIT_KEY: &str = "split_percent";
+
+/// Minimum width of each pane in pixels.
+const MIN_PANE_WIDTH: f64 = 200.0;
+
+/// Default width of the left pane in percent.
+const DEFAULT_WIDTH_PERCENT: f64 = 50.0;
+
+#[component]
+pub fn SplitPane(#[prop(into)] left: ViewFn, #[prop(into)] right: ViewFn) -> impl IntoView {
+    let stored_percent = local_storage()
+        .and_then(|storage| storage.get_item(SPLIT_KEY).ok().flatten())
+        .and_then(|s| s.parse::<f64>().ok())
+        .filter(|percent| (0.0..=100.0).contains(percent));
+    let width_percent = create_rw_signal(stored_percent.unwrap_or(DEFAULT_WIDTH_PERCENT));
+    let dragging = create_rw_signal(false);
+    let container = NodeRef::<html::Div>::new();
+
+    let start_drag = move |event: ev::MouseEvent| {
+        event.prevent_default();
+        dragging.set(true);
+    };
+    let drag_handle = window_event_listener(ev::mousemove, move |event| {
+        if !dragging.get_untracked() {
+            return;
+        }
+        let Some(container) = container.get_untracked() else {
+            return;
+        };
+        let bounds = container.get_bounding_client_rect();
+        if bounds.width() < 2.0 * MIN_PANE_WIDTH {
+            return;
+        }
+        let left_width = (f64::from(event.client_x()) - bounds.left())
+            .clamp(MIN_PANE_WIDTH, bounds.width() - MIN_PANE_WIDTH);
+        width_percent.set(left_width / bounds.width() * 100.0);
+    });
+    let drop_handle = window_event_listener(ev::mouseup, move |_event| {
+        if !dragging.get_untracked() {
+            return;
+        }
+        dragging.set(false);
+        if let Some(storage) = local_storage() {
+            let _result = storage.set_item(SPLIT_KEY, &width_percent.get_untracked().to_string());
+        }
+    });
+    on_cleanup(move || {
+        drag_handle.remove();
+        drop_handle.remove();
+    });
+
+    let left_basis = move || format!("{}%", width_percent.get());
+    let right_basis = move || format!("{}%", 100.0 - width_percent.get());
+
+    view! {
+        <div class="split-pane" class:dragging=dragging node_ref=container>
+            <div class="split-pane-side" style:flex-basis=left_basis>
+                {left.run()}
+            </div>
+            <div class="split-pane-divider" on:mousedown=start_drag></div>
+            <div class="split-pane-side" style:flex-basis=right_basis>
+                {right.run()}
+            </div>
+        </div>
+    }
+}
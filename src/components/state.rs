@@ -1,14 +1,17 @@
+use hex_conservative::{DisplayHex, FromHex};
 use leptos::{use_context, SignalGetUntracked, SignalWithUntracked};
+use simfony::elements;
 use simfony::num::U256;
 use web_sys::window;
 
 use crate::components::program_window::Program;
 use crate::components::run_window::{HashCount, KeyCount, TxEnv};
+use crate::test_store::TestCaseStore;
 use crate::transaction::TxParams;
 use crate::util::{Counter26, HashedData, SigningKeys};
 
 /// Get the browser's local storage.
-fn local_storage() -> Option<web_sys::Storage> {
+pub fn local_storage() -> Option<web_sys::Storage> {
     let window = window()?;
     window.local_storage().ok().flatten()
 }
@@ -48,6 +51,155 @@ pub trait LocalStorage: Sized {
     }
 }
 
+/// Prefix of the local storage keys of saved programs.
+const SAVED_PROGRAM_PREFIX: &str = "saved_program:";
+
+/// Save the program `source` under the given `name` in the browser's local storage.
+///
+/// Replaces any existing program of the same name.
+pub fn save_program(name: &str, source: &str) {
+    if let Some(storage) = local_storage() {
+        let key = format!("{SAVED_PROGRAM_PREFIX}{name}");
+        let _result = storage.set_item(&key, source);
+        let timestamp = (js_sys::Date::now() as u64).to_string();
+        let _result = storage.set_item(LAST_MANUAL_SAVE, &timestamp);
+    }
+}
+
+/// Load the program of the given `name` from the browser's local storage.
+pub fn load_program(name: &str) -> Option<String> {
+    let storage = local_storage()?;
+    let key = format!("{SAVED_PROGRAM_PREFIX}{name}");
+    storage.get_item(&key).ok().flatten()
+}
+
+/// Delete the program of the given `name` from the browser's local storage.
+pub fn delete_program(name: &str) {
+    if let Some(storage) = local_storage() {
+        let key = format!("{SAVED_PROGRAM_PREFIX}{name}");
+        let _result = storage.remove_item(&key);
+    }
+}
+
+/// List the names of the programs that the user saved in the browser's local storage.
+pub fn saved_program_names() -> Vec<String> {
+    let storage = match local_storage() {
+        Some(storage) => storage,
+        None => return vec![],
+    };
+    let length = storage.length().unwrap_or(0);
+    let mut names: Vec<String> = (0..length)
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .filter_map(|key| key.strip_prefix(SAVED_PROGRAM_PREFIX).map(str::to_string))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Prefix of the local storage keys of automatically saved snapshots.
+///
+/// The prefix is followed by the time of the snapshot in milliseconds since the Unix epoch.
+const AUTOSAVE_PREFIX: &str = "autosave:";
+
+/// Maximum number of automatically saved snapshots.
+///
+/// The oldest snapshot is deleted when a new snapshot exceeds this number.
+pub const MAX_AUTOSAVES: usize = 10;
+
+/// Local storage key of the time when the user last saved a program,
+/// in milliseconds since the Unix epoch.
+const LAST_MANUAL_SAVE: &str = "last_manual_save";
+
+/// List the times of the automatically saved snapshots, oldest first.
+fn autosave_timestamps(storage: &web_sys::Storage) -> Vec<u64> {
+    let length = storage.length().unwrap_or(0);
+    let mut timestamps: Vec<u64> = (0..length)
+        .filter_map(|index| storage.key(index).ok().flatten())
+        .filter_map(|key| key.strip_prefix(AUTOSAVE_PREFIX)?.parse().ok())
+        .collect();
+    timestamps.sort_unstable();
+    timestamps
+}
+
+/// Save a snapshot of the program `source` that was taken at the given time,
+/// in milliseconds since the Unix epoch.
+///
+/// Nothing is saved if the source is equal to the latest snapshot.
+/// Only the latest [`MAX_AUTOSAVES`] snapshots are kept.
+pub fn autosave_program(timestamp: u64, source: &str) {
+    let storage = match local_storage() {
+        Some(storage) => storage,
+        None => return,
+    };
+    if latest_autosave().is_some_and(|(_, latest)| latest == source) {
+        return;
+    }
+    let _result = storage.set_item(&format!("{AUTOSAVE_PREFIX}{timestamp}"), source);
+    let timestamps = autosave_timestamps(&storage);
+    let expired = timestamps.len().saturating_sub(MAX_AUTOSAVES);
+    for timestamp in &timestamps[..expired] {
+        let _result = storage.remove_item(&format!("{AUTOSAVE_PREFIX}{timestamp}"));
+    }
+}
+
+/// Load the latest automatically saved snapshot and the time when it was taken.
+pub fn latest_autosave() -> Option<(u64, String)> {
+    let storage = local_storage()?;
+    let timestamp = autosave_timestamps(&storage).pop()?;
+    let source = storage
+        .get_item(&format!("{AUTOSAVE_PREFIX}{timestamp}"))
+        .ok()
+        .flatten()?;
+    Some((timestamp, source))
+}
+
+/// Load the time when the user last saved a program.
+///
+/// Return 0 if the user never saved a program.
+pub fn last_manual_save() -> u64 {
+    local_storage()
+        .and_then(|storage| storage.get_item(LAST_MANUAL_SAVE).ok().flatten())
+        .and_then(|timestamp| timestamp.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Local storage key of the announcement that the user dismissed last.
+const DISMISSED_ANNOUNCEMENT: &str = "dismissed_announcement";
+
+/// Load the announcement that the user dismissed last, if any.
+pub fn dismissed_announcement() -> Option<String> {
+    local_storage().and_then(|storage| storage.get_item(DISMISSED_ANNOUNCEMENT).ok().flatten())
+}
+
+/// Remember that the user dismissed the given announcement.
+pub fn dismiss_announcement(announcement: &str) {
+    if let Some(storage) = local_storage() {
+        let _result = storage.set_item(DISMISSED_ANNOUNCEMENT, announcement);
+    }
+}
+
+/// Local storage key of the saved test cases.
+const TEST_CASES_KEY: &str = "test_cases";
+
+/// Load the test cases from the browser's local storage.
+///
+/// Return an empty store if there are no test cases or if they are ill-formatted.
+pub fn load_test_cases() -> TestCaseStore {
+    local_storage()
+        .and_then(|storage| storage.get_item(TEST_CASES_KEY).ok().flatten())
+        .and_then(|json| TestCaseStore::from_json(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Store the test cases in the browser's local storage.
+///
+/// Replaces all existing test cases.
+pub fn store_test_cases(store: &TestCaseStore) {
+    if let Some(storage) = local_storage() {
+        let _result = storage.set_item(TEST_CASES_KEY, &store.to_json());
+    }
+}
+
 /// Store the app's entire state in the browser's local storage.
 pub fn update_local_storage() {
     use_context::<Program>()
@@ -163,6 +315,10 @@ impl LocalStorage for TxParams {
             "fee",
             "lock_time",
             "sequence",
+            "tx_version",
+            "input_index",
+            "script_pubkey",
+            "annex",
         ]
         .into_iter()
     }
@@ -175,6 +331,18 @@ impl LocalStorage for TxParams {
         let fee = values.next().and_then(|s| s.parse().ok())?;
         let lock_time = values.next().and_then(|s| s.parse().ok())?;
         let sequence = values.next().and_then(|s| s.parse().ok())?;
+        // Environment fields were added later and may be missing from storage
+        let version = values.next().and_then(|s| s.parse().ok()).unwrap_or(2);
+        let input_index = values.next().and_then(|s| s.parse().ok()).unwrap_or(0);
+        let script_pubkey = values
+            .next()
+            .filter(|s| !s.is_empty())
+            .and_then(|s| Vec::<u8>::from_hex(&s).ok())
+            .map(elements::Script::from);
+        let annex = values
+            .next()
+            .filter(|s| !s.is_empty())
+            .and_then(|s| Vec::<u8>::from_hex(&s).ok());
 
         Some(Self {
             txid,
@@ -184,6 +352,10 @@ impl LocalStorage for TxParams {
             fee,
             lock_time,
             sequence,
+            version,
+            input_index,
+            script_pubkey,
+            annex,
         })
     }
 
@@ -199,6 +371,16 @@ impl LocalStorage for TxParams {
             self.fee.to_string(),
             self.lock_time.to_string(),
             self.sequence.to_string(),
+            self.version.to_string(),
+            self.input_index.to_string(),
+            self.script_pubkey
+                .as_ref()
+                .map(|script| script.as_bytes().to_lower_hex_string())
+                .unwrap_or_default(),
+            self.annex
+                .as_ref()
+                .map(|annex| annex.to_lower_hex_string())
+                .unwrap_or_default(),
         ]
         .into_iter()
     }
@@ -0,0 +1,108 @@
+use leptos::{
+    component, create_effect, create_rw_signal, document, ev, use_context, view, IntoView,
+    RwSignal, SignalGet, SignalUpdate,
+};
+
+use crate::components::state::local_storage;
+
+/// Local storage key of the theme that the user picked.
+const THEME_KEY: &str = "theme";
+
+/// Color scheme of the IDE.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Theme {
+    Dark,
+    Light,
+}
+
+impl Theme {
+    /// Return the CSS class of the theme, which is also its stored name.
+    pub const fn class(self) -> &'static str {
+        match self {
+            Self::Dark => "dark",
+            Self::Light => "light",
+        }
+    }
+
+    fn from_class(class: &str) -> Option<Self> {
+        match class {
+            "dark" => Some(Self::Dark),
+            "light" => Some(Self::Light),
+            _ => None,
+        }
+    }
+
+    fn toggled(self) -> Self {
+        match self {
+            Self::Dark => Self::Light,
+            Self::Light => Self::Dark,
+        }
+    }
+
+    /// Return the theme that the user picked,
+    /// falling back to the color scheme preference of the system.
+    fn detect() -> Self {
+        let stored = local_storage()
+            .and_then(|storage| storage.get_item(THEME_KEY).ok().flatten())
+            .and_then(|class| Self::from_class(&class));
+        let prefers_light = || {
+            web_sys::window()
+                .and_then(|window| {
+                    window
+                        .match_media("(prefers-color-scheme: dark)")
+                        .ok()
+                        .flatten()
+                })
+                .is_some_and(|query| !query.matches())
+        };
+        match stored {
+            Some(theme) => theme,
+            None if prefers_light() => Self::Light,
+            None => Self::Dark,
+        }
+    }
+}
+
+/// Current theme of the IDE.
+#[derive(Copy, Clone, Debug)]
+pub struct ActiveTheme(pub RwSignal<Theme>);
+
+impl Default for ActiveTheme {
+    fn default() -> Self {
+        Self(create_rw_signal(Theme::detect()))
+    }
+}
+
+#[component]
+pub fn ThemeToggle() -> impl IntoView {
+    let theme = use_context::<ActiveTheme>().expect("active theme should exist in context");
+    create_effect(move |_| {
+        let current = theme.0.get();
+        if let Some(body) = document().body() {
+            let class_list = body.class_list();
+            let _result = class_list.remove_1(current.toggled().class());
+            let _result = class_list.add_1(current.class());
+        }
+    });
+
+    let toggle_theme = move |_event: ev::MouseEvent| {
+        theme.0.update(|current| *current = current.toggled());
+        if let Some(storage) = local_storage() {
+            let _result = storage.set_item(THEME_KEY, theme.0.get().class());
+        }
+    };
+    let icon_class = move || match theme.0.get() {
+        Theme::Dark => "fa-solid fa-sun",
+        Theme::Light => "fa-solid fa-moon",
+    };
+    let button_title = move || match theme.0.get() {
+        Theme::Dark => "Switch to light mode",
+        Theme::Light => "Switch to dark mode",
+    };
+
+    view! {
+        <button class="button theme-toggle" title=button_title on:click=toggle_theme>
+            <i class=icon_class></i>
+        </button>
+    }
+}
@@ -0,0 +1,123 @@
+use leptos::{
+    component, create_rw_signal, ev, provide_context, spawn_local, store_value, view, Children,
+    For, IntoView, RwSignal, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith, StoredValue,
+};
+
+/// How long a toast is shown, in milliseconds.
+///
+/// A toast stays open for another period while the mouse hovers over it.
+const TOAST_DURATION_MS: u32 = 3000;
+
+/// Severity of a toast.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Error,
+}
+
+impl ToastLevel {
+    const fn class(self) -> &'static str {
+        match self {
+            Self::Info => "toast info",
+            Self::Success => "toast success",
+            Self::Error => "toast error",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Toast {
+    id: usize,
+    level: ToastLevel,
+    message: String,
+    hovered: RwSignal<bool>,
+}
+
+/// Notifications that are shown in the bottom-right corner.
+#[derive(Copy, Clone, Debug)]
+pub struct Toasts {
+    toasts: RwSignal<Vec<Toast>>,
+    next_id: StoredValue<usize>,
+}
+
+impl Toasts {
+    fn new() -> Self {
+        Self {
+            toasts: create_rw_signal(Vec::new()),
+            next_id: store_value(0),
+        }
+    }
+
+    /// Show a toast that is dismissed after a few seconds.
+    pub fn add_toast(self, level: ToastLevel, message: String) {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        let hovered = create_rw_signal(false);
+        self.toasts.update(|toasts| {
+            toasts.push(Toast {
+                id,
+                level,
+                message,
+                hovered,
+            })
+        });
+        spawn_local(async move {
+            loop {
+                gloo_timers::future::TimeoutFuture::new(TOAST_DURATION_MS).await;
+                if !hovered.get_untracked() {
+                    break;
+                }
+            }
+            self.dismiss(id);
+        });
+    }
+
+    fn dismiss(self, id: usize) {
+        self.toasts
+            .update(|toasts| toasts.retain(|toast| toast.id != id));
+    }
+}
+
+#[component]
+pub fn ToastProvider(children: Children) -> impl IntoView {
+    let toasts = Toasts::new();
+    provide_context(toasts);
+
+    let toast_view = move |toast: Toast| {
+        let Toast {
+            id,
+            level,
+            message,
+            hovered,
+        } = toast;
+        view! {
+            <div
+                class=level.class()
+                role="status"
+                on:mouseenter=move |_event: ev::MouseEvent| hovered.set(true)
+                on:mouseleave=move |_event: ev::MouseEvent| hovered.set(false)
+            >
+                <span class="toast-message">{message}</span>
+                <button
+                    class="toast-close"
+                    aria-label="Dismiss"
+                    on:click=move |_event: ev::MouseEvent| toasts.dismiss(id)
+                >
+                    <i class="fa-solid fa-xmark"></i>
+                </button>
+            </div>
+        }
+    };
+
+    view! {
+        {children()}
+        <div class="toast-container" aria-live="polite">
+            <For
+                each=move || toasts.toasts.with(Clone::clone)
+                key=|toast| toast.id
+                children=toast_view
+            />
+        </div>
+    }
+}
@@ -0,0 +1,355 @@
+use std::fmt;
+
+use leptos::{
+    component, create_rw_signal, ev, event_target_value, view, CollectView, IntoView, RwSignal,
+    Signal, SignalGetUntracked, SignalSet, SignalUpdate, SignalWith, View,
+};
+
+use crate::components::copy_to_clipboard::CopyToClipboard;
+use crate::components::string_box::ErrorBox;
+
+/// Simplicity type that is built from the unit type, sums and products.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum TypeTree {
+    #[default]
+    Unit,
+    Sum(Box<TypeTree>, Box<TypeTree>),
+    Product(Box<TypeTree>, Box<TypeTree>),
+}
+
+/// Names of the kinds of type nodes, in the order of the dropdown.
+const KINDS: [&str; 3] = ["Unit", "Sum", "Product"];
+
+impl TypeTree {
+    /// Return the name of the kind of the root node.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TypeTree::Unit => KINDS[0],
+            TypeTree::Sum(..) => KINDS[1],
+            TypeTree::Product(..) => KINDS[2],
+        }
+    }
+
+    /// Change the kind of the root node.
+    ///
+    /// The children are kept when changing between sum and product.
+    /// New children are units.
+    pub fn set_kind(&mut self, kind: &str) {
+        let (left, right) = match std::mem::take(self) {
+            TypeTree::Unit => Default::default(),
+            TypeTree::Sum(left, right) | TypeTree::Product(left, right) => (left, right),
+        };
+        *self = match kind {
+            "Sum" => TypeTree::Sum(left, right),
+            "Product" => TypeTree::Product(left, right),
+            _ => TypeTree::Unit,
+        };
+    }
+
+    /// Return the node at the given path.
+    ///
+    /// The path goes from the root to the node, where `false` is left and `true` is right.
+    pub fn get(&self, path: &[bool]) -> Option<&TypeTree> {
+        match (path.split_first(), self) {
+            (None, _) => Some(self),
+            (Some(..), TypeTree::Unit) => None,
+            (Some((right, rest)), TypeTree::Sum(l, r) | TypeTree::Product(l, r)) => match right {
+                false => l.get(rest),
+                true => r.get(rest),
+            },
+        }
+    }
+
+    /// Return the node at the given path as mutable reference.
+    pub fn get_mut(&mut self, path: &[bool]) -> Option<&mut TypeTree> {
+        match (path.split_first(), self) {
+            (None, node) => Some(node),
+            (Some(..), TypeTree::Unit) => None,
+            (Some((right, rest)), TypeTree::Sum(l, r) | TypeTree::Product(l, r)) => match right {
+                false => l.get_mut(rest),
+                true => r.get_mut(rest),
+            },
+        }
+    }
+
+    /// Display the type in the notation of the Simplicity paper: `1`, `(A + B)` and `(A × B)`.
+    pub fn display_simplicity(&self) -> DisplaySimplicity<'_> {
+        DisplaySimplicity(self)
+    }
+}
+
+/// Display the type in Simfony syntax: `()`, `Either<A, B>` and `(A, B)`.
+impl fmt::Display for TypeTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeTree::Unit => f.write_str("()"),
+            TypeTree::Sum(left, right) => write!(f, "Either<{left}, {right}>"),
+            TypeTree::Product(left, right) => write!(f, "({left}, {right})"),
+        }
+    }
+}
+
+pub struct DisplaySimplicity<'a>(&'a TypeTree);
+
+impl fmt::Display for DisplaySimplicity<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            TypeTree::Unit => f.write_str("1"),
+            TypeTree::Sum(left, right) => write!(
+                f,
+                "({} + {})",
+                left.display_simplicity(),
+                right.display_simplicity()
+            ),
+            TypeTree::Product(left, right) => write!(
+                f,
+                "({} × {})",
+                left.display_simplicity(),
+                right.display_simplicity()
+            ),
+        }
+    }
+}
+
+/// Parse a type in Simfony syntax or in the notation of the Simplicity paper.
+///
+/// Products may be written as `(A, B)`, `(A × B)` or `(A * B)`.
+pub fn parse_type(s: &str) -> Result<TypeTree, String> {
+    let mut parser = Parser { rest: s, input: s };
+    let tree = parser.parse_type()?;
+    parser.skip_whitespace();
+    match parser.rest.is_empty() {
+        true => Ok(tree),
+        false => Err(parser.error("expected end of type")),
+    }
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+    input: &'a str,
+}
+
+impl Parser<'_> {
+    fn error(&self, message: &str) -> String {
+        let column = self.input[..self.input.len() - self.rest.len()]
+            .chars()
+            .count()
+            + 1;
+        format!("Column {column}: {message}")
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), String> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.error(&format!("expected `{token}`"))),
+        }
+    }
+
+    fn parse_type(&mut self) -> Result<TypeTree, String> {
+        if self.eat("1") {
+            return Ok(TypeTree::Unit);
+        }
+        if self.eat("Either") {
+            self.expect("<")?;
+            let left = self.parse_type()?;
+            self.expect(",")?;
+            let right = self.parse_type()?;
+            self.expect(">")?;
+            return Ok(TypeTree::Sum(Box::new(left), Box::new(right)));
+        }
+        self.expect("(")?;
+        if self.eat(")") {
+            return Ok(TypeTree::Unit);
+        }
+        let left = self.parse_type()?;
+        let is_sum = if self.eat("+") {
+            true
+        } else if self.eat(",") || self.eat("×") || self.eat("*") {
+            false
+        } else {
+            // Parenthesized type
+            self.expect(")")?;
+            return Ok(left);
+        };
+        let right = self.parse_type()?;
+        self.expect(")")?;
+        let (left, right) = (Box::new(left), Box::new(right));
+        match is_sum {
+            true => Ok(TypeTree::Sum(left, right)),
+            false => Ok(TypeTree::Product(left, right)),
+        }
+    }
+}
+
+#[component]
+pub fn TypeExplorer() -> impl IntoView {
+    let tree = create_rw_signal(TypeTree::Unit);
+    let type_text = create_rw_signal(String::new());
+    let parse_error = create_rw_signal(String::new());
+
+    let simfony_type = Signal::derive(move || tree.with(ToString::to_string));
+    let simplicity_type =
+        Signal::derive(move || tree.with(|tree| tree.display_simplicity().to_string()));
+    let update_type_text = move |event: ev::Event| type_text.set(event_target_value(&event));
+    let parse = move |event: ev::SubmitEvent| {
+        event.prevent_default();
+        match parse_type(&type_text.get_untracked()) {
+            Ok(parsed) => {
+                tree.set(parsed);
+                parse_error.update(String::clear);
+            }
+            Err(error) => parse_error.set(error),
+        }
+    };
+
+    view! {
+        <div class="type-explorer">
+            <div class="tab-title-group">
+                <h3 class="tab-title">"Type Explorer"</h3>
+            </div>
+            <p class="tab-description">
+                "Build a type from units, sums and products, or parse an existing type such as Either<(), ((), ())>."
+            </p>
+            <form class="key-store-display-row" on:submit=parse>
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="Type"
+                    spellcheck="false"
+                    prop:value=type_text
+                    on:input=update_type_text
+                />
+                <button class="flat-button bordered" type="submit">
+                    "Parse"
+                </button>
+            </form>
+            <ErrorBox error=parse_error />
+            <div class="key-store-display-row">
+                <div class="display-row-label">"Simfony"</div>
+                <input class="input" type="text" readonly=true prop:value=simfony_type />
+                <CopyToClipboard content=simfony_type class="copy-button">
+                    <i class="far fa-copy"></i>
+                </CopyToClipboard>
+            </div>
+            <div class="key-store-display-row">
+                <div class="display-row-label">"Simplicity"</div>
+                <input class="input" type="text" readonly=true prop:value=simplicity_type />
+            </div>
+            <div class="type-tree">
+                {move || type_node(tree, vec![])}
+            </div>
+        </div>
+    }
+}
+
+/// Render the node at the given path with a dropdown to change its kind
+/// and, for sums and products, its children.
+fn type_node(tree: RwSignal<TypeTree>, path: Vec<bool>) -> View {
+    let node = tree.with(|tree| tree.get(&path).cloned().unwrap_or_default());
+    let select_path = path.clone();
+    let select_kind = move |event: ev::Event| {
+        let kind = event_target_value(&event);
+        tree.update(|tree| {
+            if let Some(node) = tree.get_mut(&select_path) {
+                node.set_kind(&kind);
+            }
+        });
+    };
+    let options = KINDS
+        .into_iter()
+        .map(|kind| view! { <option value=kind selected=kind == node.kind()>{kind}</option> })
+        .collect_view();
+    let children = match &node {
+        TypeTree::Unit => None,
+        TypeTree::Sum(..) | TypeTree::Product(..) => {
+            let child_path = |right: bool| path.iter().copied().chain([right]).collect::<Vec<_>>();
+            Some(view! {
+                <details open=true>
+                    <summary>{node.to_string()}</summary>
+                    <ul>
+                        <li>{type_node(tree, child_path(false))}</li>
+                        <li>{type_node(tree, child_path(true))}</li>
+                    </ul>
+                </details>
+            })
+        }
+    };
+
+    view! {
+        <div class="type-node">
+            <select class="input" on:change=select_kind>
+                {options}
+            </select>
+            {children}
+        </div>
+    }
+    .into_view()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_and_display() {
+        let unit = || Box::new(TypeTree::Unit);
+        let bit = || Box::new(TypeTree::Sum(unit(), unit()));
+        let tree = TypeTree::Sum(unit(), Box::new(TypeTree::Product(bit(), unit())));
+
+        assert_eq!("Either<(), (Either<(), ()>, ())>", tree.to_string());
+        assert_eq!("(1 + ((1 + 1) × 1))", tree.display_simplicity().to_string());
+        assert_eq!(Ok(tree.clone()), parse_type(&tree.to_string()));
+        assert_eq!(
+            Ok(tree.clone()),
+            parse_type(&tree.display_simplicity().to_string())
+        );
+        assert_eq!(Ok(tree), parse_type(" ( 1 + ((1+1) * ( 1 )) ) "));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_errors() {
+        assert_eq!(Err("Column 1: expected `(`".to_string()), parse_type(""));
+        assert_eq!(
+            Err("Column 12: expected `>`".to_string()),
+            parse_type("Either<1, 1")
+        );
+        assert_eq!(
+            Err("Column 3: expected end of type".to_string()),
+            parse_type("1 1")
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn edit_tree() {
+        let mut tree = TypeTree::default();
+        tree.set_kind("Product");
+        tree.get_mut(&[true]).unwrap().set_kind("Sum");
+        assert_eq!("((), Either<(), ()>)", tree.to_string());
+
+        tree.set_kind("Sum");
+        assert_eq!("Either<(), Either<(), ()>>", tree.to_string());
+        assert_eq!(Some("Sum"), tree.get(&[true]).map(TypeTree::kind));
+        assert_eq!(None, tree.get(&[false, false]));
+
+        tree.set_kind("Unit");
+        assert_eq!(TypeTree::Unit, tree);
+    }
+}
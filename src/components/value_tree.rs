@@ -0,0 +1,528 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use hex_conservative::DisplayHex;
+use leptos::{
+    component, create_rw_signal, ev, event_target_value, html, request_animation_frame,
+    set_timeout, view, Callable, Callback, CollectView, IntoView, NodeRef, RwSignal, Signal,
+    SignalSet, SignalUpdate, SignalWith, SignalWithUntracked, View,
+};
+use simfony::simplicity::types::Final;
+use simfony::simplicity::Value;
+
+use crate::components::string_box::ErrorBox;
+
+/// Maximum number of children that are shown below a node.
+///
+/// Nodes with more children show a summary instead.
+const MAX_CHILDREN: usize = 32;
+
+/// Time that an edited node stays highlighted.
+const HIGHLIGHT_DURATION: Duration = Duration::from_millis(500);
+
+/// Kind of a node of a value tree.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum NodeKind {
+    Unit,
+    /// Product of single bits whose length is not a multiple of eight.
+    Bits,
+    /// Product of single bits whose length is a multiple of eight.
+    Bytes,
+    Left,
+    Right,
+    Product,
+}
+
+impl NodeKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            NodeKind::Unit => "Unit",
+            NodeKind::Bits => "Bits",
+            NodeKind::Bytes => "Bytes",
+            NodeKind::Left => "Left",
+            NodeKind::Right => "Right",
+            NodeKind::Product => "Product",
+        }
+    }
+
+    fn class(self) -> &'static str {
+        match self {
+            NodeKind::Unit => "value-badge unit",
+            NodeKind::Bits => "value-badge bits",
+            NodeKind::Bytes => "value-badge bytes",
+            NodeKind::Left => "value-badge left",
+            NodeKind::Right => "value-badge right",
+            NodeKind::Product => "value-badge product",
+        }
+    }
+}
+
+/// Node of the tree of a Simplicity value.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValueNode {
+    pub kind: NodeKind,
+    /// Display text of the value, or its hexadecimal or binary digits for bytes and bits.
+    pub text: String,
+    pub children: Vec<ValueNode>,
+    /// Total number of children, which may exceed the number of shown children.
+    pub child_count: usize,
+    /// Path from the root value to the value of the node.
+    ///
+    /// Each step goes into the left or right component of a product,
+    /// or into the inner value of a sum, where `false` is left and `true` is right.
+    /// The single bytes below a bytes node have no path.
+    pub path: Option<Vec<bool>>,
+    /// Number of bits of bits and bytes nodes.
+    pub width: usize,
+}
+
+impl ValueNode {
+    fn leaf(kind: NodeKind, text: String, path: Option<Vec<bool>>, width: usize) -> Self {
+        Self {
+            kind,
+            text,
+            children: vec![],
+            child_count: 0,
+            path,
+            width,
+        }
+    }
+}
+
+/// Return the bits of the value if it is a product of single bits, or a single bit.
+///
+/// A bit is either the left or the right injection of the unit value.
+fn to_bits(value: &Value) -> Option<Vec<bool>> {
+    if let Some(inner) = value.as_left() {
+        return inner.is_unit().then(|| vec![false]);
+    }
+    if let Some(inner) = value.as_right() {
+        return inner.is_unit().then(|| vec![true]);
+    }
+    let (left, right) = value.as_product()?;
+    let mut bits = to_bits(&left.shallow_clone())?;
+    bits.extend(to_bits(&right.shallow_clone())?);
+    Some(bits)
+}
+
+fn bits_text(bits: &[bool]) -> String {
+    bits.iter()
+        .map(|bit| if *bit { '1' } else { '0' })
+        .collect()
+}
+
+fn bytes_of(bits: &[bool]) -> Vec<u8> {
+    bits.chunks(8)
+        .map(|byte| {
+            byte.iter()
+                .fold(0u8, |byte, bit| (byte << 1) | u8::from(*bit))
+        })
+        .collect()
+}
+
+/// Convert the value into a tree of nodes.
+///
+/// Products of at least two bits become a single node of bits or bytes.
+/// The children of a bytes node are its bytes.
+pub fn value_tree(value: &Value) -> ValueNode {
+    node_at(value, vec![])
+}
+
+fn node_at(value: &Value, path: Vec<bool>) -> ValueNode {
+    let text = value.to_string();
+    if value.is_unit() {
+        return ValueNode::leaf(NodeKind::Unit, text, Some(path), 0);
+    }
+    let child_path = |right: bool| path.iter().copied().chain([right]).collect::<Vec<_>>();
+    if let Some((left, right)) = value.as_product() {
+        if let Some(bits) = to_bits(value) {
+            if bits.len() % 8 != 0 {
+                let text = format!("0b{}", bits_text(&bits));
+                return ValueNode::leaf(NodeKind::Bits, text, Some(path), bits.len());
+            }
+            let bytes = bytes_of(&bits);
+            let children = match bytes.len() {
+                1 => vec![],
+                _ => bytes
+                    .iter()
+                    .take(MAX_CHILDREN)
+                    .map(|byte| ValueNode::leaf(NodeKind::Bits, format!("0x{byte:02x}"), None, 8))
+                    .collect(),
+            };
+            return ValueNode {
+                kind: NodeKind::Bytes,
+                text: format!("0x{}", bytes.as_hex()),
+                child_count: if bytes.len() == 1 { 0 } else { bytes.len() },
+                children,
+                path: Some(path),
+                width: bits.len(),
+            };
+        }
+        return ValueNode {
+            kind: NodeKind::Product,
+            text,
+            children: vec![
+                node_at(&left.shallow_clone(), child_path(false)),
+                node_at(&right.shallow_clone(), child_path(true)),
+            ],
+            child_count: 2,
+            path: Some(path),
+            width: 0,
+        };
+    }
+    let (kind, inner, right) = match (value.as_left(), value.as_right()) {
+        (Some(inner), _) => (NodeKind::Left, inner.shallow_clone(), false),
+        (_, Some(inner)) => (NodeKind::Right, inner.shallow_clone(), true),
+        _ => unreachable!("value is unit, product or sum"),
+    };
+    ValueNode {
+        kind,
+        text,
+        children: vec![node_at(&inner, child_path(right))],
+        child_count: 1,
+        path: Some(path),
+        width: 0,
+    }
+}
+
+/// Parse the bits of an edited bits or bytes node.
+///
+/// The bits are written as hexadecimal digits, with or without `0x` prefix,
+/// or as binary digits with `0b` prefix.
+pub fn parse_bits(text: &str, width: usize) -> Result<Vec<bool>, String> {
+    let text = text.trim();
+    if let Some(binary) = text.strip_prefix("0b") {
+        if binary.len() != width {
+            return Err(format!("Expected {width} binary digits"));
+        }
+        return binary
+            .chars()
+            .map(|digit| match digit {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                _ => Err(format!("Invalid binary digit `{digit}`")),
+            })
+            .collect();
+    }
+    if width % 4 != 0 {
+        return Err(format!("Expected {width} binary digits after `0b`"));
+    }
+    let hex = text.strip_prefix("0x").unwrap_or(text);
+    if hex.len() != width / 4 {
+        return Err(format!("Expected {} hexadecimal digits", width / 4));
+    }
+    let mut bits = Vec::with_capacity(width);
+    for digit in hex.chars() {
+        let nibble = digit
+            .to_digit(16)
+            .ok_or_else(|| format!("Invalid hexadecimal digit `{digit}`"))?;
+        bits.extend((0..4).rev().map(|shift| nibble & (1 << shift) != 0));
+    }
+    Ok(bits)
+}
+
+/// Build a value of the same shape as the given product of bits, taking the bits from the iterator.
+fn from_bits(shape: &Value, bits: &mut impl Iterator<Item = bool>) -> Option<Value> {
+    if let Some((left, right)) = shape.as_product() {
+        let left = from_bits(&left.shallow_clone(), bits)?;
+        let right = from_bits(&right.shallow_clone(), bits)?;
+        return Some(Value::product(left, right));
+    }
+    match bits.next()? {
+        false => Some(Value::left(Value::unit(), Final::unit())),
+        true => Some(Value::right(Final::unit(), Value::unit())),
+    }
+}
+
+/// Replace the bits of the bits or bytes node at the given path.
+///
+/// The values along the path are rebuilt around the new bits.
+/// Return `None` if the path or the number of bits does not fit the value.
+pub fn replace_bits(value: &Value, path: &[bool], bits: &[bool]) -> Option<Value> {
+    let Some((right, rest)) = path.split_first() else {
+        let mut bits = bits.iter().copied();
+        let replaced = from_bits(value, &mut bits)?;
+        return bits.next().is_none().then_some(replaced);
+    };
+    if let Some((left_value, right_value)) = value.as_product() {
+        let (left_value, right_value) = (left_value.shallow_clone(), right_value.shallow_clone());
+        return match right {
+            false => Some(Value::product(
+                replace_bits(&left_value, rest, bits)?,
+                right_value,
+            )),
+            true => Some(Value::product(
+                left_value,
+                replace_bits(&right_value, rest, bits)?,
+            )),
+        };
+    }
+    let (ty_l, ty_r) = value.ty().as_sum()?;
+    if let Some(inner) = value.as_left() {
+        let inner = replace_bits(&inner.shallow_clone(), rest, bits)?;
+        return Some(Value::left(inner, Arc::clone(ty_r)));
+    }
+    let inner = replace_bits(&value.as_right()?.shallow_clone(), rest, bits)?;
+    Some(Value::right(Arc::clone(ty_l), inner))
+}
+
+/// Editing state of a value tree.
+#[derive(Copy, Clone)]
+struct TreeState {
+    value: Signal<Value>,
+    on_edit: Option<Callback<Value, Result<(), String>>>,
+    /// Path of the node that is being edited.
+    editing: RwSignal<Option<Vec<bool>>>,
+    /// Path of the node that was edited last, while it is highlighted.
+    changed: RwSignal<Option<Vec<bool>>>,
+    error: RwSignal<String>,
+}
+
+impl TreeState {
+    fn apply_edit(self, path: &[bool], width: usize, text: &str) {
+        let Some(on_edit) = self.on_edit else {
+            return;
+        };
+        let result = parse_bits(text, width)
+            .and_then(|bits| {
+                self.value
+                    .with_untracked(|value| replace_bits(value, path, &bits))
+                    .ok_or_else(|| "The value has a different shape".to_string())
+            })
+            .and_then(|edited| on_edit.call(edited));
+        match result {
+            Ok(()) => {
+                self.error.update(String::clear);
+                self.editing.set(None);
+                self.changed.set(Some(path.to_vec()));
+                let path = path.to_vec();
+                let changed = self.changed;
+                set_timeout(
+                    move || {
+                        changed.update(|changed| {
+                            if changed.as_ref() == Some(&path) {
+                                *changed = None;
+                            }
+                        })
+                    },
+                    HIGHLIGHT_DURATION,
+                );
+            }
+            Err(error) => self.error.set(error),
+        }
+    }
+}
+
+/// Collapsible tree of a Simplicity value with colored badges for the kinds of nodes.
+///
+/// If `on_edit` is given, then clicking a bits or bytes node opens an editor for its digits.
+/// The edited value is passed to `on_edit`, which rejects it by returning an error.
+#[component]
+pub fn ValueTree(
+    #[prop(into)] value: Signal<Value>,
+    #[prop(optional, into)] on_edit: Option<Callback<Value, Result<(), String>>>,
+) -> impl IntoView {
+    let state = TreeState {
+        value,
+        on_edit,
+        editing: create_rw_signal(None),
+        changed: create_rw_signal(None),
+        error: create_rw_signal(String::new()),
+    };
+
+    view! {
+        <div class="value-tree">
+            {move || value.with(|value| node_view(&value_tree(value), state))}
+            <ErrorBox error=state.error />
+        </div>
+    }
+}
+
+fn node_view(node: &ValueNode, state: TreeState) -> View {
+    let badge = view! {
+        <span class=node.kind.class()>{node.kind.label()}</span>
+        {node_text(node, state)}
+    };
+    if node.child_count == 0 {
+        return view! { <div class="value-node">{badge}</div> }.into_view();
+    }
+    let children = match node.child_count <= MAX_CHILDREN {
+        true => node
+            .children
+            .iter()
+            .map(|child| view! { <li>{node_view(child, state)}</li> })
+            .collect_view(),
+        false => view! { <li class="value-summary">{format!("[{} bytes]", node.child_count)}</li> }
+            .into_view(),
+    };
+
+    view! {
+        <details class="value-node" open=true>
+            <summary>{badge}</summary>
+            <ul>{children}</ul>
+        </details>
+    }
+    .into_view()
+}
+
+/// Render the text of the node, which is editable for bits and bytes.
+fn node_text(node: &ValueNode, state: TreeState) -> View {
+    let text = node.text.clone();
+    let is_editable = matches!(node.kind, NodeKind::Bits | NodeKind::Bytes);
+    let path = match (is_editable, &node.path, state.on_edit) {
+        (true, Some(path), Some(..)) => path.clone(),
+        _ => return view! { <span class="value-text">{text}</span> }.into_view(),
+    };
+    let width = node.width;
+
+    let text_or_editor = move || {
+        let text = text.clone();
+        if state
+            .editing
+            .with(|editing| editing.as_ref() != Some(&path))
+        {
+            let changed_path = path.clone();
+            let is_changed = move || {
+                state
+                    .changed
+                    .with(|changed| changed.as_ref() == Some(&changed_path))
+            };
+            let edit_path = path.clone();
+            let start_editing = move |event: ev::MouseEvent| {
+                event.prevent_default();
+                state.editing.set(Some(edit_path.clone()));
+            };
+            return view! {
+                <span
+                    class="value-text editable"
+                    class:value-changed=is_changed
+                    title="Click to edit"
+                    on:click=start_editing
+                >
+                    {text}
+                </span>
+            }
+            .into_view();
+        }
+
+        let input_ref = NodeRef::<html::Input>::new();
+        input_ref.on_load(|input| {
+            request_animation_frame(move || {
+                let _result = input.focus();
+            })
+        });
+        let path = path.clone();
+        let keydown = move |event: ev::KeyboardEvent| match event.key().as_str() {
+            "Enter" => {
+                event.prevent_default();
+                state.apply_edit(&path, width, &event_target_value(&event));
+            }
+            "Escape" => state.editing.set(None),
+            _ => {}
+        };
+        let cancel = move |_event: ev::FocusEvent| state.editing.set(None);
+        view! {
+            <input
+                class="input value-editor"
+                type="text"
+                spellcheck="false"
+                value=text
+                node_ref=input_ref
+                on:click=|event: ev::MouseEvent| event.prevent_default()
+                on:keydown=keydown
+                on:blur=cancel
+            />
+        }
+        .into_view()
+    };
+    text_or_editor.into_view()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn unit_and_product_nodes() {
+        let unit = value_tree(&Value::unit());
+        assert_eq!(NodeKind::Unit, unit.kind);
+        assert!(unit.children.is_empty());
+
+        let product = value_tree(&Value::product(Value::unit(), Value::unit()));
+        assert_eq!(NodeKind::Product, product.kind);
+        assert_eq!(
+            vec![NodeKind::Unit, NodeKind::Unit],
+            product
+                .children
+                .iter()
+                .map(|child| child.kind)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(vec![true]), product.children[1].path);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn byte_nodes() {
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0xab;
+        let word = value_tree(&Value::u256(bytes));
+        assert_eq!(NodeKind::Bytes, word.kind);
+        assert_eq!(format!("0x{}", bytes.as_hex()), word.text);
+        assert_eq!(32, word.child_count);
+        assert_eq!(256, word.width);
+        assert_eq!("0xab", word.children[0].text);
+        assert_eq!(None, word.children[0].path);
+
+        let large = value_tree(&Value::product(Value::u256(bytes), Value::u256(bytes)));
+        assert_eq!(NodeKind::Bytes, large.kind);
+        assert_eq!(64, large.child_count);
+        assert_eq!(MAX_CHILDREN, large.children.len());
+
+        let pair = value_tree(&Value::product(Value::unit(), Value::u256(bytes)));
+        assert_eq!(NodeKind::Product, pair.kind);
+        assert_eq!(NodeKind::Bytes, pair.children[1].kind);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_edited_bits() {
+        assert_eq!(
+            Ok(vec![true, false, true, false, false, false, false, true]),
+            parse_bits("0xa1", 8)
+        );
+        assert_eq!(parse_bits("0xa1", 8), parse_bits(" A1 ", 8));
+        assert_eq!(Ok(vec![false, true]), parse_bits("0b01", 2));
+        assert_eq!(
+            Err("Expected 2 hexadecimal digits".to_string()),
+            parse_bits("0x1", 8)
+        );
+        assert_eq!(
+            Err("Invalid hexadecimal digit `g`".to_string()),
+            parse_bits("0g", 8)
+        );
+        assert_eq!(
+            Err("Expected 2 binary digits after `0b`".to_string()),
+            parse_bits("0x1", 2)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn replace_edited_bits() {
+        let bytes = [0u8; 32];
+        let mut edited_bytes = bytes;
+        edited_bytes[31] = 0x01;
+        let value = Value::product(Value::unit(), Value::u256(bytes));
+        let edited_bits = parse_bits(&edited_bytes.to_lower_hex_string(), 256).unwrap();
+
+        let edited = replace_bits(&value, &[true], &edited_bits).expect("path should fit");
+        assert_eq!(
+            value_tree(&Value::u256(edited_bytes)).text,
+            value_tree(&edited).children[1].text
+        );
+        assert!(replace_bits(&value, &[true], &edited_bits[1..]).is_none());
+        assert!(replace_bits(&value, &[true, true, true], &edited_bits).is_none());
+    }
+}
@@ -0,0 +1,6 @@
+//! Settings that are fixed at build time.
+
+/// Announcement that is shown at the top of the page, if any.
+///
+/// Set the `ANNOUNCEMENT` environment variable during the build to show an announcement.
+pub const ANNOUNCEMENT: Option<&str> = option_env!("ANNOUNCEMENT");
@@ -23,7 +23,6 @@ pub struct Example {
 }
 
 impl Example {
-    #[allow(dead_code)]
     pub fn description(self) -> &'static str {
         self.description
     }
@@ -58,6 +57,10 @@ impl Example {
             fee: 1_000,
             lock_time: elements::LockTime::from_consensus(self.lock_time),
             sequence: elements::Sequence::from_consensus(self.sequence),
+            version: 2,
+            input_index: 0,
+            script_pubkey: None,
+            annex: None,
         }
     }
 }
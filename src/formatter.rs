@@ -0,0 +1,255 @@
+//! Formatter for Simfony source code.
+//!
+//! The formatter only changes whitespace: it indents each line by the brackets that are open,
+//! removes trailing whitespace, collapses consecutive blank lines and ends the text with a newline.
+
+use std::fmt;
+
+/// Indentation of one nesting level.
+const INDENT: &str = "  ";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FormatError {
+    /// A closing bracket does not match the most recent opening bracket.
+    UnmatchedBracket { line: usize, bracket: char },
+    /// An opening bracket is never closed.
+    UnclosedBracket { line: usize, bracket: char },
+    /// A block comment is never closed.
+    UnclosedComment { line: usize },
+}
+
+impl fmt::Display for FormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatError::UnmatchedBracket { line, bracket } => {
+                write!(f, "Line {line}: unmatched closing bracket `{bracket}`")
+            }
+            FormatError::UnclosedBracket { line, bracket } => {
+                write!(f, "Line {line}: bracket `{bracket}` is never closed")
+            }
+            FormatError::UnclosedComment { line } => {
+                write!(f, "Line {line}: block comment is never closed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FormatError {}
+
+fn closing_bracket(opening: char) -> Option<char> {
+    match opening {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        _ => None,
+    }
+}
+
+fn is_closing_bracket(c: char) -> bool {
+    matches!(c, ')' | ']' | '}')
+}
+
+/// Bracket that is open at the current position of the formatter.
+struct OpenBracket {
+    bracket: char,
+    /// Line number where the bracket was opened, starting at 1.
+    line: usize,
+    /// Indentation level of the line where the bracket was opened.
+    level: usize,
+}
+
+/// Format the given Simfony source code.
+///
+/// Lines are indented by two spaces per level.
+/// All brackets that a line opens increase the indentation of the following lines by one level.
+/// Lines inside block comments are kept as they are, apart from trailing whitespace.
+pub fn format_simfony(source: &str) -> Result<String, FormatError> {
+    let mut open_brackets: Vec<OpenBracket> = vec![];
+    let mut comment_start: Option<usize> = None;
+    let mut formatted = String::new();
+    let mut previous_blank = true;
+
+    for (index, raw_line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        if comment_start.is_some() {
+            let level = open_brackets.last().map(|open| open.level + 1).unwrap_or(0);
+            comment_start = scan_line(
+                raw_line,
+                line_number,
+                level,
+                comment_start,
+                &mut open_brackets,
+            )?;
+            formatted.push_str(raw_line.trim_end());
+            formatted.push('\n');
+            previous_blank = false;
+            continue;
+        }
+
+        let line = raw_line.trim();
+        if line.is_empty() {
+            if !previous_blank {
+                formatted.push('\n');
+            }
+            previous_blank = true;
+            continue;
+        }
+
+        let level = match (line.starts_with(is_closing_bracket), open_brackets.last()) {
+            (true, Some(open)) => open.level,
+            _ => open_brackets.last().map(|open| open.level + 1).unwrap_or(0),
+        };
+        comment_start = scan_line(line, line_number, level, None, &mut open_brackets)?;
+
+        for _ in 0..level {
+            formatted.push_str(INDENT);
+        }
+        formatted.push_str(line);
+        formatted.push('\n');
+        previous_blank = false;
+    }
+
+    if let Some(line) = comment_start {
+        return Err(FormatError::UnclosedComment { line });
+    }
+    if let Some(open) = open_brackets.first() {
+        return Err(FormatError::UnclosedBracket {
+            line: open.line,
+            bracket: open.bracket,
+        });
+    }
+    if previous_blank && formatted.ends_with("\n\n") {
+        formatted.pop();
+    }
+    Ok(formatted)
+}
+
+/// Update the open brackets with the brackets of the given line, skipping comments.
+///
+/// Return the line number where the block comment started
+/// if the line ends inside a block comment.
+fn scan_line(
+    line: &str,
+    line_number: usize,
+    level: usize,
+    mut comment_start: Option<usize>,
+    open_brackets: &mut Vec<OpenBracket>,
+) -> Result<Option<usize>, FormatError> {
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        if comment_start.is_some() {
+            match rest.find("*/") {
+                Some(end) => {
+                    comment_start = None;
+                    rest = &rest[end + 2..];
+                }
+                None => break,
+            }
+            continue;
+        }
+        if rest.starts_with("//") {
+            break;
+        }
+        if rest.starts_with("/*") {
+            comment_start = Some(line_number);
+            rest = &rest[2..];
+            continue;
+        }
+        if closing_bracket(c).is_some() {
+            open_brackets.push(OpenBracket {
+                bracket: c,
+                line: line_number,
+                level,
+            });
+        } else if is_closing_bracket(c) {
+            match open_brackets.pop() {
+                Some(open) if closing_bracket(open.bracket) == Some(c) => {}
+                _ => {
+                    return Err(FormatError::UnmatchedBracket {
+                        line: line_number,
+                        bracket: c,
+                    })
+                }
+            }
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    Ok(comment_start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    const CANONICAL: &str = "/*
+ * Check that the witness is equal to one.
+ */
+fn main() {
+  let x: u8 = witness::X; // some comment
+
+  let (carry, sum): (bool, u8) = jet::add_8(
+    x,
+    1,
+  );
+  match carry {
+    true => panic!(),
+    false => {
+      assert!(jet::eq_8(sum, 2));
+    },
+  };
+}
+";
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn canonical_program_is_idempotent() {
+        assert_eq!(CANONICAL, format_simfony(CANONICAL).unwrap());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn format_is_idempotent_on_examples() {
+        for name in examples::keys() {
+            let program = examples::get(name).unwrap().template_text();
+            let formatted = format_simfony(program).unwrap();
+            assert_eq!(formatted, format_simfony(&formatted).unwrap(), "{name}");
+        }
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn normalize_whitespace() {
+        let messy =
+            "\n\nfn main() {   \n        let x: u8 = 1;\n\n\n\n   assert!(jet::eq_8(x, 1));\n}\n\n";
+        let expected = "fn main() {\n  let x: u8 = 1;\n\n  assert!(jet::eq_8(x, 1));\n}\n";
+        assert_eq!(expected, format_simfony(messy).unwrap());
+        assert_eq!("fn main() {}\n", format_simfony("fn main() {}").unwrap());
+        assert_eq!("", format_simfony("").unwrap());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn format_errors() {
+        assert_eq!(
+            Err(FormatError::UnmatchedBracket {
+                line: 2,
+                bracket: ')'
+            }),
+            format_simfony("fn main() {\n)\n")
+        );
+        assert_eq!(
+            Err(FormatError::UnclosedBracket {
+                line: 1,
+                bracket: '{'
+            }),
+            format_simfony("fn main() {\n")
+        );
+        assert_eq!(
+            Err(FormatError::UnclosedComment { line: 2 }),
+            format_simfony("fn main() {}\n/* comment\n")
+        );
+        // Brackets in comments are ignored
+        assert!(format_simfony("fn main() {} // }\n/* ( */").is_ok());
+    }
+}
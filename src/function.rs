@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::fmt;
 use std::sync::Arc;
 
@@ -7,13 +8,14 @@ use simfony::either;
 use simfony::simplicity::jet::elements::ElementsEnv;
 use simfony::SatisfiedProgram;
 use simfony::{elements, simplicity};
+use simplicity::dag::{DagLike, InternalSharing};
 use simplicity::node::Inner;
 use simplicity::types::Final;
 use simplicity::Value;
 
 use crate::jet;
 use crate::jet::JetFailed;
-use crate::util::Expression;
+use crate::util::{DisplayInner, Expression};
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum ErrorKind {
@@ -52,6 +54,44 @@ impl fmt::Display for ErrorKind {
     }
 }
 
+/// Record of a jet call during execution.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct JetCallEntry {
+    pub jet_name: String,
+    pub input: Value,
+    pub output: Result<Value, String>,
+}
+
+/// Resources that the runner used during execution.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ExecutionResources {
+    /// Number of executed nodes.
+    pub node_executions: usize,
+    /// Number of executed jets.
+    pub jet_calls: usize,
+    /// Total cost of the executed jets in milliweight.
+    pub jet_cost: u64,
+    /// Maximum number of bits that were held on the input and output stacks at once.
+    ///
+    /// This approximates the number of cells that the Bit Machine allocates.
+    pub max_cells: usize,
+}
+
+/// State of the runner after executing a single task.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct StepResult {
+    /// Description of the task that was executed.
+    pub cursor: String,
+    /// Name of the jet that was evaluated, if any.
+    pub jet: Option<String>,
+    /// Index of the node that was executed, if any.
+    pub node_index: Option<usize>,
+    /// Stack of input values, from bottom to top.
+    pub input_stack: Vec<Value>,
+    /// Stack of output values, from bottom to top.
+    pub output_stack: Vec<Value>,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 enum Task {
     Execute(Arc<Expression>),
@@ -77,10 +117,26 @@ pub struct Runner {
     active_simfony_call: Option<FallibleCall>,
     /// Output from Simfony dbg! expressions, in order of occurrence.
     debug_output: Vec<String>,
+    /// Jet calls in order of occurrence, if tracing is enabled.
+    jet_trace: Option<Vec<JetCallEntry>>,
+    /// Post-order index of each node of the program, keyed by the node's address.
+    node_indices: HashMap<*const Expression, usize>,
+    /// Resources used so far.
+    resources: ExecutionResources,
+    /// Number of bits that are currently held on the input and output stacks.
+    cells: usize,
+    /// Task that was executed last when stepping through the program.
+    last_task: Option<Task>,
 }
 
 impl Runner {
     pub fn for_program(program: SatisfiedProgram) -> Self {
+        let node_indices = program
+            .redeem()
+            .as_ref()
+            .post_order_iter::<InternalSharing>()
+            .map(|item| (item.node as *const Expression, item.index))
+            .collect();
         Self {
             tasks: vec![Task::Execute(program.redeem().clone())],
             input: vec![Value::unit()],
@@ -88,165 +144,310 @@ impl Runner {
             debug_symbols: program.debug_symbols().clone(),
             active_simfony_call: None,
             debug_output: vec![],
+            jet_trace: None,
+            node_indices,
+            resources: ExecutionResources::default(),
+            cells: 0,
+            last_task: None,
         }
     }
 
+    /// Return the number of distinct nodes of the program.
+    pub fn node_count(&self) -> usize {
+        self.node_indices.len()
+    }
+
+    /// Return the index of the node that is executed next, if any.
+    pub fn next_node_index(&self) -> Option<usize> {
+        match self.tasks.last() {
+            Some(Task::Execute(expression)) => self.node_index(expression),
+            _ => None,
+        }
+    }
+
+    fn node_index(&self, expression: &Arc<Expression>) -> Option<usize> {
+        self.node_indices.get(&Arc::as_ptr(expression)).copied()
+    }
+
+    /// Record every jet call during execution.
+    ///
+    /// This slows down execution.
+    pub fn with_jet_trace(mut self) -> Self {
+        self.jet_trace = Some(vec![]);
+        self
+    }
+
+    /// Access the recorded jet calls.
+    ///
+    /// The slice is empty if tracing is disabled.
+    pub fn jet_trace(&self) -> &[JetCallEntry] {
+        self.jet_trace.as_deref().unwrap_or_default()
+    }
+
+    /// Access the stack of output values, from bottom to top.
+    ///
+    /// After a successful run, the stack contains the single output value of the program.
+    /// After a failed run, the stack contains the values that were computed so far.
+    pub fn output_stack(&self) -> &[Value] {
+        &self.output
+    }
+
+    /// Access the resources that were used so far.
+    pub fn resources(&self) -> ExecutionResources {
+        self.resources
+    }
+
     pub fn debug_output(self) -> Vec<String> {
         self.debug_output
     }
 
     pub fn run(&mut self, env: &ElementsEnv<Arc<elements::Transaction>>) -> Result<(), ErrorKind> {
         while let Some(task) = self.tasks.pop() {
-            match task {
-                Task::Execute(expression) => {
-                    let input = self.input.pop().unwrap();
-                    match expression.inner() {
-                        Inner::Iden => self.output.push(input),
-                        Inner::Unit => self.output.push(Value::unit()),
-                        Inner::InjL(t) => {
-                            let ty_r = expression.arrow().target.as_sum().unwrap().1;
-                            self.tasks.push(Task::MakeLeft(Arc::new(ty_r.clone())));
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.input.push(input);
-                        }
-                        Inner::InjR(t) => {
-                            let ty_l = expression.arrow().target.as_sum().unwrap().0;
-                            self.tasks.push(Task::MakeRight(Arc::new(ty_l.clone())));
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.input.push(input);
-                        }
-                        Inner::Take(t) => {
-                            let (a, _) = input.as_product().ok_or(ErrorKind::WrongType)?;
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.input.push(a.shallow_clone());
-                        }
-                        Inner::Drop(t) => {
-                            let (_, b) = input.as_product().ok_or(ErrorKind::WrongType)?;
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.input.push(b.shallow_clone());
-                        }
-                        Inner::Comp(s, t) => {
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.tasks.push(Task::MoveLeftCompOutput);
-                            self.tasks.push(Task::Execute(Arc::clone(s)));
-                            self.input.push(input);
-                        }
-                        Inner::Pair(s, t) => {
-                            self.tasks.push(Task::MakeProduct);
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.tasks.push(Task::Execute(Arc::clone(s)));
-                            self.input.push(input.shallow_clone());
-                            self.input.push(input);
-                        }
-                        Inner::Case(..) | Inner::AssertL(..) | Inner::AssertR(..) => {
-                            let (sum_a_b, c) = input.as_product().ok_or(ErrorKind::WrongType)?;
-
-                            if let Inner::AssertL(_, cmr) = expression.inner() {
-                                if let Some(tracked_call) = self.debug_symbols.get(cmr) {
-                                    match tracked_call.map_value(
-                                        &simfony::value::StructuralValue::from(c.shallow_clone()),
-                                    ) {
-                                        Some(Either::Left(fallible_call)) => {
-                                            let replaced =
-                                                self.active_simfony_call.replace(fallible_call);
-                                            debug_assert!(replaced.is_none());
-                                            self.tasks.push(Task::ResetActiveSimfonyCall);
-                                        }
-                                        Some(Either::Right(debug_value)) => {
-                                            let s = format!(
-                                                "`{}` = `{}`",
-                                                debug_value.text(),
-                                                debug_value.value()
-                                            );
-                                            self.debug_output.push(s);
-                                        }
-                                        None => {}
+            self.execute_task(task, env)?;
+        }
+
+        debug_assert!(self.input.is_empty());
+        debug_assert_eq!(self.output.len(), 1);
+        debug_assert!(self.output.last().unwrap().is_unit());
+        Ok(())
+    }
+
+    /// Execute the next task.
+    ///
+    /// Return `false` if there are no more tasks.
+    pub fn advance(
+        &mut self,
+        env: &ElementsEnv<Arc<elements::Transaction>>,
+    ) -> Result<bool, ErrorKind> {
+        let task = match self.tasks.pop() {
+            Some(task) => task,
+            None => return Ok(false),
+        };
+        self.last_task = Some(task.clone());
+        self.execute_task(task, env)?;
+        Ok(true)
+    }
+
+    /// Return the state of the runner after the task that was executed last by [`Runner::advance`].
+    ///
+    /// Return `None` if no task was executed yet.
+    pub fn step_result(&self) -> Option<StepResult> {
+        let (cursor, jet, node_index) = match self.last_task.as_ref()? {
+            Task::Execute(expression) => {
+                let jet = match expression.inner() {
+                    Inner::Jet(jet) => Some(jet.to_string()),
+                    _ => None,
+                };
+                (
+                    DisplayInner::from(expression.as_ref()).to_string(),
+                    jet,
+                    self.node_index(expression),
+                )
+            }
+            task => (format!("{task:?}"), None, None),
+        };
+        Some(StepResult {
+            cursor,
+            jet,
+            node_index,
+            input_stack: self.input.clone(),
+            output_stack: self.output.clone(),
+        })
+    }
+
+    fn execute_task(
+        &mut self,
+        task: Task,
+        env: &ElementsEnv<Arc<elements::Transaction>>,
+    ) -> Result<(), ErrorKind> {
+        match task {
+            Task::Execute(expression) => {
+                self.resources.node_executions += 1;
+                let input = self.pop_input();
+                match expression.inner() {
+                    Inner::Iden => self.push_output(input),
+                    Inner::Unit => self.push_output(Value::unit()),
+                    Inner::InjL(t) => {
+                        let ty_r = expression.arrow().target.as_sum().unwrap().1;
+                        self.tasks.push(Task::MakeLeft(Arc::new(ty_r.clone())));
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.push_input(input);
+                    }
+                    Inner::InjR(t) => {
+                        let ty_l = expression.arrow().target.as_sum().unwrap().0;
+                        self.tasks.push(Task::MakeRight(Arc::new(ty_l.clone())));
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.push_input(input);
+                    }
+                    Inner::Take(t) => {
+                        let (a, _) = input.as_product().ok_or(ErrorKind::WrongType)?;
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.push_input(a.shallow_clone());
+                    }
+                    Inner::Drop(t) => {
+                        let (_, b) = input.as_product().ok_or(ErrorKind::WrongType)?;
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.push_input(b.shallow_clone());
+                    }
+                    Inner::Comp(s, t) => {
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.tasks.push(Task::MoveLeftCompOutput);
+                        self.tasks.push(Task::Execute(Arc::clone(s)));
+                        self.push_input(input);
+                    }
+                    Inner::Pair(s, t) => {
+                        self.tasks.push(Task::MakeProduct);
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.tasks.push(Task::Execute(Arc::clone(s)));
+                        self.push_input(input.shallow_clone());
+                        self.push_input(input);
+                    }
+                    Inner::Case(..) | Inner::AssertL(..) | Inner::AssertR(..) => {
+                        let (sum_a_b, c) = input.as_product().ok_or(ErrorKind::WrongType)?;
+
+                        if let Inner::AssertL(_, cmr) = expression.inner() {
+                            if let Some(tracked_call) = self.debug_symbols.get(cmr) {
+                                match tracked_call.map_value(
+                                    &simfony::value::StructuralValue::from(c.shallow_clone()),
+                                ) {
+                                    Some(Either::Left(fallible_call)) => {
+                                        let replaced =
+                                            self.active_simfony_call.replace(fallible_call);
+                                        debug_assert!(replaced.is_none());
+                                        self.tasks.push(Task::ResetActiveSimfonyCall);
+                                    }
+                                    Some(Either::Right(debug_value)) => {
+                                        let s = format!(
+                                            "`{}` = `{}`",
+                                            debug_value.text(),
+                                            debug_value.value()
+                                        );
+                                        self.debug_output.push(s);
                                     }
+                                    None => {}
                                 }
                             }
+                        }
 
-                            if let Some(a) = sum_a_b.as_left() {
-                                match expression.inner() {
-                                    Inner::Case(s, _) | Inner::AssertL(s, _) => {
-                                        self.tasks.push(Task::Execute(Arc::clone(s)));
-                                        self.input.push(Value::product(
-                                            a.shallow_clone(),
-                                            c.shallow_clone(),
-                                        ));
-                                    }
-                                    Inner::AssertR(_, _) => {
-                                        return Err(self.error(ErrorKind::AssertionFailed))
-                                    }
-                                    _ => unreachable!("Covered by outer match statement"),
+                        if let Some(a) = sum_a_b.as_left() {
+                            match expression.inner() {
+                                Inner::Case(s, _) | Inner::AssertL(s, _) => {
+                                    self.tasks.push(Task::Execute(Arc::clone(s)));
+                                    self.push_input(Value::product(
+                                        a.shallow_clone(),
+                                        c.shallow_clone(),
+                                    ));
                                 }
-                            } else if let Some(b) = sum_a_b.as_right() {
-                                match expression.inner() {
-                                    Inner::Case(_, t) | Inner::AssertR(_, t) => {
-                                        self.tasks.push(Task::Execute(Arc::clone(t)));
-                                        self.input.push(Value::product(
-                                            b.shallow_clone(),
-                                            c.shallow_clone(),
-                                        ));
-                                    }
-                                    Inner::AssertL(_, _) => {
-                                        return Err(self.error(ErrorKind::AssertionFailed))
-                                    }
-                                    _ => unreachable!("Covered by outer match statement"),
+                                Inner::AssertR(_, _) => {
+                                    return Err(self.error(ErrorKind::AssertionFailed))
+                                }
+                                _ => unreachable!("Covered by outer match statement"),
+                            }
+                        } else if let Some(b) = sum_a_b.as_right() {
+                            match expression.inner() {
+                                Inner::Case(_, t) | Inner::AssertR(_, t) => {
+                                    self.tasks.push(Task::Execute(Arc::clone(t)));
+                                    self.push_input(Value::product(
+                                        b.shallow_clone(),
+                                        c.shallow_clone(),
+                                    ));
+                                }
+                                Inner::AssertL(_, _) => {
+                                    return Err(self.error(ErrorKind::AssertionFailed))
                                 }
-                            } else {
-                                return Err(ErrorKind::WrongType);
+                                _ => unreachable!("Covered by outer match statement"),
                             }
+                        } else {
+                            return Err(ErrorKind::WrongType);
                         }
-                        Inner::Disconnect(s, t) => {
-                            self.tasks.push(Task::MakeProduct);
-                            self.tasks.push(Task::Execute(Arc::clone(t)));
-                            self.tasks.push(Task::MoveLeftDisconnectOutput);
-                            self.tasks.push(Task::Execute(Arc::clone(s)));
-                            let t_cmr = Value::u256(t.cmr().to_byte_array());
-                            self.input.push(Value::product(t_cmr, input));
+                    }
+                    Inner::Disconnect(s, t) => {
+                        self.tasks.push(Task::MakeProduct);
+                        self.tasks.push(Task::Execute(Arc::clone(t)));
+                        self.tasks.push(Task::MoveLeftDisconnectOutput);
+                        self.tasks.push(Task::Execute(Arc::clone(s)));
+                        let t_cmr = Value::u256(t.cmr().to_byte_array());
+                        self.push_input(Value::product(t_cmr, input));
+                    }
+                    Inner::Witness(value) => self.push_output(value.shallow_clone()),
+                    Inner::Fail(_) => return Err(self.error(ErrorKind::FailNode)),
+                    Inner::Jet(jet) => {
+                        let result = jet::execute_jet_with_env(jet, &input, env);
+                        self.resources.jet_calls += 1;
+                        self.resources.jet_cost += u64::from(jet::jet_cost(jet));
+                        if let Some(trace) = self.jet_trace.as_mut() {
+                            trace.push(JetCallEntry {
+                                jet_name: jet.to_string(),
+                                input: input.shallow_clone(),
+                                output: match &result {
+                                    Ok(output) => Ok(output.shallow_clone()),
+                                    Err(JetFailed) => Err(ErrorKind::JetFailed.to_string()),
+                                },
+                            });
                         }
-                        Inner::Witness(value) => self.output.push(value.shallow_clone()),
-                        Inner::Fail(_) => return Err(self.error(ErrorKind::FailNode)),
-                        Inner::Jet(jet) => match jet::execute_jet_with_env(jet, &input, env) {
-                            Ok(output) => self.output.push(output),
+                        match result {
+                            Ok(output) => self.push_output(output),
                             Err(JetFailed) => return Err(self.error(ErrorKind::JetFailed)),
-                        },
-                        Inner::Word(word) => self.output.push(word.as_value().shallow_clone()),
+                        }
                     }
+                    Inner::Word(word) => self.push_output(word.as_value().shallow_clone()),
                 }
-                Task::MoveLeftCompOutput => {
-                    let output = self.output.pop().unwrap();
-                    self.input.push(output);
-                }
-                Task::MoveLeftDisconnectOutput => {
-                    let prod_b_c = self.output.pop().unwrap();
-                    let (b, c) = prod_b_c.as_product().unwrap();
-                    self.output.push(b.shallow_clone());
-                    self.input.push(c.shallow_clone());
-                }
-                Task::MakeLeft(ty_r) => {
-                    let val_l = self.output.pop().unwrap();
-                    self.output.push(Value::left(val_l, ty_r));
-                }
-                Task::MakeRight(ty_l) => {
-                    let val_r = self.output.pop().unwrap();
-                    self.output.push(Value::right(ty_l, val_r));
-                }
-                Task::MakeProduct => {
-                    let b = self.output.pop().unwrap();
-                    let a = self.output.pop().unwrap();
-                    self.output.push(Value::product(a, b));
-                }
-                Task::ResetActiveSimfonyCall => self.active_simfony_call = None,
             }
+            Task::MoveLeftCompOutput => {
+                let output = self.pop_output();
+                self.push_input(output);
+            }
+            Task::MoveLeftDisconnectOutput => {
+                let prod_b_c = self.pop_output();
+                let (b, c) = prod_b_c.as_product().unwrap();
+                self.push_output(b.shallow_clone());
+                self.push_input(c.shallow_clone());
+            }
+            Task::MakeLeft(ty_r) => {
+                let val_l = self.pop_output();
+                self.push_output(Value::left(val_l, ty_r));
+            }
+            Task::MakeRight(ty_l) => {
+                let val_r = self.pop_output();
+                self.push_output(Value::right(ty_l, val_r));
+            }
+            Task::MakeProduct => {
+                let b = self.pop_output();
+                let a = self.pop_output();
+                self.push_output(Value::product(a, b));
+            }
+            Task::ResetActiveSimfonyCall => self.active_simfony_call = None,
         }
-
-        debug_assert!(self.input.is_empty());
-        debug_assert_eq!(self.output.len(), 1);
-        debug_assert!(self.output.pop().unwrap().is_unit());
         Ok(())
     }
 
+    fn push_input(&mut self, value: Value) {
+        self.add_cells(&value);
+        self.input.push(value);
+    }
+
+    fn push_output(&mut self, value: Value) {
+        self.add_cells(&value);
+        self.output.push(value);
+    }
+
+    fn pop_input(&mut self) -> Value {
+        let value = self.input.pop().unwrap();
+        self.cells -= value.ty().bit_width();
+        value
+    }
+
+    fn pop_output(&mut self) -> Value {
+        let value = self.output.pop().unwrap();
+        self.cells -= value.ty().bit_width();
+        value
+    }
+
+    fn add_cells(&mut self, value: &Value) {
+        self.cells += value.ty().bit_width();
+        self.resources.max_cells = self.resources.max_cells.max(self.cells);
+    }
+
     /// Try to return an error with Simfony debug information included.
     /// Otherwise, return the original error.
     fn error(&self, error: ErrorKind) -> ErrorKind {
@@ -335,4 +536,59 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn cells_of_case_heavy_example() {
+        let signing_keys = SigningKeys::default();
+        let hashed_data = HashedData::default();
+        let example = examples::get("🧨 Hash loop").unwrap();
+        let (satisfied, tx_env) = satisfied_and_tx_env(example, &signing_keys, &hashed_data);
+        let mut runner = Runner::for_program(satisfied);
+
+        runner.run(&tx_env).expect("example should succeed");
+        // A miscounted stack would wrap around to almost `usize::MAX` cells
+        let max_cells = runner.resources().max_cells;
+        assert!(0 < max_cells);
+        assert!(max_cells < 1 << 24);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn step_through_example() {
+        let signing_keys = SigningKeys::default();
+        let hashed_data = HashedData::default();
+        let example = examples::get("✍️️ P2PK").unwrap();
+        let (satisfied, tx_env) = satisfied_and_tx_env(example, &signing_keys, &hashed_data);
+        let mut runner = Runner::for_program(satisfied);
+
+        while runner.advance(&tx_env).expect("example should succeed") {}
+        let last_step = runner
+            .step_result()
+            .expect("program should take at least one step");
+        assert!(last_step.input_stack.is_empty());
+        assert_eq!(1, last_step.output_stack.len());
+        assert!(last_step.output_stack[0].is_unit());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn node_indices_in_post_order() {
+        let signing_keys = SigningKeys::default();
+        let hashed_data = HashedData::default();
+        let example = examples::get("✍️️ P2PK").unwrap();
+        let (satisfied, tx_env) = satisfied_and_tx_env(example, &signing_keys, &hashed_data);
+        let mut runner = Runner::for_program(satisfied);
+
+        // The root comes last in post order
+        let node_count = runner.node_count();
+        assert_eq!(Some(node_count - 1), runner.next_node_index());
+        while runner.advance(&tx_env).expect("example should succeed") {
+            let step = runner.step_result().expect("a task was executed");
+            if let Some(index) = step.node_index {
+                assert!(index < node_count);
+            }
+        }
+        assert_eq!(None, runner.next_node_index());
+    }
 }
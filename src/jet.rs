@@ -5,7 +5,7 @@ use simplicity::ffi::ffi::UWORD;
 use simplicity::ffi::CFrameItem;
 use simplicity::jet::Jet;
 use simplicity::types::Final;
-use simplicity::Value;
+use simplicity::{Cost, Value};
 
 pub struct JetFailed;
 
@@ -67,6 +67,27 @@ fn value_from_frame(ty: &Final, buffer: &mut [UWORD]) -> Value {
     Value::from_padded_bits(&mut it, ty).expect("Jets return values that fit their output type")
 }
 
+/// Return the cost in milliweight.
+///
+/// [`Cost`] has no accessor for its milliweight,
+/// so search for the milliweight whose cost is equal to the given cost.
+pub fn milliweight(cost: Cost) -> u32 {
+    let (mut low, mut high) = (0, u32::MAX);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        match Cost::from_milliweight(middle) < cost {
+            true => low = middle + 1,
+            false => high = middle,
+        }
+    }
+    low
+}
+
+/// Return the cost of the jet in milliweight.
+pub fn jet_cost<J: Jet>(jet: &J) -> u32 {
+    milliweight(jet.cost())
+}
+
 /// Execute a jet on an input and inside an environment. Return the output.
 pub fn execute_jet_with_env<J: Jet>(
     jet: &J,
@@ -99,4 +120,15 @@ mod tests {
     fn wasm_sanity_checks() {
         assert!(simplicity::ffi::c_jets::sanity_checks());
     }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn milliweight_of_cost() {
+        for milliweight in [0, 1, 183, 4_000_050_000, u32::MAX] {
+            assert_eq!(
+                milliweight,
+                super::milliweight(Cost::from_milliweight(milliweight))
+            );
+        }
+    }
 }
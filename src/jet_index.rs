@@ -0,0 +1,184 @@
+//! Names, type signatures and descriptions of Elements jets,
+//! for auto-completion and hover documentation.
+
+// Generated by `build.rs` as `pub const JETS: &[(&str, &str)]`, sorted by name
+include!(concat!(env!("OUT_DIR"), "/jet_index.rs"));
+
+/// Maximum number of completions that are suggested at once.
+pub const MAX_COMPLETIONS: usize = 10;
+
+/// Return the jets whose names start with the given prefix, as `(name, type_signature)`.
+pub fn complete(prefix: &str) -> Vec<(&'static str, &'static str)> {
+    JETS.iter()
+        .filter(|(name, _)| name.starts_with(prefix))
+        .take(MAX_COMPLETIONS)
+        .copied()
+        .collect()
+}
+
+/// Return the type signature of the jet with the given name.
+pub fn signature(name: &str) -> Option<&'static str> {
+    JETS.binary_search_by(|(jet, _)| (*jet).cmp(name))
+        .ok()
+        .map(|index| JETS[index].1)
+}
+
+/// One-sentence descriptions of jets, keyed by jet name or by the common prefix of a jet family.
+///
+/// The longest matching key describes a jet.
+const DESCRIPTIONS: &[(&str, &str)] = &[
+    ("add_", "Add two integers and return the carry bit and the sum."),
+    ("all_", "Check whether all bits of the value are set."),
+    ("and_", "Compute the bitwise AND of two values."),
+    ("annex_hash", "Add the hash of an optional annex to a SHA-256 hashing context."),
+    ("asset_amount_hash", "Add an asset and an amount to a SHA-256 hashing context."),
+    ("bip_0340_verify", "Verify a BIP-340 Schnorr signature of a message under a public key, failing if it is invalid."),
+    ("build_tapbranch", "Compute the tap branch hash of two child hashes."),
+    ("build_tapleaf_simplicity", "Compute the tap leaf hash of a Simplicity program from its CMR."),
+    ("build_taptweak", "Compute the taproot output key from an internal key and a merkle root."),
+    ("calculate_", "Compute an asset ID, a reissuance token or an issuance entropy as defined by Elements."),
+    ("ch_", "Select the bits of the second value where the first value is set and the bits of the third value elsewhere."),
+    ("check_lock_", "Fail unless the lock of the transaction is at least the given value."),
+    ("check_sig_verify", "Verify a Schnorr signature of a 64-byte message under a public key, failing if it is invalid."),
+    ("complement_", "Flip all bits of the value."),
+    ("current_", "Return a property of the input that is currently being spent."),
+    ("decompress", "Decompress an elliptic curve point from its x-coordinate and parity."),
+    ("decrement_", "Subtract one from an integer and return the borrow bit and the result."),
+    ("div_mod_", "Divide the first integer by the second and return the quotient and the remainder."),
+    ("divide_", "Divide the first integer by the second and return the quotient."),
+    ("divides_", "Check whether the first integer divides the second."),
+    ("eq_", "Check whether two values are equal."),
+    ("fe_", "Perform an operation on elements of the secp256k1 base field."),
+    ("full_add_", "Add two integers and an input carry and return the carry bit and the sum."),
+    ("full_decrement_", "Subtract the input borrow bit from an integer and return the borrow bit and the result."),
+    ("full_increment_", "Add the input carry bit to an integer and return the carry bit and the result."),
+    ("full_left_shift_", "Shift the first value left, filling in the bits of the second value, and return the bits that are shifted out."),
+    ("full_multiply_", "Multiply two integers, add two more integers and return the full-width result."),
+    ("full_right_shift_", "Shift the second value right, filling in the bits of the first value, and return the bits that are shifted out."),
+    ("full_subtract_", "Subtract the second integer and an input borrow from the first and return the borrow bit and the difference."),
+    ("ge_", "Perform an operation on points of secp256k1 in affine coordinates."),
+    ("gej_", "Perform an operation on points of secp256k1 in Jacobian coordinates."),
+    ("generate", "Multiply the secp256k1 generator by a scalar."),
+    ("genesis_block_hash", "Return the hash of the genesis block of the chain."),
+    ("hash_to_curve", "Map a 32-byte hash to a point of secp256k1."),
+    ("high_", "Return the value with all bits set."),
+    ("increment_", "Add one to an integer and return the carry bit and the result."),
+    ("input_", "Return a property of the inputs of the transaction."),
+    ("internal_key", "Return the internal key of the taproot output that is being spent."),
+    ("is_one_", "Check whether the integer is one."),
+    ("is_zero_", "Check whether the integer is zero."),
+    ("issuance", "Return a property of the asset issuances of the transaction."),
+    ("le_", "Check whether the first integer is less than or equal to the second."),
+    ("left_extend_", "Extend the value to a wider type by repeating its leftmost bit."),
+    ("left_pad_high_", "Pad the value on the left with set bits."),
+    ("left_pad_low_", "Pad the value on the left with unset bits."),
+    ("left_rotate_", "Rotate the bits of the value to the left by the given amount."),
+    ("left_shift_", "Shift the bits of the value to the left by the given amount, filling in unset bits."),
+    ("left_shift_with_", "Shift the bits of the value to the left by the given amount, filling in the given bit."),
+    ("leftmost_", "Return the leftmost bits of the value."),
+    ("linear_combination_1", "Compute the linear combination of a point and the generator."),
+    ("linear_verify_1", "Verify that a point is the linear combination of another point and the generator, failing otherwise."),
+    ("lock_time", "Return the lock time of the transaction."),
+    ("low_", "Return the value with all bits unset."),
+    ("lt_", "Check whether the first integer is less than the second."),
+    ("maj_", "Return the bitwise majority of three values."),
+    ("max_", "Return the larger of two integers."),
+    ("median_", "Return the median of three integers."),
+    ("min_", "Return the smaller of two integers."),
+    ("modulo_", "Return the remainder of dividing the first integer by the second."),
+    ("multiply_", "Multiply two integers and return the full-width product."),
+    ("negate_", "Negate the integer in two's complement and return the borrow bit and the result."),
+    ("nonce_hash", "Add an optional nonce to a SHA-256 hashing context."),
+    ("num_inputs", "Return the number of inputs of the transaction."),
+    ("num_outputs", "Return the number of outputs of the transaction."),
+    ("one_", "Return the integer one."),
+    ("or_", "Compute the bitwise OR of two values."),
+    ("outpoint_hash", "Add an outpoint to a SHA-256 hashing context."),
+    ("output_", "Return a property of the outputs of the transaction."),
+    ("parse_lock", "Parse a lock time into a block height or a timestamp."),
+    ("parse_sequence", "Parse a sequence number into a relative block height or duration, if it is enabled."),
+    ("point_verify_1", "Verify that a compressed point is the linear combination of another point and the generator, failing otherwise."),
+    ("right_extend_", "Extend the value to a wider type by repeating its rightmost bit."),
+    ("right_pad_high_", "Pad the value on the right with set bits."),
+    ("right_pad_low_", "Pad the value on the right with unset bits."),
+    ("right_rotate_", "Rotate the bits of the value to the right by the given amount."),
+    ("right_shift_", "Shift the bits of the value to the right by the given amount, filling in unset bits."),
+    ("right_shift_with_", "Shift the bits of the value to the right by the given amount, filling in the given bit."),
+    ("rightmost_", "Return the rightmost bits of the value."),
+    ("scalar_", "Perform an operation on scalars modulo the order of secp256k1."),
+    ("scale", "Multiply a point by a scalar."),
+    ("script_cmr", "Return the CMR of the program that is being run."),
+    ("sha_256_block", "Compress a 512-bit block into a SHA-256 midstate."),
+    ("sha_256_ctx_8_add_", "Add bytes to a SHA-256 hashing context."),
+    ("sha_256_ctx_8_finalize", "Finish a SHA-256 hashing context and return the hash."),
+    ("sha_256_ctx_8_init", "Create an empty SHA-256 hashing context."),
+    ("sha_256_iv", "Return the initial midstate of SHA-256."),
+    ("sig_all_hash", "Return the hash of the transaction data that is signed with SIGHASH_ALL."),
+    ("some_", "Check whether any bit of the value is set."),
+    ("subtract_", "Subtract the second integer from the first and return the borrow bit and the difference."),
+    ("swu", "Map a field element to a point of secp256k1 using the Shallue-van de Woestijne method."),
+    ("tap_env_hash", "Return the hash of the taproot data of the input that is being spent."),
+    ("tapleaf_", "Return a property of the tap leaf of the program that is being run."),
+    ("tappath", "Return the hash at the given position of the merkle path to the tap leaf of the program, if any."),
+    ("tx_is_final", "Check whether the sequence numbers of all inputs disable the lock time."),
+    ("tx_lock_", "Return the lock of the transaction if it is of the given kind."),
+    ("verify", "Fail unless the bit is set."),
+    ("version", "Return the version of the transaction."),
+    ("xor_", "Compute the bitwise XOR of two values."),
+    ("xor_xor_", "Compute the bitwise XOR of three values."),
+];
+
+/// Return the one-sentence description of the jet with the given name, if any.
+pub fn description(name: &str) -> Option<&'static str> {
+    DESCRIPTIONS
+        .iter()
+        .filter(|(key, _)| name.starts_with(key))
+        .max_by_key(|(key, _)| key.len())
+        .map(|(_, description)| *description)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn index_contains_jets() {
+        let (_, signature) = JETS
+            .iter()
+            .find(|(name, _)| *name == "add_8")
+            .expect("add_8 should be indexed");
+        assert_eq!("fn(u8, u8) -> (bool, u8)", *signature);
+        assert!(JETS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn complete_by_prefix() {
+        let completions = complete("sha_256_ctx_8_add_");
+        assert!(!completions.is_empty());
+        assert!(completions.len() <= MAX_COMPLETIONS);
+        assert!(completions
+            .iter()
+            .all(|(name, _)| name.starts_with("sha_256_ctx_8_add_")));
+        assert!(complete("no_such_jet").is_empty());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn signature_and_description() {
+        assert_eq!(Some("fn(u8, u8) -> (bool, u8)"), signature("add_8"));
+        assert_eq!(None, signature("no_such_jet"));
+        assert_eq!(
+            Some("Add bytes to a SHA-256 hashing context."),
+            description("sha_256_ctx_8_add_1")
+        );
+        // The longest key wins
+        assert_eq!(
+            Some("Compute the bitwise XOR of three values."),
+            description("xor_xor_8")
+        );
+        assert_eq!(None, description("no_such_jet"));
+        assert!(DESCRIPTIONS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
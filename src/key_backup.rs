@@ -0,0 +1,99 @@
+//! Password-encrypted backups of named secret keys.
+//!
+//! The key for AES-256-GCM is derived from the password via Argon2.
+//! The backup is a JSON object that contains the Argon2 salt, the AES nonce and the ciphertext.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::Argon2;
+use hex_conservative::{DisplayHex, FromHex};
+use serde::{Deserialize, Serialize};
+
+pub const SALT_LEN: usize = 16;
+pub const NONCE_LEN: usize = 12;
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedBackup {
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BackupEntry {
+    name: String,
+    secret_key: String,
+}
+
+fn cipher(password: &str, salt: &[u8]) -> Result<Aes256Gcm, String> {
+    let mut key = [0; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|error| error.to_string())?;
+    Ok(Aes256Gcm::new(&key.into()))
+}
+
+/// Encrypt the named secret keys under the password.
+pub fn encrypt_keys(
+    keys: &[(String, [u8; 32])],
+    password: &str,
+    salt: [u8; SALT_LEN],
+    nonce: [u8; NONCE_LEN],
+) -> Result<String, String> {
+    let entries: Vec<BackupEntry> = keys
+        .iter()
+        .map(|(name, secret_key)| BackupEntry {
+            name: name.clone(),
+            secret_key: secret_key.as_hex().to_string(),
+        })
+        .collect();
+    let plaintext = serde_json::to_vec(&entries).map_err(|error| error.to_string())?;
+    let ciphertext = cipher(password, &salt)?
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|_| "Failed to encrypt keys".to_string())?;
+    let backup = EncryptedBackup {
+        salt: salt.as_hex().to_string(),
+        nonce: nonce.as_hex().to_string(),
+        ciphertext: ciphertext.as_hex().to_string(),
+    };
+    serde_json::to_string_pretty(&backup).map_err(|error| error.to_string())
+}
+
+/// Decrypt the named secret keys from a backup using the password.
+///
+/// Return an error if the backup is ill-formatted or if the password is wrong.
+pub fn decrypt_keys(backup: &str, password: &str) -> Result<Vec<(String, [u8; 32])>, String> {
+    let backup: EncryptedBackup =
+        serde_json::from_str(backup).map_err(|error| error.to_string())?;
+    let salt = Vec::<u8>::from_hex(&backup.salt).map_err(|error| error.to_string())?;
+    let nonce = <[u8; NONCE_LEN]>::from_hex(&backup.nonce).map_err(|error| error.to_string())?;
+    let ciphertext = Vec::<u8>::from_hex(&backup.ciphertext).map_err(|error| error.to_string())?;
+    let plaintext = cipher(password, &salt)?
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "Wrong password or corrupted backup".to_string())?;
+    let entries: Vec<BackupEntry> =
+        serde_json::from_slice(&plaintext).map_err(|error| error.to_string())?;
+    entries
+        .into_iter()
+        .map(|entry| {
+            <[u8; 32]>::from_hex(&entry.secret_key)
+                .map(|secret_key| (entry.name, secret_key))
+                .map_err(|error| error.to_string())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn encrypt_decrypt_roundtrip() {
+        let keys = vec![("Alice".to_string(), [1; 32]), ("Bob".to_string(), [2; 32])];
+        let backup = encrypt_keys(&keys, "correct horse", [3; SALT_LEN], [4; NONCE_LEN]).unwrap();
+        assert_eq!(Ok(keys), decrypt_keys(&backup, "correct horse"));
+        assert!(decrypt_keys(&backup, "wrong password").is_err());
+        assert!(decrypt_keys("{}", "correct horse").is_err());
+    }
+}
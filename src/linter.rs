@@ -0,0 +1,359 @@
+//! Linter that warns about common mistakes in Simfony programs before compilation.
+//!
+//! The linter works on tokens instead of a parsed program,
+//! so it also runs on programs that do not compile.
+
+use std::collections::HashMap;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum LintSeverity {
+    Warning,
+    Error,
+}
+
+/// Warning that is attached to a span of the program text.
+///
+/// Lines and columns start at 1. The column range is exclusive at the end.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LintWarning {
+    pub line: usize,
+    pub col_start: usize,
+    pub col_end: usize,
+    pub message: String,
+    pub severity: LintSeverity,
+}
+
+/// Identifier or punctuation of the program text.
+///
+/// Comments, whitespace and number literals are skipped.
+#[derive(Copy, Clone, Debug)]
+struct Token<'a> {
+    text: &'a str,
+    line: usize,
+    col: usize,
+}
+
+impl Token<'_> {
+    fn is_ident(&self) -> bool {
+        self.text
+            .starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+    }
+
+    fn warning(&self, message: String, severity: LintSeverity) -> LintWarning {
+        LintWarning {
+            line: self.line,
+            col_start: self.col,
+            col_end: self.col + self.text.chars().count(),
+            message,
+            severity,
+        }
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+fn tokenize(source: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut line = 1;
+    let mut col = 1;
+    let mut rest = source;
+
+    while let Some(c) = rest.chars().next() {
+        let len = if rest.starts_with("//") {
+            rest.find('\n').unwrap_or(rest.len())
+        } else if rest.starts_with("/*") {
+            rest.find("*/").map(|end| end + 2).unwrap_or(rest.len())
+        } else if c.is_whitespace() {
+            c.len_utf8()
+        } else if c.is_ascii_digit() {
+            rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len())
+        } else {
+            let len = if c.is_ascii_alphabetic() || c == '_' {
+                rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len())
+            } else if rest.starts_with("::") {
+                2
+            } else {
+                c.len_utf8()
+            };
+            tokens.push(Token {
+                text: &rest[..len],
+                line,
+                col,
+            });
+            len
+        };
+
+        for skipped in rest[..len].chars() {
+            match skipped {
+                '\n' => {
+                    line += 1;
+                    col = 1;
+                }
+                _ => col += 1,
+            }
+        }
+        rest = &rest[len..];
+    }
+
+    tokens
+}
+
+/// Check the given Simfony program for common mistakes.
+///
+/// Warnings are sorted by their position in the program.
+pub fn lint(source: &str) -> Vec<LintWarning> {
+    let tokens = tokenize(source);
+    let mut warnings = vec![];
+    warnings.extend(unused_let_bindings(&tokens));
+    warnings.extend(unused_witnesses(&tokens));
+    warnings.extend(failing_assertions(&tokens));
+    warnings.sort_by_key(|warning| (warning.line, warning.col_start));
+    warnings
+}
+
+/// Variable that is bound by a `let` statement.
+struct Binding<'a> {
+    token: Token<'a>,
+    used: bool,
+}
+
+/// Find the index of the `;` that ends the statement which starts at the given index.
+fn statement_end(tokens: &[Token], start: usize) -> usize {
+    let mut depth = 0usize;
+    for (index, token) in tokens.iter().enumerate().skip(start) {
+        match token.text {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" if depth == 0 => return index,
+            ")" | "]" | "}" => depth -= 1,
+            ";" if depth == 0 => return index,
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+/// Warn about `let` bindings whose variables are never used.
+///
+/// Variables whose name starts with an underscore are exempt.
+/// Each block opens a new scope and later bindings shadow earlier ones.
+fn unused_let_bindings(tokens: &[Token]) -> Vec<LintWarning> {
+    let mut bindings: Vec<Binding> = vec![];
+    // Bindings that become visible after the statement at the given index ends
+    let mut pending: Vec<(usize, Vec<usize>)> = vec![];
+    let mut visible: HashMap<&str, Vec<usize>> = HashMap::new();
+    let mut scopes: Vec<Vec<usize>> = vec![vec![]];
+    let mut pattern_end = 0;
+
+    for (index, token) in tokens.iter().enumerate() {
+        while let Some(position) = pending.iter().position(|(end, _)| *end < index) {
+            let (_, ids) = pending.remove(position);
+            for id in ids {
+                visible.entry(bindings[id].token.text).or_default().push(id);
+                scopes.last_mut().expect("there is a scope").push(id);
+            }
+        }
+
+        match token.text {
+            "let" => {
+                let end = statement_end(tokens, index + 1);
+                let pattern_len = tokens[index + 1..end]
+                    .iter()
+                    .take_while(|token| token.text != ":" && token.text != "=")
+                    .count();
+                pattern_end = index + 1 + pattern_len;
+                let ids = tokens[index + 1..pattern_end]
+                    .iter()
+                    .filter(|token| token.is_ident() && !token.text.starts_with('_'))
+                    .map(|token| {
+                        bindings.push(Binding {
+                            token: *token,
+                            used: false,
+                        });
+                        bindings.len() - 1
+                    })
+                    .collect::<Vec<_>>();
+                pending.push((end, ids));
+            }
+            "{" => scopes.push(vec![]),
+            "}" => {
+                for id in scopes.pop().unwrap_or_default() {
+                    if let Some(ids) = visible.get_mut(bindings[id].token.text) {
+                        ids.retain(|visible_id| *visible_id != id);
+                    }
+                }
+                if scopes.is_empty() {
+                    scopes.push(vec![]);
+                }
+            }
+            _ if token.is_ident() && pattern_end <= index => {
+                let is_path = (index > 0 && tokens[index - 1].text == "::")
+                    || tokens.get(index + 1).is_some_and(|next| next.text == "::");
+                if !is_path {
+                    if let Some(id) = visible.get(token.text).and_then(|ids| ids.last()) {
+                        bindings[*id].used = true;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    bindings
+        .into_iter()
+        .filter(|binding| !binding.used)
+        .map(|binding| {
+            let message = format!("Variable `{}` is never used", binding.token.text);
+            binding.token.warning(message, LintSeverity::Warning)
+        })
+        .collect()
+}
+
+/// Warn about witnesses that are declared in the `witness` module but never used.
+fn unused_witnesses(tokens: &[Token]) -> Vec<LintWarning> {
+    let used = tokens
+        .windows(3)
+        .filter(|window| window[0].text == "witness" && window[1].text == "::")
+        .map(|window| window[2].text)
+        .collect::<Vec<_>>();
+    let mut warnings = vec![];
+
+    for (index, window) in tokens.windows(3).enumerate() {
+        if window[0].text != "mod" || window[1].text != "witness" || window[2].text != "{" {
+            continue;
+        }
+        let mut depth = 0usize;
+        for (position, token) in tokens.iter().enumerate().skip(index + 3) {
+            match token.text {
+                "{" | "(" | "[" => depth += 1,
+                "}" if depth == 0 => break,
+                "}" | ")" | "]" => depth = depth.saturating_sub(1),
+                "const" if depth == 0 => {
+                    if let Some(name) = tokens.get(position + 1).filter(|name| name.is_ident()) {
+                        if !used.contains(&name.text) {
+                            let message = format!("Witness `{}` is never used", name.text);
+                            warnings.push(name.warning(message, LintSeverity::Warning));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Report assertions that always fail, such as `assert!(false)`.
+fn failing_assertions(tokens: &[Token]) -> Vec<LintWarning> {
+    tokens
+        .windows(5)
+        .filter(|window| {
+            let texts = window.iter().map(|token| token.text).collect::<Vec<_>>();
+            texts == ["assert", "!", "(", "false", ")"]
+        })
+        .map(|window| {
+            let message = "This assertion always fails".to_string();
+            window[3].warning(message, LintSeverity::Error)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples;
+
+    fn messages(source: &str) -> Vec<(usize, String, LintSeverity)> {
+        lint(source)
+            .into_iter()
+            .map(|warning| (warning.line, warning.message, warning.severity))
+            .collect()
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn unused_let_binding() {
+        let source = "fn main() {\n    let x: u8 = 1;\n    let (y, _z): (u8, u8) = (2, 3);\n    assert!(jet::eq_8(y, 2));\n}";
+        assert_eq!(
+            vec![(
+                2,
+                "Variable `x` is never used".to_string(),
+                LintSeverity::Warning
+            )],
+            messages(source)
+        );
+        let warning = &lint(source)[0];
+        assert_eq!((9, 10), (warning.col_start, warning.col_end));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn shadowed_binding() {
+        let used = "fn main() {\n    let ctx: Ctx8 = jet::sha_256_ctx_8_init();\n    let ctx: Ctx8 = jet::sha_256_ctx_8_add_1(ctx, 0x01);\n    let _hash: u256 = jet::sha_256_ctx_8_finalize(ctx);\n}";
+        assert!(lint(used).is_empty());
+
+        let unused =
+            "fn main() {\n    let x: u8 = 1;\n    let x: u8 = 2;\n    assert!(jet::eq_8(x, 2));\n}";
+        assert_eq!(
+            vec![(
+                2,
+                "Variable `x` is never used".to_string(),
+                LintSeverity::Warning
+            )],
+            messages(unused)
+        );
+
+        let scoped = "fn main() {\n    let x: u8 = 1;\n    {\n        let x: u8 = 2;\n    };\n    assert!(jet::eq_8(x, 1));\n}";
+        assert_eq!(
+            vec![(
+                4,
+                "Variable `x` is never used".to_string(),
+                LintSeverity::Warning
+            )],
+            messages(scoped)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn unused_witness() {
+        let source = "mod witness {\n    const SIG: Signature = 0x00;\n    const EXTRA: u8 = 1;\n}\n\nfn main() {\n    let _sig: Signature = witness::SIG;\n}";
+        assert_eq!(
+            vec![(
+                3,
+                "Witness `EXTRA` is never used".to_string(),
+                LintSeverity::Warning
+            )],
+            messages(source)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn failing_assertion() {
+        let source = "fn main() {\n    // assert!(false)\n    assert!(false);\n}";
+        assert_eq!(
+            vec![(
+                3,
+                "This assertion always fails".to_string(),
+                LintSeverity::Error
+            )],
+            messages(source)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn examples_have_no_errors() {
+        for name in examples::keys() {
+            let program = examples::get(name).unwrap().template_text();
+            let errors = lint(program)
+                .into_iter()
+                .filter(|warning| warning.severity == LintSeverity::Error)
+                .collect::<Vec<_>>();
+            assert!(errors.is_empty(), "{name}: {errors:?}");
+        }
+    }
+}
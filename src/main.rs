@@ -1,7 +1,14 @@
 mod components;
+mod config;
 mod examples;
+mod formatter;
 mod function;
 mod jet;
+mod jet_index;
+mod key_backup;
+mod linter;
+mod routing;
+mod test_store;
 mod transaction;
 mod util;
 
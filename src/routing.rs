@@ -0,0 +1,136 @@
+use crate::examples;
+
+/// Maximum length of a program that is passed in the query string, in bytes.
+///
+/// Longer URLs are not reliably supported by browsers and servers.
+const MAX_SOURCE_LEN: usize = 4096;
+
+/// Initial program that is requested by the query string of the URL.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Route {
+    /// Open the example of the given name.
+    Example(&'static str),
+    /// Open the given program source.
+    Source(String),
+}
+
+/// Convert an example name into the form that is used in URLs.
+///
+/// Emojis and spaces are dropped, so "💸 Hodl vault" becomes "hodl_vault".
+pub fn example_slug(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<String>>()
+        .join("_")
+}
+
+/// Decode a percent-encoded query string component.
+///
+/// Return `None` if the encoding is ill-formatted or not UTF-8.
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(byte) = iter.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let high = char::from(iter.next()?).to_digit(16)?;
+                let low = char::from(iter.next()?).to_digit(16)?;
+                bytes.push((high * 16 + low) as u8);
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Parse the query string of a URL, including the leading `?`.
+///
+/// Return `None` if there is no route or if the route is invalid.
+pub fn parse_query(search: &str) -> Option<Route> {
+    let query = search.strip_prefix('?').unwrap_or(search);
+    for pair in query.split('&') {
+        let (key, value) = match pair.split_once('=') {
+            Some(split) => split,
+            None => continue,
+        };
+        match key {
+            "example" => {
+                let slug = percent_decode(value)?;
+                return examples::keys()
+                    .find(|name| example_slug(name) == slug)
+                    .map(Route::Example);
+            }
+            "source" => {
+                if MAX_SOURCE_LEN < value.len() {
+                    return None;
+                }
+                return percent_decode(value).map(Route::Source);
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse the query string of the current URL.
+pub fn route_from_location() -> Option<Route> {
+    let search = web_sys::window()?.location().search().ok()?;
+    parse_query(&search)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn example_slugs_are_unique() {
+        let slugs = examples::keys()
+            .map(example_slug)
+            .collect::<std::collections::HashSet<String>>();
+        assert_eq!(examples::keys().len(), slugs.len());
+        assert!(slugs.contains("hodl_vault"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_valid_query() {
+        assert_eq!(
+            Some(Route::Example("⚡ HTLC")),
+            parse_query("?example=htlc")
+        );
+        assert_eq!(
+            Some(Route::Source("fn main() {}".to_string())),
+            parse_query("?foo=bar&source=fn+main%28%29%20%7B%7D")
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_missing_query() {
+        assert_eq!(None, parse_query(""));
+        assert_eq!(None, parse_query("?"));
+        assert_eq!(None, parse_query("?foo=bar"));
+        assert_eq!(None, parse_query("?example=does_not_exist"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_malformed_query() {
+        assert_eq!(None, parse_query("?example"));
+        assert_eq!(None, parse_query("?source=%4"));
+        assert_eq!(None, parse_query("?source=%zz"));
+        assert_eq!(None, parse_query("?source=%ff%fe"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn parse_oversized_query() {
+        let source = "a".repeat(MAX_SOURCE_LEN);
+        assert!(parse_query(&format!("?source={source}")).is_some());
+        let source = "a".repeat(MAX_SOURCE_LEN + 1);
+        assert_eq!(None, parse_query(&format!("?source={source}")));
+    }
+}
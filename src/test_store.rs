@@ -0,0 +1,115 @@
+//! Named test cases that replay a program with a saved witness.
+
+/// Simfony text of a `mod witness { ... }` block.
+pub type WitnessText = String;
+
+/// Named witnesses that the user saved as test cases.
+///
+/// Test cases are sorted by name and names are unique.
+/// The store is persisted in the browser's local storage as JSON.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TestCaseStore {
+    cases: Vec<(String, WitnessText)>,
+}
+
+impl TestCaseStore {
+    /// Return the saved test cases, sorted by name.
+    pub fn cases(&self) -> &[(String, WitnessText)] {
+        &self.cases
+    }
+
+    /// Return the witness of the test case of the given name.
+    pub fn get(&self, name: &str) -> Option<&WitnessText> {
+        self.position(name).ok().map(|index| &self.cases[index].1)
+    }
+
+    /// Save the witness as test case of the given name.
+    ///
+    /// Replaces any existing test case of the same name.
+    pub fn insert(&mut self, name: String, witness: WitnessText) {
+        match self.position(&name) {
+            Ok(index) => self.cases[index].1 = witness,
+            Err(index) => self.cases.insert(index, (name, witness)),
+        }
+    }
+
+    /// Delete the test case of the given name.
+    pub fn remove(&mut self, name: &str) {
+        if let Ok(index) = self.position(name) {
+            self.cases.remove(index);
+        }
+    }
+
+    fn position(&self, name: &str) -> Result<usize, usize> {
+        self.cases
+            .binary_search_by(|(case_name, _)| case_name.as_str().cmp(name))
+    }
+
+    /// Parse test cases from a JSON array of `[name, witness]` pairs.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let cases: Vec<(String, WitnessText)> = serde_json::from_str(json)?;
+        let mut store = Self::default();
+        for (name, witness) in cases {
+            store.insert(name, witness);
+        }
+        Ok(store)
+    }
+
+    /// Serialize the test cases as JSON array of `[name, witness]` pairs.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.cases).expect("string pairs should serialize")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn insert_and_remove() {
+        let mut store = TestCaseStore::default();
+        store.insert(
+            "zero".to_string(),
+            "mod witness { const X: u8 = 0; }".to_string(),
+        );
+        store.insert(
+            "max".to_string(),
+            "mod witness { const X: u8 = 1; }".to_string(),
+        );
+        store.insert(
+            "max".to_string(),
+            "mod witness { const X: u8 = 255; }".to_string(),
+        );
+
+        let names = store
+            .cases()
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["max", "zero"], names);
+        assert_eq!(
+            Some("mod witness { const X: u8 = 255; }"),
+            store.get("max").map(String::as_str)
+        );
+
+        store.remove("max");
+        store.remove("missing");
+        assert_eq!(1, store.cases().len());
+        assert!(store.get("max").is_none());
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn json_roundtrip() {
+        let mut store = TestCaseStore::default();
+        store.insert("a".to_string(), "mod witness {}".to_string());
+        store.insert(
+            "b".to_string(),
+            "mod witness {\n    const FLAG: bool = true;\n}".to_string(),
+        );
+        let json = store.to_json();
+        assert_eq!(store, TestCaseStore::from_json(&json).unwrap());
+        assert!(TestCaseStore::from_json("not json").is_err());
+    }
+}
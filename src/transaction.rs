@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
+use elements::bitcoin;
 use elements::confidential;
 use elements::hashes::Hash;
+use hex_conservative::FromHex;
 use simfony::{elements, simplicity, SatisfiedProgram};
 use simplicity::jet::elements::{ElementsEnv, ElementsUtxo};
 
@@ -16,6 +18,17 @@ pub struct TxParams {
     pub fee: u64,
     pub lock_time: elements::LockTime,
     pub sequence: elements::Sequence,
+    pub version: u32,
+    /// Index of the spent input.
+    ///
+    /// Inputs before the spent input spend empty dummy UTXOs.
+    pub input_index: u32,
+    /// Script pubkey of the spent UTXO.
+    ///
+    /// Defaults to the address of the program if `None`.
+    pub script_pubkey: Option<elements::Script>,
+    /// BIP-341 annex of the spent input, including the `0x50` prefix.
+    pub annex: Option<Vec<u8>>,
 }
 
 impl Default for TxParams {
@@ -28,26 +41,45 @@ impl Default for TxParams {
             fee: 1_000,
             lock_time: elements::LockTime::from_consensus(0),
             sequence: elements::Sequence::from_consensus(0),
+            version: 2,
+            input_index: 0,
+            script_pubkey: None,
+            annex: None,
         }
     }
 }
 
 impl TxParams {
     fn unsatisfied_transaction(&self) -> elements::Transaction {
+        let dummy_input = |vout: u32| elements::TxIn {
+            previous_output: elements::OutPoint {
+                txid: elements::Txid::all_zeros(),
+                vout,
+            },
+            is_pegin: false,
+            script_sig: elements::Script::new(),
+            sequence: elements::Sequence::MAX,
+            asset_issuance: elements::AssetIssuance::null(),
+            witness: elements::TxInWitness::empty(),
+        };
+        let spent_input = elements::TxIn {
+            previous_output: elements::OutPoint {
+                txid: self.txid,
+                vout: self.vout,
+            },
+            is_pegin: false,
+            script_sig: elements::Script::new(),
+            sequence: self.sequence,
+            asset_issuance: elements::AssetIssuance::null(),
+            witness: elements::TxInWitness::empty(), // not required here
+        };
         elements::Transaction {
-            version: 2,
+            version: self.version,
             lock_time: self.lock_time,
-            input: vec![elements::TxIn {
-                previous_output: elements::OutPoint {
-                    txid: self.txid,
-                    vout: self.vout,
-                },
-                is_pegin: false,
-                script_sig: elements::Script::new(),
-                sequence: self.sequence,
-                asset_issuance: elements::AssetIssuance::null(),
-                witness: elements::TxInWitness::empty(), // not required here
-            }],
+            input: (0..self.input_index)
+                .map(dummy_input)
+                .chain(std::iter::once(spent_input))
+                .collect(),
             output: vec![
                 elements::TxOut {
                     asset: confidential::Asset::Explicit(util::liquid_testnet_bitcoin_asset()),
@@ -73,17 +105,55 @@ impl TxParams {
         }
     }
 
+    /// Populate the environment from the given input of a raw Bitcoin transaction.
+    ///
+    /// The value and script pubkey of the spent UTXO are not part of the transaction
+    /// and stay unchanged.
+    pub fn import_transaction(&mut self, tx_hex: &str, input_index: u32) -> Result<(), String> {
+        let bytes = Vec::<u8>::from_hex(tx_hex.trim()).map_err(|error| error.to_string())?;
+        let tx: bitcoin::Transaction =
+            bitcoin::consensus::deserialize(&bytes).map_err(|error| error.to_string())?;
+        let input = tx.input.get(input_index as usize).ok_or_else(|| {
+            format!(
+                "Input index {input_index} is out of range: the transaction has {} inputs",
+                tx.input.len()
+            )
+        })?;
+
+        self.version = tx.version.0 as u32;
+        self.lock_time = elements::LockTime::from_consensus(tx.lock_time.to_consensus_u32());
+        self.input_index = input_index;
+        self.txid = elements::Txid::from_byte_array(input.previous_output.txid.to_byte_array());
+        self.vout = input.previous_output.vout;
+        self.sequence = elements::Sequence::from_consensus(input.sequence.to_consensus_u32());
+        self.annex = input.witness.taproot_annex().map(<[u8]>::to_vec);
+        Ok(())
+    }
+
+    fn dummy_utxo() -> ElementsUtxo {
+        ElementsUtxo {
+            script_pubkey: elements::Script::new(),
+            asset: confidential::Asset::Explicit(util::liquid_testnet_bitcoin_asset()),
+            value: confidential::Value::Explicit(0),
+        }
+    }
+
     pub fn tx_env(&self, cmr: simplicity::Cmr) -> ElementsEnv<Arc<elements::Transaction>> {
-        let script_pubkey = util::liquid_testnet_address(cmr).script_pubkey();
-        let index = 0;
-        let annex = None;
+        let script_pubkey = self
+            .script_pubkey
+            .clone()
+            .unwrap_or_else(|| util::liquid_testnet_address(cmr).script_pubkey());
+        let utxos = (0..self.input_index)
+            .map(|_| Self::dummy_utxo())
+            .chain(std::iter::once(self.utxo(script_pubkey)))
+            .collect();
         ElementsEnv::new(
             Arc::new(self.unsatisfied_transaction()),
-            vec![self.utxo(script_pubkey)],
-            index,
+            utxos,
+            self.input_index,
             cmr,
             util::control_block(cmr),
-            annex,
+            self.annex.clone(),
             util::liquid_testnet_genesis(),
         )
     }
@@ -93,17 +163,70 @@ impl TxParams {
         let (simplicity_program_bytes, simplicity_witness_bytes) =
             satisfied.redeem().encode_to_vec();
         let cmr = satisfied.redeem().cmr();
-        tx.input[0].witness = elements::TxInWitness {
+        let mut script_witness = vec![
+            simplicity_witness_bytes,
+            simplicity_program_bytes,
+            cmr.as_ref().to_vec(),
+            util::control_block(cmr).serialize(),
+        ];
+        script_witness.extend(self.annex.clone());
+        tx.input[self.input_index as usize].witness = elements::TxInWitness {
             amount_rangeproof: None,
             inflation_keys_rangeproof: None,
-            script_witness: vec![
-                simplicity_witness_bytes,
-                simplicity_program_bytes,
-                cmr.as_ref().to_vec(),
-                util::control_block(cmr).serialize(),
-            ],
+            script_witness,
             pegin_witness: vec![],
         };
         tx
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_conservative::DisplayHex;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn import_bitcoin_transaction() {
+        let previous_output = bitcoin::OutPoint {
+            txid: bitcoin::Txid::from_byte_array([7; 32]),
+            vout: 3,
+        };
+        let annex = vec![0x50, 0x01, 0x02];
+        let input = |vout: u32, witness: bitcoin::Witness| bitcoin::TxIn {
+            previous_output: bitcoin::OutPoint {
+                vout,
+                ..previous_output
+            },
+            script_sig: bitcoin::ScriptBuf::new(),
+            sequence: bitcoin::Sequence::from_consensus(0xfffffffd),
+            witness,
+        };
+        let tx = bitcoin::Transaction {
+            version: bitcoin::transaction::Version::TWO,
+            lock_time: bitcoin::absolute::LockTime::from_consensus(800_000),
+            input: vec![
+                input(0, bitcoin::Witness::new()),
+                input(
+                    3,
+                    bitcoin::Witness::from_slice(&[vec![1; 64], annex.clone()]),
+                ),
+            ],
+            output: vec![],
+        };
+        let tx_hex = bitcoin::consensus::serialize(&tx).to_lower_hex_string();
+
+        let mut params = TxParams::default();
+        params.import_transaction(&tx_hex, 1).unwrap();
+        assert_eq!(2, params.version);
+        assert_eq!(800_000, params.lock_time.to_consensus_u32());
+        assert_eq!(1, params.input_index);
+        assert_eq!([7; 32], params.txid.to_byte_array());
+        assert_eq!(3, params.vout);
+        assert_eq!(0xfffffffd, params.sequence.to_consensus_u32());
+        assert_eq!(Some(annex), params.annex);
+
+        assert!(params.import_transaction(&tx_hex, 2).is_err());
+        assert!(params.import_transaction("00", 0).is_err());
+    }
+}
@@ -1,4 +1,6 @@
+use std::collections::HashSet;
 use std::fmt;
+use std::ops::Range;
 
 use elements::hashes::{sha256, Hash};
 use elements::secp256k1_zkp as secp256k1;
@@ -6,7 +8,7 @@ use secp256k1::rand::{self, Rng, SeedableRng};
 use simfony::num::U256;
 use simfony::simplicity::Preimage32;
 use simfony::{elements, simplicity};
-use simplicity::dag::{DagLike, MaxSharing, NoSharing};
+use simplicity::dag::{DagLike, InternalSharing, MaxSharing, NoSharing};
 use simplicity::jet::Elements;
 use simplicity::node::Inner;
 use simplicity::{node, RedeemNode};
@@ -115,6 +117,86 @@ pub fn get_compression_factor<M: node::Marker>(node: &node::Node<M>) -> usize {
     unshared_len / shared_len
 }
 
+/// Size and shape of a Simplicity program.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProgramMetrics {
+    /// Number of distinct nodes.
+    pub node_count: usize,
+    /// Number of distinct jets that are called.
+    pub jet_count: usize,
+    /// Number of distinct types of node inputs and outputs.
+    pub type_count: usize,
+    /// Length of the longest path from the root to a leaf.
+    ///
+    /// This is the maximum number of nodes on the stack of the step-by-step executor.
+    pub max_depth: usize,
+    /// Total length of the witness values in bits.
+    pub witness_bits: usize,
+}
+
+pub fn get_program_metrics(expression: &Expression) -> ProgramMetrics {
+    let mut depths: Vec<usize> = vec![];
+    let mut jets = HashSet::new();
+    let mut types = HashSet::new();
+    let mut witness_bits = 0;
+
+    for data in expression.post_order_iter::<InternalSharing>() {
+        let child_depth = [data.left_index, data.right_index]
+            .into_iter()
+            .flatten()
+            .map(|index| depths[index])
+            .max()
+            .unwrap_or(0);
+        depths.push(child_depth + 1);
+        let arrow = data.node.arrow();
+        match data.node.inner() {
+            Inner::Jet(jet) => {
+                jets.insert(*jet);
+            }
+            Inner::Witness(..) => witness_bits += arrow.target.bit_width(),
+            _ => {}
+        }
+        types.insert(arrow.source.tmr());
+        types.insert(arrow.target.tmr());
+    }
+
+    ProgramMetrics {
+        node_count: depths.len(),
+        jet_count: jets.len(),
+        type_count: types.len(),
+        max_depth: depths.last().copied().unwrap_or(0),
+        witness_bits,
+    }
+}
+
+/// Return the length of the compact size encoding of the given number, in bytes.
+fn compact_size_len(n: usize) -> usize {
+    match n {
+        0..=0xfc => 1,
+        0xfd..=0xffff => 3,
+        0x10000..=0xffff_ffff => 5,
+        _ => 9,
+    }
+}
+
+/// Return the weight of the witness that spends a Simplicity leaf of a tap tree.
+///
+/// The witness stack consists of the encoded witness, the encoded program,
+/// the CMR as leaf script and the control block with the given number of merkle proof hashes.
+/// Witness data weighs one weight unit per byte.
+pub fn spend_witness_weight(
+    program_bytes: usize,
+    witness_bytes: usize,
+    proof_depth: usize,
+) -> usize {
+    let items = [witness_bytes, program_bytes, 32, 33 + 32 * proof_depth];
+    compact_size_len(items.len())
+        + items
+            .into_iter()
+            .map(|len| compact_size_len(len) + len)
+            .sum::<usize>()
+}
+
 pub struct DisplayInner<'a, M: node::Marker>(&'a node::Node<M>);
 
 impl<'a, M: node::Marker> From<&'a node::Node<M>> for DisplayInner<'a, M> {
@@ -152,7 +234,7 @@ impl<'a, M: node::Marker> fmt::Debug for DisplayInner<'a, M> {
     }
 }
 
-fn unspendable_internal_key() -> secp256k1::XOnlyPublicKey {
+pub fn unspendable_internal_key() -> secp256k1::XOnlyPublicKey {
     secp256k1::XOnlyPublicKey::from_slice(&[
         0xf5, 0x91, 0x9f, 0xa6, 0x4c, 0xe4, 0x5f, 0x83, 0x06, 0x84, 0x90, 0x72, 0xb2, 0x6c, 0x1b,
         0xfd, 0xd2, 0x93, 0x7e, 0x6b, 0x81, 0x77, 0x47, 0x96, 0xff, 0x37, 0x2b, 0xd1, 0xeb, 0x53,
@@ -217,3 +299,205 @@ pub fn control_block(cmr: simplicity::Cmr) -> elements::taproot::ControlBlock {
     info.control_block(&script_ver)
         .expect("control block should exist")
 }
+
+/// Return the characters of the given program text that lie outside comments and string literals,
+/// together with their byte offsets.
+fn code_char_indices(text: &str) -> Vec<(usize, char)> {
+    let mut code = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '/' if text[offset..].starts_with("//") => {
+                while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+            }
+            '/' if text[offset..].starts_with("/*") => {
+                chars.next();
+                while let Some((offset, _)) = chars.next() {
+                    if text[offset..].starts_with("*/") {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            '"' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            _ => code.push((offset, c)),
+        }
+    }
+    code
+}
+
+/// Find the byte range of the `mod witness { ... }` block of the given program text.
+///
+/// Comments and string literals are skipped.
+fn witness_module_range(text: &str) -> Option<Range<usize>> {
+    let code = code_char_indices(text);
+    let position = code
+        .iter()
+        .position(|(offset, _)| text[*offset..].starts_with("mod witness"))?;
+    let start = code[position].0;
+    let mut depth = 0usize;
+    for &(offset, c) in &code[position..] {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 1 => return Some(start..offset + 1),
+            '}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Return the `mod witness { ... }` block of the given program text.
+pub fn witness_module(text: &str) -> Option<&str> {
+    witness_module_range(text).map(|range| &text[range])
+}
+
+/// Remove the `mod witness { ... }` block from the given program text.
+pub fn strip_witness_module(text: &str) -> String {
+    match witness_module_range(text) {
+        Some(range) => format!("{}{}", &text[..range.start], &text[range.end..]),
+        None => text.to_string(),
+    }
+}
+
+/// Replace the `mod witness { ... }` block of the given program text by the given witness module.
+///
+/// Prepend the witness module if the program text has none.
+pub fn replace_witness_module(text: &str, witness: &str) -> String {
+    match witness_module_range(text) {
+        Some(range) => format!("{}{witness}{}", &text[..range.start], &text[range.end..]),
+        None => format!("{witness}\n\n{text}"),
+    }
+}
+
+/// Replace the value of the witness with the given name in the witness module
+/// of the given program text by the given literal.
+///
+/// Return `None` if the witness module declares no such witness.
+pub fn replace_witness_value(text: &str, name: &str, literal: &str) -> Option<String> {
+    let range = witness_module_range(text)?;
+    let module = &text[range.clone()];
+    let code = code_char_indices(module);
+    let declaration = code.iter().map(|(offset, _)| *offset).find(|offset| {
+        module[*offset..]
+            .strip_prefix("const")
+            .and_then(|rest| rest.trim_start().strip_prefix(name))
+            .is_some_and(|rest| rest.trim_start().starts_with(':'))
+    })?;
+    let value_start = code
+        .iter()
+        .find(|(offset, c)| declaration < *offset && *c == '=')?
+        .0
+        + 1;
+    let value_end = code
+        .iter()
+        .find(|(offset, c)| value_start < *offset && *c == ';')?
+        .0;
+    let module_start = range.start;
+    Some(format!(
+        "{} {literal}{}",
+        &text[..module_start + value_start],
+        &text[module_start + value_end..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn program_metrics() {
+        let text = "fn main() {\n    assert!(jet::eq_8(1, 1));\n    assert!(jet::eq_8(2, 2));\n}";
+        let satisfied = simfony::CompiledProgram::new(text, simfony::Arguments::default())
+            .and_then(|compiled| compiled.satisfy(simfony::WitnessValues::default()))
+            .expect("program should compile");
+        let metrics = get_program_metrics(satisfied.redeem());
+
+        assert_eq!(
+            satisfied
+                .redeem()
+                .post_order_iter::<InternalSharing>()
+                .count(),
+            metrics.node_count
+        );
+        assert_eq!(1, metrics.jet_count);
+        // The unit type and the types of the jet arguments
+        assert!(2 <= metrics.type_count);
+        assert!(1 < metrics.max_depth && metrics.max_depth <= metrics.node_count);
+        assert_eq!(0, metrics.witness_bits);
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn witness_weight() {
+        // 1 item count + (1 + 10) + (1 + 100) + (1 + 32) + (1 + 33)
+        assert_eq!(180, spend_witness_weight(100, 10, 0));
+        assert_eq!(180 + 2 * 32, spend_witness_weight(100, 10, 2));
+        // Items of 253 bytes or more need a longer length prefix
+        assert_eq!(
+            spend_witness_weight(252, 0, 0) + 1 + 2,
+            spend_witness_weight(253, 0, 0)
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn witness_module_text() {
+        let text = "mod witness {\n    const X: u8 = 1;\n}\n\nfn main() {}";
+        assert_eq!("\n\nfn main() {}", strip_witness_module(text));
+        assert_eq!("fn main() {}", strip_witness_module("fn main() {}"));
+        assert_eq!(
+            Some("mod witness {\n    const X: u8 = 1;\n}"),
+            witness_module(text)
+        );
+        assert_eq!(None, witness_module("fn main() {}"));
+        assert_eq!(
+            "mod witness {}\n\nfn main() {}",
+            replace_witness_module(text, "mod witness {}")
+        );
+        assert_eq!(
+            "mod witness {}\n\nfn main() {}",
+            replace_witness_module("fn main() {}", "mod witness {}")
+        );
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn witness_value_text() {
+        let text = "mod witness {\n    const XY: u8 = 2;\n    const X: u8 = 1;\n}\n\nfn main() {}";
+        assert_eq!(
+            Some(
+                "mod witness {\n    const XY: u8 = 2;\n    const X: u8 = 0xff;\n}\n\nfn main() {}"
+            ),
+            replace_witness_value(text, "X", "0xff").as_deref()
+        );
+        assert_eq!(None, replace_witness_value(text, "Y", "0xff"));
+        assert_eq!(None, replace_witness_value("fn main() {}", "X", "0xff"));
+    }
+
+    #[test]
+    #[wasm_bindgen_test::wasm_bindgen_test]
+    fn witness_module_skips_comments() {
+        let text = "// mod witness { }\n/* mod witness { */\nmod witness {\n    // const X: u8 = 2; }\n    const X: u8 = 1; /* ; } */\n}\n\nfn main() {}";
+        assert_eq!(
+            Some("mod witness {\n    // const X: u8 = 2; }\n    const X: u8 = 1; /* ; } */\n}"),
+            witness_module(text)
+        );
+        assert_eq!(
+            Some("// mod witness { }\n/* mod witness { */\nmod witness {\n    // const X: u8 = 2; }\n    const X: u8 = 0xff; /* ; } */\n}\n\nfn main() {}"),
+            replace_witness_value(text, "X", "0xff").as_deref()
+        );
+        assert_eq!(None, witness_module("// mod witness {}\nfn main() {}"));
+    }
+}